@@ -0,0 +1,92 @@
+use clap::Args;
+use common::{
+    config::Config,
+    crypt::{self, InputKey, KEY_ENV_VAR},
+    registry::Registry,
+};
+use std::{fs, process};
+
+#[derive(Args)]
+pub struct EncryptArgs {
+    /// Delete each plaintext input once its encrypted version has been written.
+    #[arg(long)]
+    remove_plaintext: bool,
+}
+
+/// Writes an encrypted copy of every registered puzzle's plaintext input, next to it.
+/// Inputs whose encrypted copy is already up to date are left alone, so that re-running
+/// this doesn't touch files that haven't changed.
+pub fn encrypt_inputs(registry: &Registry, year: Option<u16>, config: &Config, args: &EncryptArgs) {
+    let Some(key) = InputKey::load(config) else {
+        eprintln!("no key was given (set {KEY_ENV_VAR} or `input_key` in the config)");
+        process::exit(1);
+    };
+
+    let ids = registry
+        .entries()
+        .filter(|entry| year.is_none_or(|year| entry.year == year))
+        .filter_map(|entry| entry.id());
+
+    for id in ids {
+        let path = config.input_path(id);
+        let Ok(plaintext) = fs::read_to_string(&path) else {
+            continue;
+        };
+
+        let encrypted = crypt::encrypted_path(&path);
+        let up_to_date = fs::read(&encrypted)
+            .ok()
+            .and_then(|data| key.decrypt(&data))
+            .is_some_and(|existing| existing == plaintext);
+
+        if up_to_date {
+            println!("{id}: {} is up to date", encrypted.display());
+        } else {
+            fs::write(&encrypted, key.encrypt(&plaintext))
+                .unwrap_or_else(|err| panic!("could not write {}: {err}", encrypted.display()));
+            println!("{id}: wrote {}", encrypted.display());
+        }
+
+        if args.remove_plaintext {
+            fs::remove_file(&path)
+                .unwrap_or_else(|err| panic!("could not remove {}: {err}", path.display()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::registry::registry;
+    use common::{
+        config::Config,
+        crypt::{self, InputKey},
+    };
+    use std::fs;
+
+    #[test]
+    fn test_encrypted_inputs_decrypt() {
+        // a broken personal config is no reason to fail the suite, so it counts as no key
+        let Ok(config) = Config::load() else {
+            eprintln!("skipping: could not load the config");
+            return;
+        };
+        let Some(key) = InputKey::load(&config) else {
+            eprintln!("skipping: no input key");
+            return;
+        };
+
+        for id in registry().entries().filter_map(|entry| entry.id()) {
+            let path = config.input_path(id);
+            let Ok(data) = fs::read(crypt::encrypted_path(&path)) else {
+                continue;
+            };
+
+            let decrypted = key
+                .decrypt(&data)
+                .unwrap_or_else(|| panic!("{id}: could not decrypt the input"));
+            if let Ok(plaintext) = fs::read_to_string(&path) {
+                assert_eq!(decrypted, plaintext, "{id}: encrypted input is stale");
+            }
+        }
+    }
+}
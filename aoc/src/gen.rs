@@ -0,0 +1,82 @@
+use clap::Args;
+use common::{registry::Registry, rng::Rng};
+use std::process;
+
+#[derive(Args)]
+pub struct GenArgs {
+    /// The day of the puzzle to generate an input for.
+    day: String,
+
+    /// How big of an input to generate. What this counts depends on the puzzle,
+    /// eg. lines for some, or the width of the grid for others.
+    #[arg(long, default_value_t = 100)]
+    size: usize,
+
+    /// The seed for the random number generator. The same seed and size always
+    /// produce the same input.
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+}
+
+/// Prints a random input for a puzzle, for running its solvers on inputs much larger
+/// than the real ones.
+pub fn gen(registry: &Registry, year: u16, args: &GenArgs) {
+    let Some(entry) = registry.find(year, &args.day) else {
+        eprintln!("No puzzle is registered for {year} day {}.", args.day);
+        process::exit(1);
+    };
+    let Some(generate) = entry.generate else {
+        eprintln!(
+            "Puzzle {year}/{} doesn't have an input generator.",
+            entry.name
+        );
+        process::exit(1);
+    };
+
+    println!("{}", generate(args.size, &mut Rng::new(args.seed)));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::registry::registry;
+    use common::{
+        puzzle::{PuzzlePartName, Status},
+        rng::Rng,
+    };
+
+    #[test]
+    fn test_generated_inputs_are_deterministic() {
+        for entry in registry().entries() {
+            let Some(generate) = entry.generate else {
+                continue;
+            };
+            assert_eq!(
+                generate(10, &mut Rng::new(3)),
+                generate(10, &mut Rng::new(3)),
+                "generator for {} isn't deterministic",
+                entry.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_generated_inputs_can_be_solved() {
+        for entry in registry().entries() {
+            let Some(generate) = entry.generate else {
+                continue;
+            };
+
+            for seed in 0..10 {
+                let input = generate(12, &mut Rng::new(seed));
+                for part in [PuzzlePartName::A, PuzzlePartName::B] {
+                    let part_entry = entry.part(part);
+                    if part_entry.status != Status::Done {
+                        continue;
+                    }
+                    // solvers panic on inputs they can't make sense of
+                    (part_entry.solve)(&input);
+                }
+            }
+        }
+    }
+}
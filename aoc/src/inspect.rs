@@ -0,0 +1,395 @@
+use clap::Args;
+use common::{
+    config::Config,
+    crypt::{self, InputError, InputKey},
+    id::PuzzleId,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    process,
+};
+
+#[derive(Args)]
+pub struct InspectArgs {
+    /// The day of the puzzle whose input should be inspected.
+    day: u32,
+
+    /// Inspect the example input instead of the primary one.
+    #[arg(long = "example", short = 'e')]
+    use_example: bool,
+}
+
+/// Prints a summary of a puzzle's input, to get a feel for it before writing a parser.
+pub fn inspect(year: u16, config: &Config, args: &InspectArgs) {
+    let id = PuzzleId::new(year, args.day);
+    let path = if args.use_example {
+        config.example_path(id)
+    } else {
+        config.input_path(id)
+    };
+
+    let key = InputKey::load(config);
+    let input = crypt::read_input(&path, key.as_ref()).unwrap_or_else(|err| {
+        match err {
+            InputError::NotFound(_) if !args.use_example => {
+                eprintln!("{err} (download it from {})", config.input_url(id));
+            }
+            err => eprintln!("{err}"),
+        }
+        process::exit(1);
+    });
+
+    let profile = InputProfile::of(&input);
+    println!("{}", path.display());
+    for line in profile.report() {
+        println!("{line}");
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Shape {
+    Empty,
+    /// Every line has the same width and there are no spaces.
+    Grid {
+        rows: usize,
+        cols: usize,
+    },
+    /// Each blank-line-separated block is a grid of its own.
+    Grids {
+        count: usize,
+    },
+    Ragged {
+        min_width: usize,
+        max_width: usize,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct NumberStats {
+    count: usize,
+    min: i128,
+    max: i128,
+}
+
+impl NumberStats {
+    fn max_magnitude(&self) -> u128 {
+        self.min.unsigned_abs().max(self.max.unsigned_abs())
+    }
+
+    /// Which of the usual integer types can hold every number.
+    fn fits_in(&self) -> Vec<&'static str> {
+        let types = [
+            ("i32", i32::MIN.into(), i32::MAX.into()),
+            ("u32", 0, u32::MAX.into()),
+            ("i64", i64::MIN.into(), i64::MAX.into()),
+            ("u64", 0, u64::MAX.into()),
+        ];
+
+        types
+            .into_iter()
+            .filter(|(_, min, max)| *min <= self.min && self.max <= *max)
+            .map(|(name, _, _)| name)
+            .collect()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct LinePattern {
+    template: String,
+    matching: usize,
+}
+
+#[derive(Debug)]
+struct InputProfile {
+    lines: usize,
+    blocks: usize,
+    shape: Shape,
+    histogram: Vec<(char, usize)>,
+    numbers: Option<NumberStats>,
+    pattern: Option<LinePattern>,
+}
+
+impl InputProfile {
+    fn of(input: &str) -> Self {
+        let lines: Vec<&str> = input.lines().collect();
+        let blocks: Vec<Vec<&str>> = input
+            .split("\n\n")
+            .map(|block| block.lines().collect::<Vec<_>>())
+            .filter(|block| !block.is_empty())
+            .collect();
+
+        let shape = shape(&lines, &blocks);
+        // the lines of a grid don't have a format worth guessing at
+        let pattern = match shape {
+            Shape::Grid { .. } | Shape::Grids { .. } => None,
+            _ => pattern(&lines),
+        };
+
+        Self {
+            lines: lines.len(),
+            blocks: blocks.len(),
+            shape,
+            histogram: histogram(input),
+            numbers: numbers(input),
+            pattern,
+        }
+    }
+
+    fn report(&self) -> Vec<String> {
+        let mut report = vec![
+            format!("lines:    {}", self.lines),
+            format!("blocks:   {}", self.blocks),
+        ];
+
+        report.push(match self.shape {
+            Shape::Empty => "shape:    empty".into(),
+            Shape::Grid { rows, cols } => format!("shape:    grid, {rows} rows x {cols} columns"),
+            Shape::Grids { count } => format!("shape:    {count} separate grids"),
+            Shape::Ragged {
+                min_width,
+                max_width,
+            } => format!("shape:    lines are {min_width} to {max_width} characters wide"),
+        });
+
+        match &self.numbers {
+            Some(numbers) => {
+                report.push(format!(
+                    "numbers:  {} from {} to {} (largest magnitude {})",
+                    numbers.count,
+                    numbers.min,
+                    numbers.max,
+                    numbers.max_magnitude()
+                ));
+                let fits_in = numbers.fits_in();
+                report.push(if fits_in.is_empty() {
+                    "fits in:  none of i32, u32, i64 or u64".into()
+                } else {
+                    format!("fits in:  {}", fits_in.join(", "))
+                });
+            }
+            None => report.push("numbers:  none".into()),
+        }
+
+        if let Some(LinePattern { template, matching }) = &self.pattern {
+            report.push(format!(
+                "pattern:  {template:?} ({matching} of {} lines)",
+                self.lines
+            ));
+        }
+
+        report.push("characters:".into());
+        for (c, count) in &self.histogram {
+            report.push(format!("  {:>6}  {count}", describe_char(*c)));
+        }
+
+        report
+    }
+}
+
+fn shape(lines: &[&str], blocks: &[Vec<&str>]) -> Shape {
+    let width = |line: &&str| line.chars().count();
+    let is_grid = |lines: &[&str]| {
+        lines.iter().all(|line| !line.contains(' '))
+            && lines.iter().all(|line| width(line) == width(&lines[0]))
+    };
+
+    let non_empty: Vec<&str> = lines
+        .iter()
+        .copied()
+        .filter(|line| !line.is_empty())
+        .collect();
+    if non_empty.is_empty() {
+        Shape::Empty
+    } else if blocks.len() == 1 && is_grid(&non_empty) {
+        Shape::Grid {
+            rows: non_empty.len(),
+            cols: width(&non_empty[0]),
+        }
+    } else if blocks.len() > 1 && blocks.iter().all(|block| is_grid(block)) {
+        Shape::Grids {
+            count: blocks.len(),
+        }
+    } else {
+        Shape::Ragged {
+            min_width: non_empty.iter().map(width).min().unwrap(),
+            max_width: non_empty.iter().map(width).max().unwrap(),
+        }
+    }
+}
+
+/// Counts each character other than newlines, most common first.
+fn histogram(input: &str) -> Vec<(char, usize)> {
+    let mut counts: BTreeMap<char, usize> = BTreeMap::new();
+    for c in input.chars().filter(|c| *c != '\n') {
+        *counts.entry(c).or_default() += 1;
+    }
+
+    let mut counts: Vec<_> = counts.into_iter().collect();
+    counts.sort_by(|(_, count1), (_, count2)| count2.cmp(count1));
+    counts
+}
+
+/// Finds every run of digits, treating a `-` right before one as a sign
+/// unless it joins two words (eg. "seed-to-soil").
+fn numbers(input: &str) -> Option<NumberStats> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut values = vec![];
+
+    let mut idx = 0;
+    while idx < chars.len() {
+        if !chars[idx].is_ascii_digit() {
+            idx += 1;
+            continue;
+        }
+
+        let start = idx;
+        while idx < chars.len() && chars[idx].is_ascii_digit() {
+            idx += 1;
+        }
+        let digits: String = chars[start..idx].iter().collect();
+
+        let is_negative = start > 0
+            && chars[start - 1] == '-'
+            && (start < 2 || !chars[start - 2].is_alphanumeric());
+        // anything too big for an i128 is well past overflowing anyway
+        let value = digits.parse::<i128>().unwrap_or(i128::MAX);
+        values.push(if is_negative { -value } else { value });
+    }
+
+    Some(NumberStats {
+        count: values.len(),
+        min: *values.iter().min()?,
+        max: *values.iter().max()?,
+    })
+}
+
+/// Guesses at the format of the lines by abstracting away the numbers in them, and then the
+/// words too if that isn't enough to make at least half of the lines look alike.
+fn pattern(lines: &[&str]) -> Option<LinePattern> {
+    let most_common = |abstract_words: bool| {
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for line in lines.iter().filter(|line| !line.is_empty()) {
+            *counts.entry(template(line, abstract_words)).or_default() += 1;
+        }
+
+        counts
+            .into_iter()
+            .max_by(|(template1, count1), (template2, count2)| {
+                count1.cmp(count2).then(template2.cmp(template1))
+            })
+            .map(|(template, matching)| LinePattern { template, matching })
+    };
+
+    let pattern = most_common(false)?;
+    if pattern.matching * 2 >= lines.len() {
+        Some(pattern)
+    } else {
+        most_common(true)
+    }
+}
+
+/// Replaces each number with `#`, and each run of letters with `a` if `abstract_words` is set.
+fn template(line: &str, abstract_words: bool) -> String {
+    let mut template = String::new();
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        let is_sign = c == '-' && chars.peek().is_some_and(char::is_ascii_digit);
+        let replacement = if c.is_ascii_digit() || is_sign {
+            '#'
+        } else if abstract_words && c.is_alphabetic() {
+            'a'
+        } else {
+            template.push(c);
+            continue;
+        };
+
+        if !template.ends_with(replacement) {
+            template.push(replacement);
+        }
+    }
+    template
+}
+
+fn describe_char(c: char) -> String {
+    match c {
+        ' ' => "space".into(),
+        '\t' => "tab".into(),
+        '\r' => "\\r".into(),
+        c => format!("'{c}'"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_grid() {
+        let profile = InputProfile::of("#.#\n..#\n");
+
+        assert_eq!(profile.lines, 2);
+        assert_eq!(profile.blocks, 1);
+        assert_eq!(profile.shape, Shape::Grid { rows: 2, cols: 3 });
+        assert_eq!(profile.histogram, vec![('#', 3), ('.', 3)]);
+        assert_eq!(profile.numbers, None);
+    }
+
+    #[test]
+    fn test_profile_blocks_of_grids() {
+        let profile = InputProfile::of("#.\n.#\n\n###\n#..\n...\n");
+
+        assert_eq!(profile.blocks, 2);
+        assert_eq!(profile.shape, Shape::Grids { count: 2 });
+    }
+
+    #[test]
+    fn test_numbers() {
+        let stats = numbers("seed-to-soil map:\n-3 50 98\n5000000000, -12").unwrap();
+
+        assert_eq!(
+            stats,
+            NumberStats {
+                count: 5,
+                min: -12,
+                max: 5_000_000_000,
+            }
+        );
+        assert_eq!(stats.max_magnitude(), 5_000_000_000);
+        assert_eq!(stats.fits_in(), vec!["i64"]);
+
+        let stats = numbers("3000000000 1").unwrap();
+        assert_eq!(stats.fits_in(), vec!["u32", "i64", "u64"]);
+    }
+
+    #[test]
+    fn test_pattern() {
+        let lines = [
+            "Game 1: 3 blue, 4 red",
+            "Game 2: 1 red, 2 green",
+            "Game 13: 8 green, 6 blue",
+        ];
+        assert_eq!(
+            pattern(&lines),
+            Some(LinePattern {
+                template: "a #: # a, # a".into(),
+                matching: 3,
+            })
+        );
+
+        let lines = [
+            "px{a<2006:qkq,m>2090:A,rfg}",
+            "pv{a>1716:R,A}",
+            "in{s<1351:px,qqz}",
+        ];
+        assert_eq!(pattern(&lines).unwrap().matching, 1);
+
+        let lines = ["0 3 -6 9", "1 3 6 10", "10 13 16"];
+        assert_eq!(
+            pattern(&lines),
+            Some(LinePattern {
+                template: "# # # #".into(),
+                matching: 2,
+            })
+        );
+    }
+}
@@ -0,0 +1,137 @@
+use clap::Args;
+use common::{
+    puzzle::{Status, Tag},
+    registry::{PuzzleEntry, Registry},
+};
+
+#[derive(Args)]
+pub struct ListArgs {
+    /// Only list puzzles with this tag.
+    #[arg(long)]
+    tag: Option<Tag>,
+
+    /// Only list puzzles with at least one part in this state.
+    #[arg(long)]
+    status: Option<Status>,
+}
+
+/// Prints a table of the registered puzzles that match the filters in `args`.
+/// Puzzles from every year are listed unless `year` is given.
+pub fn list(registry: &Registry, year: Option<u16>, args: &ListArgs) {
+    let entries: Vec<_> = registry
+        .entries()
+        .filter(|entry| year.is_none_or(|year| entry.year == year))
+        .filter(|entry| matches(entry, args))
+        .collect();
+
+    if entries.is_empty() {
+        println!("No matching puzzles.");
+        return;
+    }
+
+    let rows: Vec<[String; 6]> = entries
+        .iter()
+        .map(|entry| {
+            [
+                entry.year.to_string(),
+                entry.name.to_string(),
+                entry.title.to_string(),
+                entry.part_a.status.to_string(),
+                entry.part_b.status.to_string(),
+                entry
+                    .tags
+                    .iter()
+                    .map(Tag::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ]
+        })
+        .collect();
+
+    let header = ["Year", "Day", "Title", "A", "B", "Tags"].map(String::from);
+    for line in format_table(&header, &rows) {
+        println!("{line}");
+    }
+}
+
+fn matches(entry: &PuzzleEntry, args: &ListArgs) -> bool {
+    let tag_matches = args.tag.is_none_or(|tag| entry.tags.contains(&tag));
+    let status_matches = args
+        .status
+        .is_none_or(|status| entry.part_a.status == status || entry.part_b.status == status);
+
+    tag_matches && status_matches
+}
+
+/// Lays out rows in left-aligned columns that are as wide as their widest cell.
+fn format_table<const N: usize>(header: &[String; N], rows: &[[String; N]]) -> Vec<String> {
+    let widths: Vec<usize> = (0..N)
+        .map(|col| {
+            rows.iter()
+                .chain([header])
+                .map(|row| row[col].chars().count())
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    [header]
+        .into_iter()
+        .chain(rows)
+        .map(|row| {
+            row.iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{cell:width$}"))
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::registry;
+
+    fn matching_days(args: &ListArgs) -> Vec<&'static str> {
+        registry()
+            .entries()
+            .filter(|entry| matches(entry, args))
+            .map(|entry| entry.name)
+            .collect()
+    }
+
+    #[test]
+    fn test_filter_by_tag() {
+        let args = ListArgs {
+            tag: Some(Tag::Intervals),
+            status: None,
+        };
+        assert_eq!(matching_days(&args), vec!["05", "19"]);
+    }
+
+    #[test]
+    fn test_filter_by_status() {
+        let args = ListArgs {
+            tag: None,
+            status: Some(Status::Todo),
+        };
+        assert_eq!(matching_days(&args), vec!["24"]);
+    }
+
+    #[test]
+    fn test_format_table() {
+        let header = ["Day", "Title"].map(String::from);
+        let rows = [
+            ["1", "Short"].map(String::from),
+            ["10", "A bit longer"].map(String::from),
+        ];
+
+        assert_eq!(
+            format_table(&header, &rows),
+            vec!["Day  Title", "1    Short", "10   A bit longer"]
+        );
+    }
+}
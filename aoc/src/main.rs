@@ -0,0 +1,79 @@
+use clap::{Parser, Subcommand};
+use common::{config::Config, puzzle::DEFAULT_YEAR};
+use std::process;
+
+mod encrypt;
+mod gen;
+mod inspect;
+mod list;
+mod parse;
+mod registry;
+mod serve;
+
+#[derive(Parser)]
+#[command(about = "Runs the Advent of Code puzzle solvers in this workspace")]
+struct Cli {
+    /// The year of the event to look puzzles up in (2023 if not given).
+    #[arg(long, global = true)]
+    year: Option<u16>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List the registered puzzles, optionally filtered by tag or status.
+    List(list::ListArgs),
+
+    /// Summarize a puzzle's input: its shape, characters, numbers and line format.
+    Inspect(inspect::InspectArgs),
+
+    /// Check that a puzzle's input parses, or dump the parsed model with `--json`.
+    Parse(parse::ParseArgs),
+
+    /// Print a random input for a puzzle, for testing solvers at scale.
+    Gen(gen::GenArgs),
+
+    /// Encrypt each puzzle's input, so that it can be committed without publishing it.
+    EncryptInputs(encrypt::EncryptArgs),
+
+    /// Start a local HTTP server that solves puzzles on request.
+    Serve(serve::ServeArgs),
+}
+
+fn main() {
+    let cli = Cli::parse();
+    let config = Config::load().unwrap_or_else(|err| {
+        eprintln!("{err}");
+        process::exit(1);
+    });
+    config.color.apply();
+
+    match cli.command {
+        Command::List(args) => list::list(&registry::registry(), cli.year, &args),
+        Command::Inspect(args) => {
+            inspect::inspect(cli.year.unwrap_or(DEFAULT_YEAR), &config, &args)
+        }
+        Command::Parse(args) => parse::parse(
+            &registry::registry(),
+            cli.year.unwrap_or(DEFAULT_YEAR),
+            &config,
+            &args,
+        ),
+        Command::Gen(args) => gen::gen(
+            &registry::registry(),
+            cli.year.unwrap_or(DEFAULT_YEAR),
+            &args,
+        ),
+        Command::EncryptInputs(args) => {
+            encrypt::encrypt_inputs(&registry::registry(), cli.year, &config, &args)
+        }
+        Command::Serve(args) => serve::serve(
+            registry::registry(),
+            cli.year.unwrap_or(DEFAULT_YEAR),
+            &config,
+            &args,
+        ),
+    }
+}
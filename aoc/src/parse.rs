@@ -0,0 +1,80 @@
+use clap::Args;
+use common::{
+    config::Config,
+    crypt::{self, InputKey},
+    registry::Registry,
+};
+use std::process;
+
+#[derive(Args)]
+pub struct ParseArgs {
+    /// The day of the puzzle whose input should be parsed.
+    day: String,
+
+    /// Print the parsed model as JSON, rather than just checking that the input parses.
+    #[arg(long)]
+    json: bool,
+
+    /// Parse the example input instead of the primary one.
+    #[arg(long = "example", short = 'e')]
+    use_example: bool,
+}
+
+/// Runs a puzzle's parser on its input, pointing out where it fails if it does.
+pub fn parse(registry: &Registry, year: u16, config: &Config, args: &ParseArgs) {
+    let Some(entry) = registry.find(year, &args.day) else {
+        eprintln!("No puzzle is registered for {year} day {}.", args.day);
+        process::exit(1);
+    };
+    let (Some(id), Some(to_json)) = (entry.id(), entry.to_json) else {
+        eprintln!(
+            "Puzzle {year}/{}'s parsed input can't be serialized.",
+            entry.name
+        );
+        process::exit(1);
+    };
+
+    let path = if args.use_example {
+        config.example_path(id)
+    } else {
+        config.input_path(id)
+    };
+    let key = InputKey::load(config);
+    let input = crypt::read_input(&path, key.as_ref()).unwrap_or_else(|err| {
+        eprintln!("{err}");
+        process::exit(1);
+    });
+
+    match to_json(&input) {
+        Ok(parsed) if args.json => println!(
+            "{}",
+            serde_json::to_string_pretty(&parsed).expect("JSON values should be printable")
+        ),
+        Ok(_) => println!("{} parsed successfully.", path.display()),
+        Err(err) => {
+            eprintln!("{}", err.render(&input, &path.display().to_string()));
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::registry::registry;
+    use common::rng::Rng;
+
+    #[test]
+    fn test_generated_inputs_serialize() {
+        for entry in registry().entries() {
+            let (Some(generate), Some(to_json)) = (entry.generate, entry.to_json) else {
+                continue;
+            };
+            let input = generate(12, &mut Rng::new(0));
+
+            let parsed = to_json(&input).unwrap_or_else(|err| {
+                panic!("puzzle {}: {}", entry.name, err.render(&input, "input"))
+            });
+            assert!(!parsed.is_null(), "puzzle {}", entry.name);
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use common::registry::{PuzzleEntry, Registry};
+
+/// Every puzzle in the workspace. New days need to be added here
+/// (and to this crate's dependencies) to be reachable from the runner.
+pub fn registry() -> Registry {
+    Registry::new(vec![
+        PuzzleEntry::of::<puzzle01::Puzzle01>()
+            .with_generator::<puzzle01::Puzzle01>()
+            .with_json::<puzzle01::Puzzle01>()
+            .with_printer::<puzzle01::Puzzle01>(),
+        PuzzleEntry::of::<puzzle02::Puzzle02>()
+            .with_generator::<puzzle02::Puzzle02>()
+            .with_json::<puzzle02::Puzzle02>()
+            .with_printer::<puzzle02::Puzzle02>(),
+        PuzzleEntry::of::<puzzle03::Puzzle03>()
+            .with_generator::<puzzle03::Puzzle03>()
+            .with_json::<puzzle03::Puzzle03>()
+            .with_printer::<puzzle03::Puzzle03>(),
+        PuzzleEntry::of::<puzzle04::Puzzle04>()
+            .with_generator::<puzzle04::Puzzle04>()
+            .with_json::<puzzle04::Puzzle04>()
+            .with_printer::<puzzle04::Puzzle04>(),
+        PuzzleEntry::of::<puzzle05::Puzzle05>()
+            .with_generator::<puzzle05::Puzzle05>()
+            .with_json::<puzzle05::Puzzle05>()
+            .with_printer::<puzzle05::Puzzle05>(),
+        PuzzleEntry::of::<puzzle06::Puzzle06>()
+            .with_generator::<puzzle06::Puzzle06>()
+            .with_json::<puzzle06::Puzzle06>()
+            .with_printer::<puzzle06::Puzzle06>(),
+        PuzzleEntry::of::<puzzle07::Puzzle07>()
+            .with_generator::<puzzle07::Puzzle07>()
+            .with_json::<puzzle07::Puzzle07>()
+            .with_printer::<puzzle07::Puzzle07>(),
+        PuzzleEntry::of::<puzzle08::Puzzle08>()
+            .with_generator::<puzzle08::Puzzle08>()
+            .with_json::<puzzle08::Puzzle08>()
+            .with_printer::<puzzle08::Puzzle08>(),
+        PuzzleEntry::of::<puzzle09::Puzzle09>()
+            .with_generator::<puzzle09::Puzzle09>()
+            .with_json::<puzzle09::Puzzle09>()
+            .with_printer::<puzzle09::Puzzle09>(),
+        PuzzleEntry::of::<puzzle10::Puzzle10>()
+            .with_generator::<puzzle10::Puzzle10>()
+            .with_json::<puzzle10::Puzzle10>()
+            .with_printer::<puzzle10::Puzzle10>(),
+        PuzzleEntry::of::<puzzle11::Puzzle11>()
+            .with_generator::<puzzle11::Puzzle11>()
+            .with_json::<puzzle11::Puzzle11>()
+            .with_printer::<puzzle11::Puzzle11>(),
+        PuzzleEntry::of::<puzzle12::Puzzle12>()
+            .with_generator::<puzzle12::Puzzle12>()
+            .with_json::<puzzle12::Puzzle12>()
+            .with_printer::<puzzle12::Puzzle12>(),
+        PuzzleEntry::of::<puzzle13::Puzzle13>()
+            .with_generator::<puzzle13::Puzzle13>()
+            .with_json::<puzzle13::Puzzle13>()
+            .with_printer::<puzzle13::Puzzle13>(),
+        PuzzleEntry::of::<puzzle14::Puzzle14>()
+            .with_generator::<puzzle14::Puzzle14>()
+            .with_json::<puzzle14::Puzzle14>()
+            .with_printer::<puzzle14::Puzzle14>(),
+        PuzzleEntry::of::<puzzle15::Puzzle15>()
+            .with_generator::<puzzle15::Puzzle15>()
+            .with_json::<puzzle15::Puzzle15>()
+            .with_printer::<puzzle15::Puzzle15>(),
+        PuzzleEntry::of::<puzzle16::Puzzle16>()
+            .with_generator::<puzzle16::Puzzle16>()
+            .with_json::<puzzle16::Puzzle16>()
+            .with_printer::<puzzle16::Puzzle16>(),
+        PuzzleEntry::of::<puzzle19::Puzzle19>()
+            .with_generator::<puzzle19::Puzzle19>()
+            .with_json::<puzzle19::Puzzle19>()
+            .with_printer::<puzzle19::Puzzle19>(),
+        PuzzleEntry::of::<puzzle24::Puzzle24>()
+            .with_generator::<puzzle24::Puzzle24>()
+            .with_json::<puzzle24::Puzzle24>()
+            .with_printer::<puzzle24::Puzzle24>(),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::rng::Rng;
+
+    #[test]
+    fn test_ids_are_unique() {
+        let registry = registry();
+        let mut ids: Vec<_> = registry.entries().map(|entry| entry.id()).collect();
+        let count = ids.len();
+        ids.sort();
+        ids.dedup();
+
+        assert!(ids.iter().all(Option::is_some));
+        assert_eq!(ids.len(), count);
+    }
+
+    #[test]
+    fn test_every_part_has_a_description() {
+        for entry in registry().entries() {
+            assert!(!(entry.part_a.description)().is_empty());
+            assert!(!(entry.part_b.description)().is_empty());
+        }
+    }
+
+    #[test]
+    fn test_printed_inputs_parse_the_same() {
+        // models don't all implement `PartialEq`, but every printable one can be
+        // compared through its JSON
+        for entry in registry().entries() {
+            let (Some(generate), Some(reprint), Some(to_json)) =
+                (entry.generate, entry.reprint, entry.to_json)
+            else {
+                continue;
+            };
+
+            for size in 1..13 {
+                let input = generate(size, &mut Rng::new(size as u64));
+                let printed = reprint(&input).unwrap();
+                assert_eq!(
+                    to_json(&printed).unwrap(),
+                    to_json(&input).unwrap(),
+                    "puzzle {} printed an input of size {size} as:\n{printed}",
+                    entry.name
+                );
+            }
+        }
+    }
+}
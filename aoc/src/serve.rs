@@ -0,0 +1,446 @@
+use clap::Args;
+use common::{
+    config::Config,
+    puzzle::PuzzlePartName,
+    registry::{PartEntry, Registry},
+};
+use serde::Serialize;
+use std::{
+    any::Any,
+    io::{Cursor, Read},
+    panic, process,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+#[derive(Args)]
+pub struct ServeArgs {
+    /// The port to listen on. The server only ever binds to localhost.
+    #[arg(long)]
+    port: Option<u16>,
+
+    /// The largest request body (in bytes) that will be accepted.
+    #[arg(long)]
+    max_body_bytes: Option<usize>,
+
+    /// How long (in seconds) a solver may run before the request gives up on it.
+    #[arg(long)]
+    timeout_secs: Option<u64>,
+
+    /// How many requests are handled at once, which is also how many solvers may be
+    /// running at once.
+    #[arg(long)]
+    workers: Option<usize>,
+}
+
+/// Starts a server that handles requests until the process is killed:
+/// - `GET /puzzles` lists every registered puzzle
+/// - `POST /puzzles/{day}/{part}` solves one part using the request body as input
+/// - `POST /puzzles/{year}/{day}/{part}` does the same for a year other than `default_year`
+///
+/// Requests are handled by a fixed number of worker threads, and each solver runs on a
+/// thread of its own. Solvers can't be cancelled, so one that times out keeps running (and
+/// keeps its slot) until it finishes; once every slot is taken, solve requests get a 503.
+///
+/// Any settings not given in `args` come from the `[serve]` section of the config.
+pub fn serve(registry: Registry, default_year: u16, config: &Config, args: &ServeArgs) {
+    let port = args.port.unwrap_or(config.serve.port);
+    let address = format!("127.0.0.1:{port}");
+    let server = Server::http(&address).unwrap_or_else(|err| {
+        eprintln!("could not bind to {address}: {err}");
+        process::exit(1);
+    });
+    println!("Listening on http://{address}");
+
+    let server = Arc::new(server);
+    let registry = Arc::new(registry);
+    let settings = Settings {
+        default_year,
+        max_body_bytes: args.max_body_bytes.unwrap_or(config.serve.max_body_bytes),
+        timeout: Duration::from_secs(args.timeout_secs.unwrap_or(config.serve.timeout_secs)),
+    };
+    let workers = args.workers.unwrap_or(config.serve.workers).max(1);
+    let slots = Slots::new(workers);
+
+    let handles: Vec<_> = (0..workers)
+        .map(|_| {
+            let server = Arc::clone(&server);
+            let registry = Arc::clone(&registry);
+            let slots = Arc::clone(&slots);
+            thread::spawn(move || {
+                for request in server.incoming_requests() {
+                    handle(&registry, settings, &slots, request);
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().expect("request handlers don't panic");
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Settings {
+    default_year: u16,
+    max_body_bytes: usize,
+    timeout: Duration,
+}
+
+/// Keeps count of the solvers that are running, including any whose requests have
+/// already timed out.
+struct Slots {
+    running: AtomicUsize,
+    limit: usize,
+}
+
+impl Slots {
+    fn new(limit: usize) -> Arc<Self> {
+        Arc::new(Self {
+            running: AtomicUsize::new(0),
+            limit,
+        })
+    }
+
+    /// Takes a slot, if there's one free, which is given back when the `Slot` is dropped.
+    fn try_acquire(self: &Arc<Self>) -> Option<Slot> {
+        self.running
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |running| {
+                (running < self.limit).then_some(running + 1)
+            })
+            .ok()
+            .map(|_| Slot(Arc::clone(self)))
+    }
+}
+
+struct Slot(Arc<Slots>);
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.running.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Route<'a> {
+    ListPuzzles,
+    Solve {
+        year: Option<&'a str>,
+        day: &'a str,
+        part: PuzzlePartName,
+    },
+}
+
+fn route(url: &str) -> Option<Route<'_>> {
+    let path = url.split('?').next().unwrap_or_default();
+    let segments: Vec<_> = path.trim_matches('/').split('/').collect();
+
+    let (year, day, part) = match segments[..] {
+        ["puzzles"] => return Some(Route::ListPuzzles),
+        ["puzzles", day, part] => (None, day, part),
+        ["puzzles", year, day, part] => (Some(year), day, part),
+        _ => return None,
+    };
+
+    let part = match part {
+        "a" | "A" => PuzzlePartName::A,
+        "b" | "B" => PuzzlePartName::B,
+        _ => return None,
+    };
+    Some(Route::Solve { year, day, part })
+}
+
+fn handle(registry: &Registry, settings: Settings, slots: &Arc<Slots>, mut request: Request) {
+    let url = request.url().to_string();
+
+    let response = match (request.method(), route(&url)) {
+        (Method::Get, Some(Route::ListPuzzles)) => json_response(200, &list_puzzles(registry)),
+        (Method::Post, Some(Route::Solve { year, day, part })) => {
+            let year = year.map_or(Some(settings.default_year), |year| year.parse().ok());
+            match year.and_then(|year| registry.find(year, day)) {
+                Some(entry) => match slots.try_acquire() {
+                    Some(slot) => match read_body(&mut request, settings.max_body_bytes) {
+                        Ok(input) => {
+                            let (status, body) =
+                                solve(entry.part(part), input, settings.timeout, slot);
+                            json_response(status, &body)
+                        }
+                        Err((status, message)) => error_response(status, message),
+                    },
+                    None => error_response(503, "every solver is busy, try again later".into()),
+                },
+                None => error_response(404, format!("no puzzle registered for day {day}")),
+            }
+        }
+        (_, Some(_)) => error_response(405, "method not allowed".into()),
+        (_, None) => error_response(404, format!("no route for {url}")),
+    };
+
+    // the client may have hung up already, and there's nobody left to tell about it
+    let _ = request.respond(response);
+}
+
+#[derive(Serialize)]
+struct PuzzleSummary {
+    year: u16,
+    day: &'static str,
+    title: &'static str,
+    parts: Vec<PartSummary>,
+}
+
+#[derive(Serialize)]
+struct PartSummary {
+    part: &'static str,
+    description: &'static str,
+    status: String,
+}
+
+fn list_puzzles(registry: &Registry) -> Vec<PuzzleSummary> {
+    registry
+        .entries()
+        .map(|entry| PuzzleSummary {
+            year: entry.year,
+            day: entry.name,
+            title: entry.title,
+            parts: vec![
+                PartSummary {
+                    part: "a",
+                    description: (entry.part_a.description)(),
+                    status: entry.part_a.status.to_string(),
+                },
+                PartSummary {
+                    part: "b",
+                    description: (entry.part_b.description)(),
+                    status: entry.part_b.status.to_string(),
+                },
+            ],
+        })
+        .collect()
+}
+
+fn read_body(request: &mut Request, limit: usize) -> Result<String, (u16, String)> {
+    let too_large = || (413, format!("request body is larger than {limit} bytes"));
+
+    if request.body_length().is_some_and(|length| length > limit) {
+        return Err(too_large());
+    }
+
+    // the declared length can't be trusted (or might be missing), so read at most
+    // one byte past the limit to find out whether it was exceeded
+    let mut body = vec![];
+    request
+        .as_reader()
+        .take(limit as u64 + 1)
+        .read_to_end(&mut body)
+        .map_err(|err| (400, format!("could not read request body: {err}")))?;
+    if body.len() > limit {
+        return Err(too_large());
+    }
+
+    String::from_utf8(body).map_err(|_| (400, "request body must be UTF-8 text".into()))
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct SolveResponse {
+    answer: Option<String>,
+    parse_ms: Option<f64>,
+    solve_ms: Option<f64>,
+    elapsed_ms: f64,
+    error: Option<String>,
+}
+
+/// Runs a solver on its own thread so that it can be abandoned if it runs too long,
+/// though it isn't stopped, and only gives back its `slot` once it finishes.
+/// Solvers signal bad input by panicking, so panics are reported as errors.
+fn solve(part: &PartEntry, input: String, timeout: Duration, slot: Slot) -> (u16, SolveResponse) {
+    let solver = part.solve;
+    let (sender, receiver) = mpsc::channel();
+
+    let start = Instant::now();
+    thread::spawn(move || {
+        let _slot = slot;
+        let result = panic::catch_unwind(|| solver(&input)).map_err(panic_message);
+        // if the receiver is gone, the request already timed out
+        let _ = sender.send(result);
+    });
+    let result = receiver.recv_timeout(timeout);
+    let elapsed_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+    let response = SolveResponse {
+        answer: None,
+        parse_ms: None,
+        solve_ms: None,
+        elapsed_ms,
+        error: None,
+    };
+    match result {
+        Ok(Ok(solution)) => (
+            200,
+            SolveResponse {
+                answer: Some(solution.answer),
+                parse_ms: Some(solution.parse_time.as_secs_f64() * 1000.0),
+                solve_ms: Some(solution.solve_time.as_secs_f64() * 1000.0),
+                ..response
+            },
+        ),
+        Ok(Err(message)) => (
+            422,
+            SolveResponse {
+                error: Some(message),
+                ..response
+            },
+        ),
+        Err(_) => (
+            504,
+            SolveResponse {
+                error: Some(format!("solver did not finish within {timeout:?}")),
+                ..response
+            },
+        ),
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        format!("solver panicked: {message}")
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        format!("solver panicked: {message}")
+    } else {
+        "solver panicked".into()
+    }
+}
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+fn error_response(status: u16, error: String) -> Response<Cursor<Vec<u8>>> {
+    json_response(status, &ErrorResponse { error })
+}
+
+fn json_response<T: Serialize>(status: u16, body: &T) -> Response<Cursor<Vec<u8>>> {
+    let data = serde_json::to_vec(body).expect("responses are always serializable");
+    let content_type = Header::from_bytes("Content-Type", "application/json").unwrap();
+
+    Response::from_data(data)
+        .with_status_code(status)
+        .with_header(content_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::puzzle::{Solution, Status};
+
+    fn solution(answer: String) -> Solution {
+        Solution {
+            answer,
+            parse_time: Duration::ZERO,
+            solve_time: Duration::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_route() {
+        assert_eq!(route("/puzzles"), Some(Route::ListPuzzles));
+        assert_eq!(route("/puzzles/"), Some(Route::ListPuzzles));
+        assert_eq!(
+            route("/puzzles/05/b"),
+            Some(Route::Solve {
+                year: None,
+                day: "05",
+                part: PuzzlePartName::B
+            })
+        );
+        assert_eq!(
+            route("/puzzles/2022/05/b"),
+            Some(Route::Solve {
+                year: Some("2022"),
+                day: "05",
+                part: PuzzlePartName::B
+            })
+        );
+        assert_eq!(
+            route("/puzzles/5/A?verbose"),
+            Some(Route::Solve {
+                year: None,
+                day: "5",
+                part: PuzzlePartName::A
+            })
+        );
+        assert_eq!(route("/puzzles/05/c"), None);
+        assert_eq!(route("/puzzles/05"), None);
+        assert_eq!(route("/"), None);
+    }
+
+    #[test]
+    fn test_solve() {
+        let part = PartEntry {
+            description: || "Count the lines.",
+            status: Status::Done,
+            solve: |input| solution(input.lines().count().to_string()),
+        };
+
+        let slot = Slots::new(1).try_acquire().unwrap();
+        let (status, response) = solve(&part, "a\nb\nc".into(), Duration::from_secs(5), slot);
+        assert_eq!(status, 200);
+        assert_eq!(response.answer, Some("3".into()));
+        assert_eq!(response.solve_ms, Some(0.0));
+        assert_eq!(response.error, None);
+    }
+
+    #[test]
+    fn test_solve_reports_panics() {
+        let part = PartEntry {
+            description: || "Parse a number.",
+            status: Status::Done,
+            solve: |input| solution(input.parse::<u32>().unwrap().to_string()),
+        };
+
+        let slot = Slots::new(1).try_acquire().unwrap();
+        let (status, response) = solve(&part, "nope".into(), Duration::from_secs(5), slot);
+        assert_eq!(status, 422);
+        assert_eq!(response.answer, None);
+        assert!(response.error.unwrap().starts_with("solver panicked"));
+    }
+
+    #[test]
+    fn test_solve_times_out() {
+        let part = PartEntry {
+            description: || "Take forever.",
+            status: Status::Done,
+            solve: |_| {
+                thread::sleep(Duration::from_secs(2));
+                solution("done".into())
+            },
+        };
+
+        let slots = Slots::new(1);
+        let slot = slots.try_acquire().unwrap();
+        let (status, response) = solve(&part, "".into(), Duration::from_millis(10), slot);
+        assert_eq!(status, 504);
+        assert_eq!(response.answer, None);
+        assert!(response.error.is_some());
+
+        // the abandoned solver is still running, so it still has the only slot
+        assert!(slots.try_acquire().is_none());
+    }
+
+    #[test]
+    fn test_slots() {
+        let slots = Slots::new(2);
+        let first = slots.try_acquire().unwrap();
+        let second = slots.try_acquire().unwrap();
+        assert!(slots.try_acquire().is_none());
+
+        drop(first);
+        assert!(slots.try_acquire().is_some());
+        drop(second);
+        assert_eq!(slots.running.load(Ordering::Acquire), 0);
+    }
+}
@@ -0,0 +1,54 @@
+//! A group per day, timing the parser and each part on the real input.
+
+use benches::real_input;
+use common::puzzle::{Puzzle, PuzzlePart, Status};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+fn bench_puzzle<P: Puzzle>(c: &mut Criterion) {
+    let Some(input) = real_input::<P>() else {
+        return;
+    };
+
+    let mut group = c.benchmark_group(format!("day{}", P::name()));
+    group.bench_function("parse", |b| b.iter(|| P::parse(black_box(&input))));
+
+    // slow and unfinished parts would take forever (or panic), so leave them out
+    let parsed = P::parse(&input);
+    if P::PartA::status() == Status::Done {
+        group.bench_function("part_a", |b| b.iter(|| P::PartA::solve(black_box(&parsed))));
+    }
+    if P::PartB::status() == Status::Done {
+        group.bench_function("part_b", |b| b.iter(|| P::PartB::solve(black_box(&parsed))));
+    }
+
+    group.finish();
+}
+
+fn days(c: &mut Criterion) {
+    bench_puzzle::<puzzle01::Puzzle01>(c);
+    bench_puzzle::<puzzle02::Puzzle02>(c);
+    bench_puzzle::<puzzle03::Puzzle03>(c);
+    bench_puzzle::<puzzle04::Puzzle04>(c);
+    bench_puzzle::<puzzle05::Puzzle05>(c);
+    bench_puzzle::<puzzle06::Puzzle06>(c);
+    bench_puzzle::<puzzle07::Puzzle07>(c);
+    bench_puzzle::<puzzle08::Puzzle08>(c);
+    bench_puzzle::<puzzle09::Puzzle09>(c);
+    bench_puzzle::<puzzle10::Puzzle10>(c);
+    bench_puzzle::<puzzle11::Puzzle11>(c);
+    bench_puzzle::<puzzle12::Puzzle12>(c);
+    bench_puzzle::<puzzle13::Puzzle13>(c);
+    bench_puzzle::<puzzle14::Puzzle14>(c);
+    bench_puzzle::<puzzle15::Puzzle15>(c);
+    bench_puzzle::<puzzle16::Puzzle16>(c);
+    bench_puzzle::<puzzle19::Puzzle19>(c);
+    bench_puzzle::<puzzle24::Puzzle24>(c);
+}
+
+criterion_group! {
+    name = benches;
+    // some parts take a good fraction of a second, so keep the sample count down
+    config = Criterion::default().sample_size(20);
+    targets = days
+}
+criterion_main!(benches);
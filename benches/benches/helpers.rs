@@ -0,0 +1,122 @@
+//! The hot helpers behind some of the slower parts, timed on their own.
+
+use benches::real_input;
+use common::{geom::Point, piecewise::PiecewiseLinearMap, puzzle::Puzzle, rng::Rng};
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use puzzle05::Puzzle05;
+use puzzle12::Puzzle12;
+use puzzle14::Puzzle14;
+use puzzle16::Puzzle16;
+use puzzle19::part_set::PartSet;
+
+fn map_compose(c: &mut Criterion) {
+    let Some(input) = real_input::<Puzzle05>() else {
+        return;
+    };
+    let map_set = Puzzle05::parse(&input);
+
+    let maps: Vec<PiecewiseLinearMap> = map_set
+        .maps()
+        .iter()
+        .map(PiecewiseLinearMap::from)
+        .collect();
+
+    c.bench_function("PiecewiseLinearMap::compose", |b| {
+        b.iter(|| {
+            maps.iter().fold(PiecewiseLinearMap::new(), |acc, map| {
+                PiecewiseLinearMap::compose(&acc, black_box(map))
+            })
+        })
+    });
+}
+
+fn count_arrangements(c: &mut Criterion) {
+    let Some(input) = real_input::<Puzzle12>() else {
+        return;
+    };
+    let schematics = Puzzle12::parse(&input);
+
+    c.bench_function("Schematic::count_arrangements", |b| {
+        b.iter(|| {
+            schematics
+                .iter()
+                .map(|schematic| black_box(schematic).count_arrangements())
+                .sum::<u64>()
+        })
+    });
+}
+
+fn trace_beams(c: &mut Criterion) {
+    let Some(input) = real_input::<Puzzle16>() else {
+        return;
+    };
+    let grid = Puzzle16::parse(&input);
+
+    c.bench_function("TileGrid::trace_beams", |b| {
+        b.iter_batched(
+            || grid.clone(),
+            |mut grid| grid.trace_beams(Point::ORIGIN, puzzle16::Direction::Right),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn tilt(c: &mut Criterion) {
+    let Some(input) = real_input::<Puzzle14>() else {
+        return;
+    };
+    let platform = Puzzle14::parse(&input);
+
+    let mut group = c.benchmark_group("Platform::tilt");
+    group.bench_function("north", |b| {
+        b.iter_batched(
+            || platform.clone(),
+            |mut platform| platform.tilt(puzzle14::Direction::Up),
+            BatchSize::SmallInput,
+        )
+    });
+    // tilting east means switching from columns to rows first
+    group.bench_function("east", |b| {
+        b.iter_batched(
+            || platform.clone(),
+            |mut platform| platform.tilt(puzzle14::Direction::Right),
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+fn refine(c: &mut Criterion) {
+    // random overlapping sets, since the ones from the real input are buried in the solver
+    let mut rng = Rng::new(19);
+    let mut range = || {
+        let start = rng.range(1..4000) as u64;
+        start..rng.range(start as usize + 1..4001) as u64
+    };
+    let sets: Vec<PartSet> = (0..100)
+        .map(|_| PartSet {
+            x: range().into(),
+            m: range().into(),
+            a: range().into(),
+            s: range().into(),
+        })
+        .collect();
+
+    c.bench_function("PartSet::refine", |b| {
+        b.iter(|| {
+            sets.windows(2)
+                .map(|pair| pair[0].refine(black_box(&pair[1])).len())
+                .sum::<usize>()
+        })
+    });
+}
+
+criterion_group!(
+    benches,
+    map_compose,
+    count_arrangements,
+    trace_beams,
+    tilt,
+    refine
+);
+criterion_main!(benches);
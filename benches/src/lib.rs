@@ -0,0 +1,24 @@
+//! Shared setup for the benchmarks in `benches/`. Run them with `cargo bench -p benches`.
+
+use common::{
+    config::Config,
+    crypt::{self, InputKey},
+    id::PuzzleId,
+    puzzle::Puzzle,
+};
+
+/// Reads a puzzle's real input the same way the runner does, decrypting it if need be.
+/// Benchmarks for puzzles without an input on this machine are skipped.
+pub fn real_input<P: Puzzle>() -> Option<String> {
+    let config = Config::load().ok()?;
+    let id = PuzzleId::new(P::year(), P::name().parse().ok()?);
+    let key = InputKey::load(&config);
+
+    match crypt::read_input(&config.input_path(id), key.as_ref()) {
+        Ok(input) => Some(input),
+        Err(err) => {
+            eprintln!("skipping puzzle {}: {err}", P::name());
+            None
+        }
+    }
+}
@@ -0,0 +1,214 @@
+//! Arithmetic for answers that can outgrow the integer types the solvers start out with.
+//!
+//! Release builds silently wrap around on overflow, which turns a too-big input into a
+//! wrong answer rather than an error. Arithmetic done through [`Checked`] or on an
+//! [`Answer`] behaves like the built-in operators by default, but with the workspace's
+//! `checked` feature turned on it panics with an [`Overflow`] in every build. Solvers
+//! already report panics as errors, the same way they do for inputs that don't parse.
+
+use std::{
+    error::Error,
+    fmt::Display,
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign},
+};
+
+/// An arithmetic operation whose result didn't fit in its type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow {
+    pub operation: &'static str,
+    pub type_name: &'static str,
+}
+
+impl Display for Overflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} overflowed in {}", self.type_name, self.operation)
+    }
+}
+
+impl Error for Overflow {}
+
+/// Integer arithmetic that reports overflow when the `checked` feature is on.
+pub trait Checked: Copy {
+    fn plus(self, rhs: Self) -> Self;
+    fn minus(self, rhs: Self) -> Self;
+    fn times(self, rhs: Self) -> Self;
+    fn power(self, exp: u32) -> Self;
+}
+
+#[cfg(feature = "checked")]
+fn or_overflow<T>(result: Option<T>, operation: &'static str) -> T {
+    result.unwrap_or_else(|| {
+        panic!(
+            "{}",
+            Overflow {
+                operation,
+                type_name: std::any::type_name::<T>(),
+            }
+        )
+    })
+}
+
+macro_rules! impl_checked {
+    ($($t:ty),*) => {
+        $(impl Checked for $t {
+            #[cfg(feature = "checked")]
+            fn plus(self, rhs: Self) -> Self {
+                or_overflow(self.checked_add(rhs), "addition")
+            }
+
+            #[cfg(feature = "checked")]
+            fn minus(self, rhs: Self) -> Self {
+                or_overflow(self.checked_sub(rhs), "subtraction")
+            }
+
+            #[cfg(feature = "checked")]
+            fn times(self, rhs: Self) -> Self {
+                or_overflow(self.checked_mul(rhs), "multiplication")
+            }
+
+            #[cfg(feature = "checked")]
+            fn power(self, exp: u32) -> Self {
+                or_overflow(self.checked_pow(exp), "exponentiation")
+            }
+
+            #[cfg(not(feature = "checked"))]
+            fn plus(self, rhs: Self) -> Self {
+                self + rhs
+            }
+
+            #[cfg(not(feature = "checked"))]
+            fn minus(self, rhs: Self) -> Self {
+                self - rhs
+            }
+
+            #[cfg(not(feature = "checked"))]
+            fn times(self, rhs: Self) -> Self {
+                self * rhs
+            }
+
+            #[cfg(not(feature = "checked"))]
+            fn power(self, exp: u32) -> Self {
+                self.pow(exp)
+            }
+        })*
+    };
+}
+
+impl_checked!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// A puzzle answer, wide enough that sums and products of the numbers in an input
+/// (or in a much bigger generated one) won't overflow it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Answer(i128);
+
+impl Answer {
+    pub const ZERO: Self = Self(0);
+    pub const ONE: Self = Self(1);
+
+    pub fn pow(self, exp: u32) -> Self {
+        Self(self.0.power(exp))
+    }
+}
+
+macro_rules! impl_from {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(value: $t) -> Self {
+                Self(value as i128)
+            }
+        })*
+    };
+}
+
+// every one of these fits in an i128 (usize included, on the platforms we run on)
+impl_from!(u8, u16, u32, u64, usize, i8, i16, i32, i64, i128);
+
+impl Add for Answer {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0.plus(rhs.0))
+    }
+}
+
+impl Sub for Answer {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(self.0.minus(rhs.0))
+    }
+}
+
+impl Mul for Answer {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self(self.0.times(rhs.0))
+    }
+}
+
+impl AddAssign for Answer {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Answer {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign for Answer {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl Sum for Answer {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+impl Product for Answer {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, Mul::mul)
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer_arithmetic() {
+        let answer: Answer = [u32::MAX, u32::MAX].into_iter().map(Answer::from).sum();
+        assert_eq!(answer.to_string(), "8589934590");
+
+        let answer = Answer::from(u64::MAX) * Answer::from(2) - Answer::ONE;
+        assert_eq!(answer.to_string(), "36893488147419103229");
+        assert_eq!(Answer::from(-3).pow(3), Answer::from(-27));
+    }
+
+    #[test]
+    fn test_checked_in_range() {
+        assert_eq!(200u8.plus(55), 255);
+        assert_eq!(3i32.minus(5), -2);
+        assert_eq!(2u32.power(31), 1 << 31);
+    }
+
+    #[cfg(feature = "checked")]
+    #[test]
+    #[should_panic(expected = "u32 overflowed in exponentiation")]
+    fn test_checked_overflow() {
+        2u32.power(32);
+    }
+}
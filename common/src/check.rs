@@ -0,0 +1,311 @@
+//! A small property-testing harness, for checking an optimized solver against an obviously
+//! correct (but slow) one on lots of small random inputs.
+//!
+//! Inputs are generated as plain data (numbers, tuples and vectors of them) rather than as
+//! puzzle text, so that a failing input can be shrunk piece by piece into a minimal
+//! reproduction. Properties are expected to render that data into whatever the solvers need.
+
+use crate::rng::Rng;
+use std::{
+    any::Any,
+    fmt::Debug,
+    panic::{self, AssertUnwindSafe},
+};
+
+/// Environment variable that overrides the seed of every check, to explore new cases.
+pub const SEED_ENV_VAR: &str = "AOC_CHECK_SEED";
+
+/// The most shrinking steps to take before settling for what's been found so far.
+const MAX_SHRINK_STEPS: usize = 1_000;
+
+/// Values that can be made "smaller", for boiling failing inputs down.
+pub trait Shrink: Sized {
+    /// Candidates that are simpler than this value, simplest first. Empty if there
+    /// is nothing simpler.
+    fn shrink(&self) -> Vec<Self>;
+}
+
+macro_rules! shrink_unsigned {
+    ($($t:ty),*) => {
+        $(impl Shrink for $t {
+            fn shrink(&self) -> Vec<Self> {
+                let mut candidates = vec![0, self / 2, self.saturating_sub(1)];
+                candidates.dedup();
+                candidates.retain(|candidate| candidate < self);
+                candidates
+            }
+        })*
+    };
+}
+
+shrink_unsigned!(u8, u32, u64, usize);
+
+impl Shrink for i64 {
+    fn shrink(&self) -> Vec<Self> {
+        // towards zero, preferring positive numbers, which `i64::MIN` doesn't have one of
+        let mut candidates: Vec<_> = [Some(0), self.checked_abs(), Some(self / 2)]
+            .into_iter()
+            .flatten()
+            .chain([self - self.signum()])
+            .collect();
+        candidates.dedup();
+        candidates.retain(|candidate| {
+            candidate.unsigned_abs() < self.unsigned_abs()
+                || (candidate.unsigned_abs() == self.unsigned_abs() && *candidate > *self)
+        });
+        candidates
+    }
+}
+
+impl Shrink for bool {
+    fn shrink(&self) -> Vec<Self> {
+        if *self {
+            vec![false]
+        } else {
+            vec![]
+        }
+    }
+}
+
+impl<T: Shrink + Clone> Shrink for Vec<T> {
+    fn shrink(&self) -> Vec<Self> {
+        let mut candidates = vec![];
+
+        // try dropping big chunks first, then single items, then simplifying items
+        if self.len() > 1 {
+            let half = self.len() / 2;
+            candidates.push(self[..half].to_vec());
+            candidates.push(self[half..].to_vec());
+        }
+        for idx in 0..self.len() {
+            let mut candidate = self.clone();
+            candidate.remove(idx);
+            candidates.push(candidate);
+        }
+        for (idx, item) in self.iter().enumerate() {
+            for smaller in item.shrink() {
+                let mut candidate = self.clone();
+                candidate[idx] = smaller;
+                candidates.push(candidate);
+            }
+        }
+
+        candidates
+    }
+}
+
+macro_rules! shrink_tuple {
+    ($(($($name:ident $idx:tt),*)),*) => {
+        $(impl<$($name: Shrink + Clone),*> Shrink for ($($name,)*) {
+            fn shrink(&self) -> Vec<Self> {
+                let mut candidates = vec![];
+                $(
+                    for smaller in self.$idx.shrink() {
+                        let mut candidate = self.clone();
+                        candidate.$idx = smaller;
+                        candidates.push(candidate);
+                    }
+                )*
+                candidates
+            }
+        })*
+    };
+}
+
+shrink_tuple!(
+    (A 0, B 1),
+    (A 0, B 1, C 2),
+    (A 0, B 1, C 2, D 3)
+);
+
+/// The outcome of a property on one input: `Err` describes why it doesn't hold.
+pub type Outcome = Result<(), String>;
+
+/// Checks that an optimized answer matches the one from a slower oracle.
+pub fn agree<T: PartialEq + Debug>(optimized: T, oracle: T) -> Outcome {
+    if optimized == oracle {
+        Ok(())
+    } else {
+        Err(format!(
+            "optimized solver gave {optimized:?}, oracle gave {oracle:?}"
+        ))
+    }
+}
+
+/// A failing input, before and after shrinking.
+#[derive(Debug)]
+pub struct Failure<T> {
+    pub seed: u64,
+    pub original: T,
+    pub shrunk: T,
+    pub message: String,
+}
+
+/// Runs a property on a number of random inputs.
+#[derive(Debug, Clone)]
+pub struct Check {
+    cases: u64,
+    seed: u64,
+}
+
+impl Default for Check {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Check {
+    /// 100 cases, starting from the seed in `AOC_CHECK_SEED` if it's set.
+    pub fn new() -> Self {
+        let seed = std::env::var(SEED_ENV_VAR)
+            .ok()
+            .and_then(|seed| seed.parse().ok())
+            .unwrap_or(0);
+        Self { cases: 100, seed }
+    }
+
+    pub fn cases(self, cases: u64) -> Self {
+        Self { cases, ..self }
+    }
+
+    pub fn seed(self, seed: u64) -> Self {
+        Self { seed, ..self }
+    }
+
+    /// Panics with a minimal failing input if the property doesn't hold for every case.
+    pub fn run<T: Shrink + Clone + Debug>(
+        &self,
+        generate: impl Fn(&mut Rng) -> T,
+        property: impl Fn(&T) -> Outcome,
+    ) {
+        if let Some(failure) = self.find_failure(generate, property) {
+            panic!(
+                "property failed for seed {} (rerun with {SEED_ENV_VAR}={0})\n  \
+                 original input: {:?}\n  shrunk input:   {:?}\n  {}",
+                failure.seed, failure.original, failure.shrunk, failure.message
+            );
+        }
+    }
+
+    /// Like `run`, but returns the first failure instead of panicking.
+    pub fn find_failure<T: Shrink + Clone + Debug>(
+        &self,
+        generate: impl Fn(&mut Rng) -> T,
+        property: impl Fn(&T) -> Outcome,
+    ) -> Option<Failure<T>> {
+        // solvers tend to panic on inputs they can't handle, which counts as failing too
+        let property = |input: &T| {
+            panic::catch_unwind(AssertUnwindSafe(|| property(input)))
+                .unwrap_or_else(|payload| Err(format!("panicked: {}", panic_message(&*payload))))
+        };
+
+        (self.seed..self.seed + self.cases).find_map(|seed| {
+            let original = generate(&mut Rng::new(seed));
+            let message = property(&original).err()?;
+            let (shrunk, message) = shrink(original.clone(), message, property);
+            Some(Failure {
+                seed,
+                original,
+                shrunk,
+                message,
+            })
+        })
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "(no message)"
+    }
+}
+
+/// Greedily replaces the input with the first simpler candidate that still fails,
+/// until none of them do.
+fn shrink<T: Shrink>(
+    mut input: T,
+    mut message: String,
+    property: impl Fn(&T) -> Outcome,
+) -> (T, String) {
+    for _ in 0..MAX_SHRINK_STEPS {
+        let Some((smaller, smaller_message)) = input
+            .shrink()
+            .into_iter()
+            .find_map(|candidate| property(&candidate).err().map(|msg| (candidate, msg)))
+        else {
+            break;
+        };
+        input = smaller;
+        message = smaller_message;
+    }
+    (input, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shrink_numbers() {
+        assert_eq!(10u64.shrink(), vec![0, 5, 9]);
+        assert_eq!(1u64.shrink(), vec![0]);
+        assert!(0u64.shrink().is_empty());
+        assert_eq!((-6i64).shrink(), vec![0, 6, -3, -5]);
+        assert_eq!(i64::MIN.shrink(), vec![0, i64::MIN / 2, i64::MIN + 1]);
+    }
+
+    #[test]
+    fn test_passing_property() {
+        let failure = Check::new().find_failure(
+            |rng| {
+                (0..rng.range(0..10))
+                    .map(|_| rng.below(100))
+                    .collect::<Vec<_>>()
+            },
+            |numbers| agree(numbers.iter().sum::<u64>(), numbers.iter().rev().sum()),
+        );
+        assert!(failure.is_none());
+    }
+
+    #[test]
+    fn test_failing_property_is_shrunk() {
+        // "no list adds up to 100 or more" is false, and the simplest
+        // counterexamples add up to exactly 100
+        let failure = Check::new()
+            .find_failure(
+                |rng| (0..rng.range(0..20)).map(|_| rng.below(100)).collect(),
+                |numbers: &Vec<u64>| {
+                    let sum: u64 = numbers.iter().sum();
+                    if sum < 100 {
+                        Ok(())
+                    } else {
+                        Err(format!("sum is {sum}"))
+                    }
+                },
+            )
+            .unwrap();
+
+        assert_eq!(failure.shrunk.iter().sum::<u64>(), 100);
+        assert!(failure.shrunk.len() < failure.original.len());
+        assert_eq!(failure.message, "sum is 100");
+    }
+
+    #[test]
+    fn test_panics_are_failures() {
+        let failure = Check::new()
+            .find_failure(
+                |rng| rng.below(1000),
+                |number: &u64| {
+                    assert!(*number < 10, "too big");
+                    Ok(())
+                },
+            )
+            .unwrap();
+
+        assert_eq!(failure.shrunk, 10);
+        assert_eq!(failure.message, "panicked: too big");
+    }
+}
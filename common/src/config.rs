@@ -0,0 +1,403 @@
+//! Settings shared by the puzzle runners, read from TOML files.
+//!
+//! The user's config lives at `$XDG_CONFIG_HOME/aoc/config.toml` (or `~/.config/aoc/config.toml`),
+//! and an `aoc.toml` at the root of the workspace overrides any of its values. Everything is optional:
+//!
+//! ```toml
+//! session = "53616c7465645f5f..."
+//! input_key = "correct horse battery staple"
+//! base_url = "https://adventofcode.com"
+//! format = "text"      # or "json"
+//! color = "auto"       # or "always", "never"
+//!
+//! [paths]
+//! root = "/home/me/aoc"
+//! input = "{crate}/data/input"
+//! example = "{crate}/data/example"
+//! answers = "{crate}/data/answers"
+//!
+//! [serve]
+//! port = 8023
+//! max_body_bytes = 1048576
+//! timeout_secs = 30
+//! workers = 4
+//! ```
+//!
+//! Paths are relative to `root`, which defaults to the workspace the runner is started in
+//! (see [`workspace_root`]), and may use the placeholders `{crate}` (the puzzle's crate
+//! directory), `{year}` and `{day}`.
+
+use crate::id::{PuzzleId, SITE_URL};
+use clap::ValueEnum;
+use serde::Deserialize;
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+};
+use toml::{Table, Value};
+
+/// The name of the per-workspace config file.
+pub const WORKSPACE_CONFIG_FILE: &str = "aoc.toml";
+
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// The session cookie used to authenticate with the Advent of Code site.
+    pub session: Option<String>,
+
+    /// The passphrase for encrypted puzzle inputs (see [`crate::crypt`]).
+    pub input_key: Option<String>,
+
+    /// The site that inputs are fetched from and answers are submitted to.
+    pub base_url: String,
+
+    /// How the runners print their results.
+    pub format: OutputFormat,
+
+    /// Whether the runners use colors in their output.
+    pub color: ColorChoice,
+
+    pub paths: PathLayout,
+
+    pub serve: ServeConfig,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            session: None,
+            input_key: None,
+            base_url: SITE_URL.into(),
+            format: OutputFormat::default(),
+            color: ColorChoice::default(),
+            paths: PathLayout::default(),
+            serve: ServeConfig::default(),
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorChoice {
+    /// Use colors when writing to a terminal.
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    pub fn apply(self) {
+        match self {
+            ColorChoice::Auto => colored::control::unset_override(),
+            ColorChoice::Always => colored::control::set_override(true),
+            ColorChoice::Never => colored::control::set_override(false),
+        }
+    }
+}
+
+/// Where each puzzle's files live.
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PathLayout {
+    /// The directory that the other paths are relative to, instead of the workspace root.
+    pub root: Option<PathBuf>,
+    pub input: String,
+    pub example: String,
+    pub answers: String,
+}
+
+impl Default for PathLayout {
+    fn default() -> Self {
+        Self {
+            root: None,
+            input: "{crate}/data/input".into(),
+            example: "{crate}/data/example".into(),
+            answers: "{crate}/data/answers".into(),
+        }
+    }
+}
+
+/// Defaults for `aoc serve`.
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServeConfig {
+    pub port: u16,
+    pub max_body_bytes: usize,
+    pub timeout_secs: u64,
+    pub workers: usize,
+}
+
+impl Default for ServeConfig {
+    fn default() -> Self {
+        Self {
+            port: 8023,
+            max_body_bytes: 1024 * 1024,
+            timeout_secs: 30,
+            workers: 4,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ConfigError {
+    path: Option<PathBuf>,
+    message: String,
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.path {
+            Some(path) => write!(f, "invalid config in {}: {}", path.display(), self.message),
+            None => write!(f, "invalid config: {}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// The root of the workspace the runner was started in: the closest of the current directory
+/// and its ancestors that has an `aoc.toml` or a Cargo workspace manifest in it, or just the
+/// current directory if none of them do.
+pub fn workspace_root() -> PathBuf {
+    let current_dir = env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    find_workspace_root(&current_dir).unwrap_or(current_dir)
+}
+
+fn find_workspace_root(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .find(|dir| dir.join(WORKSPACE_CONFIG_FILE).exists() || is_cargo_workspace(dir))
+        .map(Path::to_path_buf)
+}
+
+fn is_cargo_workspace(dir: &Path) -> bool {
+    fs::read_to_string(dir.join("Cargo.toml"))
+        .ok()
+        .and_then(|text| text.parse::<Table>().ok())
+        .is_some_and(|manifest| manifest.contains_key("workspace"))
+}
+
+/// The location of the user's config file, following the XDG base directory spec.
+pub fn user_config_path() -> Option<PathBuf> {
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config_dir.join("aoc").join("config.toml"))
+}
+
+impl Config {
+    /// Reads the user's config and then the workspace's `aoc.toml`, so that the latter
+    /// takes precedence. Missing files are skipped.
+    pub fn load() -> Result<Self, ConfigError> {
+        let workspace_config = workspace_root().join(WORKSPACE_CONFIG_FILE);
+        Self::load_from(user_config_path().into_iter().chain([workspace_config]))
+    }
+
+    /// Reads each of the given files that exists, with later files overriding earlier ones.
+    pub fn load_from(paths: impl IntoIterator<Item = PathBuf>) -> Result<Self, ConfigError> {
+        let mut merged = Table::new();
+
+        for path in paths {
+            if !path.exists() {
+                continue;
+            }
+
+            let table = fs::read_to_string(&path)
+                .map_err(|err| err.to_string())
+                .and_then(|text| text.parse::<Table>().map_err(|err| err.to_string()))
+                .and_then(|table| {
+                    // check each file on its own too, so that errors point at the right one
+                    Self::from_table(table.clone()).map(|_| table)
+                })
+                .map_err(|message| ConfigError {
+                    path: Some(path.clone()),
+                    message,
+                })?;
+            merge(&mut merged, table);
+        }
+
+        Self::from_table(merged).map_err(|message| ConfigError {
+            path: None,
+            message,
+        })
+    }
+
+    fn from_table(table: Table) -> Result<Self, String> {
+        Value::Table(table)
+            .try_into()
+            .map_err(|err: toml::de::Error| err.to_string())
+    }
+
+    pub fn input_path(&self, id: PuzzleId) -> PathBuf {
+        self.root().join(expand(&self.paths.input, id))
+    }
+
+    pub fn example_path(&self, id: PuzzleId) -> PathBuf {
+        self.root().join(expand(&self.paths.example, id))
+    }
+
+    pub fn answers_path(&self, id: PuzzleId) -> PathBuf {
+        self.root().join(expand(&self.paths.answers, id))
+    }
+
+    /// Where the puzzle's input can be downloaded from, on the configured site.
+    pub fn input_url(&self, id: PuzzleId) -> String {
+        id.input_url(&self.base_url)
+    }
+
+    fn root(&self) -> PathBuf {
+        self.paths.root.clone().unwrap_or_else(workspace_root)
+    }
+}
+
+/// Recursively copies the values of `overrides` into `base`.
+fn merge(base: &mut Table, overrides: Table) {
+    for (key, value) in overrides {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(base)), Value::Table(overrides)) => merge(base, overrides),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+fn expand(template: &str, id: PuzzleId) -> String {
+    template
+        .replace("{crate}", &id.crate_dir().to_string_lossy())
+        .replace("{year}", &id.year.to_string())
+        .replace("{day}", &format!("{:02}", id.day))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_config(name: &str, contents: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-config-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_missing_files_give_defaults() {
+        let config = Config::load_from([PathBuf::from("/does/not/exist.toml")]).unwrap();
+        assert_eq!(config, Config::default());
+    }
+
+    #[test]
+    fn test_later_files_override_earlier_ones() {
+        let user = write_config(
+            "user.toml",
+            "session = \"abc\"\ninput_key = \"def\"\ncolor = \"never\"\n[serve]\nport = 9000\ntimeout_secs = 5\n",
+        );
+        let workspace = write_config(
+            "workspace.toml",
+            "base_url = \"http://localhost:8000\"\ncolor = \"always\"\n[serve]\ntimeout_secs = 60\n",
+        );
+
+        let config = Config::load_from([user, workspace]).unwrap();
+
+        assert_eq!(config.session, Some("abc".into()));
+        assert_eq!(config.input_key, Some("def".into()));
+        assert_eq!(config.base_url, "http://localhost:8000");
+        assert_eq!(config.color, ColorChoice::Always);
+        assert_eq!(config.serve.port, 9000);
+        assert_eq!(config.serve.timeout_secs, 60);
+        assert_eq!(
+            config.serve.max_body_bytes,
+            ServeConfig::default().max_body_bytes
+        );
+    }
+
+    #[test]
+    fn test_errors_name_the_file() {
+        let path = write_config("bad.toml", "format = \"yaml\"\n");
+
+        let err = Config::load_from([path]).unwrap_err();
+        assert!(err.to_string().contains("bad.toml"));
+    }
+
+    #[test]
+    fn test_expand_path_layout() {
+        let config = Config {
+            paths: PathLayout {
+                input: "inputs/{year}/day{day}.txt".into(),
+                ..PathLayout::default()
+            },
+            ..Config::default()
+        };
+
+        let id = PuzzleId::new(2023, 5);
+        assert_eq!(
+            config.input_path(id),
+            workspace_root().join("inputs/2023/day05.txt")
+        );
+        assert_eq!(
+            config.example_path(id),
+            workspace_root().join("puzzle05/data/example")
+        );
+
+        let id = PuzzleId::new(2024, 12);
+        assert_eq!(
+            config.answers_path(id),
+            workspace_root().join("2024/puzzle12/data/answers")
+        );
+
+        let config = Config {
+            paths: PathLayout {
+                root: Some("/srv/aoc".into()),
+                ..PathLayout::default()
+            },
+            ..Config::default()
+        };
+        assert_eq!(
+            config.input_path(id),
+            PathBuf::from("/srv/aoc/2024/puzzle12/data/input")
+        );
+    }
+
+    #[test]
+    fn test_input_url() {
+        let id = PuzzleId::new(2024, 12);
+        assert_eq!(
+            Config::default().input_url(id),
+            "https://adventofcode.com/2024/day/12/input"
+        );
+
+        let config = Config {
+            base_url: "http://localhost:8000".into(),
+            ..Config::default()
+        };
+        assert_eq!(
+            config.input_url(id),
+            "http://localhost:8000/2024/day/12/input"
+        );
+    }
+
+    #[test]
+    fn test_find_workspace_root() {
+        // the tests run from inside the `common` crate, whose parent is the workspace
+        let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let workspace = crate_dir.parent().unwrap();
+        assert_eq!(
+            find_workspace_root(&crate_dir.join("src")),
+            Some(workspace.to_path_buf())
+        );
+        assert_eq!(workspace_root(), workspace);
+    }
+}
@@ -0,0 +1,196 @@
+//! Puzzle inputs shouldn't be published, but keeping them in the repo makes runs reproducible.
+//! An input can instead be stored encrypted next to where the plaintext would be
+//! (eg. `data/input.enc`), and is decrypted when it's loaded.
+//!
+//! The key is any passphrase, taken from the `AOC_INPUT_KEY` environment variable
+//! or the `input_key` setting in the config. Each file gets its own random salt, and the
+//! encryption key is derived from the passphrase and salt with Argon2, so that guessing
+//! passphrases against a published file is slow.
+
+use crate::config::Config;
+use argon2::Argon2;
+use chacha20poly1305::{
+    aead::{rand_core::RngCore, Aead, AeadCore, KeyInit, OsRng},
+    Key, XChaCha20Poly1305, XNonce,
+};
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// The environment variable that the key is read from. It takes precedence over the config.
+pub const KEY_ENV_VAR: &str = "AOC_INPUT_KEY";
+
+/// Marks the start of an encrypted file, so that the format can change later on.
+const MAGIC: &[u8] = b"aoc-enc2";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+
+pub struct InputKey(String);
+
+impl InputKey {
+    pub fn from_passphrase(passphrase: &str) -> Self {
+        Self(passphrase.into())
+    }
+
+    /// Looks for a key in the environment, then in the config.
+    pub fn load(config: &Config) -> Option<Self> {
+        env::var(KEY_ENV_VAR)
+            .ok()
+            .or_else(|| config.input_key.clone())
+            .filter(|passphrase| !passphrase.is_empty())
+            .map(|passphrase| Self::from_passphrase(&passphrase))
+    }
+
+    /// Encrypts with a fresh random salt and nonce, so the same plaintext never encrypts the
+    /// same way twice.
+    pub fn encrypt(&self, plaintext: &str) -> Vec<u8> {
+        let mut salt = [0; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        let cipher = self.cipher(&salt);
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(&nonce, plaintext.as_bytes())
+            .expect("encrypting into memory can't fail");
+
+        [MAGIC, &salt, nonce.as_slice(), &ciphertext].concat()
+    }
+
+    /// Returns `None` if the data wasn't encrypted with this key (or has been tampered with).
+    pub fn decrypt(&self, data: &[u8]) -> Option<String> {
+        let data = data.strip_prefix(MAGIC)?;
+        if data.len() < SALT_LEN + NONCE_LEN {
+            return None;
+        }
+        let (salt, data) = data.split_at(SALT_LEN);
+        let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+
+        let plaintext = self
+            .cipher(salt)
+            .decrypt(XNonce::from_slice(nonce), ciphertext)
+            .ok()?;
+        String::from_utf8(plaintext).ok()
+    }
+
+    fn cipher(&self, salt: &[u8]) -> XChaCha20Poly1305 {
+        let mut key = Key::default();
+        Argon2::default()
+            .hash_password_into(self.0.as_bytes(), salt, &mut key)
+            .expect("the salt and key are long enough");
+        XChaCha20Poly1305::new(&key)
+    }
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    NotFound(PathBuf),
+    Io(PathBuf, io::Error),
+    MissingKey(PathBuf),
+    WrongKey(PathBuf),
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::NotFound(path) => write!(f, "{} does not exist", path.display()),
+            InputError::Io(path, err) => write!(f, "could not read {}: {err}", path.display()),
+            InputError::MissingKey(path) => write!(
+                f,
+                "{} is encrypted, but no key was given (set {KEY_ENV_VAR} or `input_key` in the config)",
+                path.display()
+            ),
+            InputError::WrongKey(path) => {
+                write!(f, "{} could not be decrypted with the given key", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// Where the encrypted version of the file at `path` lives.
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(".enc");
+    PathBuf::from(path)
+}
+
+/// Reads the file at `path`, or decrypts its encrypted version if there is no plaintext.
+pub fn read_input(path: &Path, key: Option<&InputKey>) -> Result<String, InputError> {
+    match fs::read_to_string(path) {
+        Ok(input) => return Ok(input),
+        Err(err) if err.kind() != io::ErrorKind::NotFound => {
+            return Err(InputError::Io(path.into(), err))
+        }
+        Err(_) => {}
+    }
+
+    let encrypted = encrypted_path(path);
+    let data = match fs::read(&encrypted) {
+        Ok(data) => data,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            return Err(InputError::NotFound(path.into()))
+        }
+        Err(err) => return Err(InputError::Io(encrypted, err)),
+    };
+
+    let key = key.ok_or_else(|| InputError::MissingKey(encrypted.clone()))?;
+    key.decrypt(&data).ok_or(InputError::WrongKey(encrypted))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let key = InputKey::from_passphrase("hunter2");
+        let encrypted = key.encrypt("467..114..\n...*......\n");
+
+        assert_ne!(encrypted, key.encrypt("467..114..\n...*......\n"));
+        assert_eq!(
+            key.decrypt(&encrypted),
+            Some("467..114..\n...*......\n".into())
+        );
+        assert_eq!(
+            InputKey::from_passphrase("hunter3").decrypt(&encrypted),
+            None
+        );
+        assert_eq!(key.decrypt(b"aoc-enc2 too short"), None);
+
+        // the salt is part of the key, so changing it breaks decryption
+        let mut tampered = encrypted.clone();
+        tampered[MAGIC.len()] ^= 1;
+        assert_eq!(key.decrypt(&tampered), None);
+    }
+
+    #[test]
+    fn test_read_input_falls_back_to_encrypted() {
+        let dir = env::temp_dir().join(format!("aoc-crypt-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("input");
+        let key = InputKey::from_passphrase("hunter2");
+        fs::write(encrypted_path(&path), key.encrypt("secret")).unwrap();
+
+        assert_eq!(read_input(&path, Some(&key)).unwrap(), "secret");
+        assert!(matches!(
+            read_input(&path, None),
+            Err(InputError::MissingKey(_))
+        ));
+        assert!(matches!(
+            read_input(&path, Some(&InputKey::from_passphrase("nope"))),
+            Err(InputError::WrongKey(_))
+        ));
+
+        // the plaintext wins when both are around
+        fs::write(&path, "plain").unwrap();
+        assert_eq!(read_input(&path, None).unwrap(), "plain");
+
+        assert!(matches!(
+            read_input(&dir.join("missing"), Some(&key)),
+            Err(InputError::NotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,217 @@
+//! Finding where a sequence of states starts repeating, for puzzles that ask about the
+//! state after far more steps than could ever be simulated.
+//!
+//! All of these take a starting state and a `step` function, and look at the sequence
+//! `start, step(start), step(step(start)), ...`. Since `step` only sees the current state,
+//! once any state comes up a second time everything after it repeats too.
+
+use std::{collections::HashMap, hash::Hash};
+
+/// The shape of a sequence that eventually repeats: `prefix` states that are never seen
+/// again, followed by `period` states that repeat forever.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cycle {
+    pub prefix: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    /// The earliest step that reaches the same state as step `n`.
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.prefix {
+            n
+        } else {
+            self.prefix + (n - self.prefix) % self.period
+        }
+    }
+}
+
+fn advance<T>(state: &T, step: &mut impl FnMut(&T) -> T, times: usize) -> T
+where
+    T: Clone,
+{
+    (0..times).fold(state.clone(), |state, _| step(&state))
+}
+
+/// Floyd's tortoise and hare, which only ever keeps a couple of states around but steps
+/// through the sequence about three times over.
+pub fn floyd<T: Eq + Clone>(start: &T, mut step: impl FnMut(&T) -> T) -> Cycle {
+    // the hare moves twice as fast, so they meet somewhere in the cycle, at a step that
+    // is a multiple of the period
+    let mut tortoise = step(start);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        let halfway = step(&hare);
+        hare = step(&halfway);
+    }
+
+    // so walking from the start and from the meeting point together, they line up at the
+    // first state of the cycle
+    let mut prefix = 0;
+    tortoise = start.clone();
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        prefix += 1;
+    }
+
+    let mut period = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        period += 1;
+    }
+
+    Cycle { prefix, period }
+}
+
+/// Brent's algorithm, which keeps as little around as [`floyd`] but usually calls `step`
+/// fewer times.
+pub fn brent<T: Eq + Clone>(start: &T, mut step: impl FnMut(&T) -> T) -> Cycle {
+    // look for the period first, moving the tortoise up to the hare at each power of two
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = start.clone();
+    let mut hare = step(start);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare);
+        period += 1;
+    }
+
+    // then start the hare a period ahead, so that they meet at the start of the cycle
+    let mut prefix = 0;
+    tortoise = start.clone();
+    hare = advance(start, &mut step, period);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        prefix += 1;
+    }
+
+    Cycle { prefix, period }
+}
+
+/// Steps until a state repeats or `limit` steps have been taken, returning every state
+/// seen along the way (the last of which is where it stopped) and the cycle, if one
+/// turned up.
+fn walk<T>(start: T, step: &mut impl FnMut(&T) -> T, limit: usize) -> (Vec<T>, Option<Cycle>)
+where
+    T: Hash + Eq + Clone,
+{
+    let mut seen = HashMap::new();
+    let mut states = vec![];
+    let mut state = start;
+    while states.len() < limit {
+        if let Some(&prefix) = seen.get(&state) {
+            let period = states.len() - prefix;
+            return (states, Some(Cycle { prefix, period }));
+        }
+        seen.insert(state.clone(), states.len());
+        let next = step(&state);
+        states.push(state);
+        state = next;
+    }
+    states.push(state);
+    (states, None)
+}
+
+/// Finds the cycle by remembering every state, which takes only as many steps as the
+/// prefix and period add up to, at the cost of keeping all of those states around. They
+/// are returned too, in order, so that any later state can be looked up in them.
+pub fn find<T>(start: T, mut step: impl FnMut(&T) -> T) -> (Cycle, Vec<T>)
+where
+    T: Hash + Eq + Clone,
+{
+    match walk(start, &mut step, usize::MAX) {
+        (states, Some(cycle)) => (cycle, states),
+        (_, None) => unreachable!("no sequence takes usize::MAX steps to repeat"),
+    }
+}
+
+/// The state after `n` steps, skipping over however many times the sequence repeats
+/// along the way.
+pub fn state_after<T>(start: T, mut step: impl FnMut(&T) -> T, n: usize) -> T
+where
+    T: Hash + Eq + Clone,
+{
+    let (mut states, cycle) = walk(start, &mut step, n);
+    match cycle {
+        Some(cycle) => states.swap_remove(cycle.reduce(n)),
+        // there weren't enough steps to repeat anything
+        None => states.pop().unwrap(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::{agree, Check};
+
+    /// Counts up to `prefix + period`, then wraps back around to `prefix`.
+    fn lasso(prefix: usize, period: usize) -> impl Fn(&usize) -> usize {
+        move |n| {
+            if n + 1 == prefix + period {
+                prefix
+            } else {
+                n + 1
+            }
+        }
+    }
+
+    #[test]
+    fn test_reduce() {
+        let cycle = Cycle {
+            prefix: 3,
+            period: 4,
+        };
+        assert_eq!(cycle.reduce(2), 2);
+        assert_eq!(cycle.reduce(7), 3);
+        assert_eq!(cycle.reduce(1_000_000_000), 4);
+    }
+
+    #[test]
+    fn test_detection() {
+        let expected = Cycle {
+            prefix: 5,
+            period: 3,
+        };
+        assert_eq!(floyd(&0, lasso(5, 3)), expected);
+        assert_eq!(brent(&0, lasso(5, 3)), expected);
+
+        let (cycle, states) = find(0, lasso(5, 3));
+        assert_eq!(cycle, expected);
+        assert_eq!(states, (0..8).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_state_after() {
+        assert_eq!(state_after(0, lasso(5, 3), 0), 0);
+        assert_eq!(state_after(0, lasso(5, 3), 4), 4);
+        assert_eq!(state_after(0, lasso(5, 3), 8), 5);
+        assert_eq!(state_after(0, lasso(5, 3), 1_000_000_000), 7);
+    }
+
+    #[test]
+    fn test_detectors_agree_with_simulation() {
+        Check::new().run(
+            |rng| (rng.range(0..20), rng.range(1..20), rng.range(0..100)),
+            |&(prefix, period, n)| {
+                let period = period.max(1);
+                let expected = Cycle { prefix, period };
+                agree(floyd(&0, lasso(prefix, period)), expected)?;
+                agree(brent(&0, lasso(prefix, period)), expected)?;
+                agree(find(0, lasso(prefix, period)).0, expected)?;
+                agree(
+                    state_after(0, lasso(prefix, period), n),
+                    advance(&0, &mut lasso(prefix, period), n),
+                )
+            },
+        );
+    }
+}
@@ -0,0 +1,179 @@
+//! Parse errors that point into the input text, rendered the way a compiler would:
+//!
+//! ```text
+//! error: expected `<` or `>` after category
+//!  --> puzzle19/data/input:3:5
+//!   |
+//! 3 | px{a=2006:qkq,m>2090:A,rfg}
+//!   |     ^
+//! ```
+
+use std::{error::Error, fmt::Display, ops::Range};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Byte offsets of the offending text in the string that was being parsed.
+    pub span: Range<usize>,
+    /// What should have been there, eg. "`<` or `>` after category".
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(span: Range<usize>, expected: impl Into<String>) -> Self {
+        Self {
+            span,
+            expected: expected.into(),
+        }
+    }
+
+    /// An error about `fragment`, which must be a slice of `input`.
+    pub fn at(input: &str, fragment: &str, expected: impl Into<String>) -> Self {
+        let start = offset_in(input, fragment);
+        Self::new(start..start + fragment.len(), expected)
+    }
+
+    /// An error about whatever comes at the very end of `input`.
+    pub fn at_end(input: &str, expected: impl Into<String>) -> Self {
+        Self::new(input.len()..input.len(), expected)
+    }
+
+    /// Converts an error from a nom parser that was run on `input`.
+    pub fn from_nom(
+        input: &str,
+        err: nom::Err<nom::error::Error<&str>>,
+        expected: impl Into<String>,
+    ) -> Self {
+        let start = match err {
+            nom::Err::Error(err) | nom::Err::Failure(err) => offset_in(input, err.input),
+            nom::Err::Incomplete(_) => input.len(),
+        };
+        // point at the character nom stopped at
+        let len = input[start..].chars().next().map_or(0, char::len_utf8);
+        Self::new(start..start + len, expected)
+    }
+
+    /// Moves the span to where it would be in `outer`, for errors found while parsing
+    /// `input`, a slice cut out of it.
+    pub fn within(self, outer: &str, input: &str) -> Self {
+        let offset = offset_in(outer, input);
+        Self {
+            span: self.span.start + offset..self.span.end + offset,
+            ..self
+        }
+    }
+
+    /// Shows the error along with the line it's on, underlining the offending text.
+    /// `source` is the full text the span refers to, and `file_name` says where it came from.
+    pub fn render(&self, source: &str, file_name: &str) -> String {
+        let start = self.span.start.min(source.len());
+        let line_start = source[..start].rfind('\n').map_or(0, |idx| idx + 1);
+        let line_end = source[start..]
+            .find('\n')
+            .map_or(source.len(), |idx| start + idx);
+        let line = &source[line_start..line_end];
+
+        let line_number = source[..start].matches('\n').count() + 1;
+        let column = source[line_start..start].chars().count() + 1;
+        // spans that run onto the next line only get underlined up to the end of this one
+        let underline_len = source[start..self.span.end.clamp(start, line_end)]
+            .chars()
+            .count()
+            .max(1);
+
+        let gutter = " ".repeat(line_number.to_string().len());
+        [
+            format!("error: {self}"),
+            format!("{gutter}--> {file_name}:{line_number}:{column}"),
+            format!("{gutter} |"),
+            format!("{line_number} | {line}"),
+            format!(
+                "{gutter} | {}{}",
+                " ".repeat(column - 1),
+                "^".repeat(underline_len)
+            ),
+        ]
+        .join("\n")
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expected {}", self.expected)
+    }
+}
+
+impl Error for ParseError {}
+
+/// Where `fragment` starts within `input`. Panics unless `fragment` is a slice of `input`.
+pub fn offset_in(input: &str, fragment: &str) -> usize {
+    let offset = (fragment.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize);
+    assert!(
+        offset <= input.len() && offset + fragment.len() <= input.len(),
+        "fragment is not a slice of the input"
+    );
+    offset
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::{character::complete::digit1, IResult};
+
+    #[test]
+    fn test_render() {
+        let source = "in{s<1351:px,qqz}\npx{a=2006:qkq,m>2090:A,rfg}\n";
+        let error = ParseError::new(22..23, "`<` or `>` after category");
+
+        assert_eq!(
+            error.render(source, "input"),
+            [
+                "error: expected `<` or `>` after category",
+                " --> input:2:5",
+                "  |",
+                "2 | px{a=2006:qkq,m>2090:A,rfg}",
+                "  |     ^",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_render_at_end_of_line() {
+        let source = "50 98\n";
+        let error = ParseError::new(5..6, "a third number");
+
+        assert_eq!(
+            error.render(source, "input"),
+            [
+                "error: expected a third number",
+                " --> input:1:6",
+                "  |",
+                "1 | 50 98",
+                "  |      ^",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_within() {
+        let outer = "seeds: 1 2\n\n3 x 5";
+        let line = outer.lines().nth(2).unwrap();
+        let error = ParseError::at(line, &line[2..3], "a number").within(outer, line);
+
+        assert_eq!(error.span, 14..15);
+    }
+
+    #[test]
+    fn test_from_nom() {
+        fn number(input: &str) -> IResult<&str, &str> {
+            digit1(input)
+        }
+
+        let input = "12 ab";
+        let err = number(&input[3..]).unwrap_err();
+        let error = ParseError::from_nom(&input[3..], err, "a number").within(input, &input[3..]);
+
+        assert_eq!(error.span, 3..4);
+    }
+}
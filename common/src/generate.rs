@@ -0,0 +1,9 @@
+use crate::rng::Rng;
+
+/// Produces random, valid inputs for a puzzle, so that solvers can be run on inputs
+/// far larger than the official ones.
+pub trait InputGenerator {
+    /// Generates an input whose size grows with `size`. What exactly `size` counts
+    /// (lines, rows, workflows...) is up to each puzzle.
+    fn generate(size: usize, rng: &mut Rng) -> String;
+}
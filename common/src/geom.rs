@@ -0,0 +1,233 @@
+//! Points, offsets and directions on a grid, with rows counting down from the top and
+//! columns counting right from the left.
+
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::Display,
+    ops::{Add, Mul, Neg},
+};
+
+/// One of the four ways to move on a grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    /// The direction after a quarter turn counterclockwise.
+    pub fn turn_left(self) -> Self {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Left => Direction::Down,
+            Direction::Down => Direction::Right,
+            Direction::Right => Direction::Up,
+        }
+    }
+
+    /// The direction after a quarter turn clockwise.
+    pub fn turn_right(self) -> Self {
+        self.turn_left().reverse()
+    }
+
+    pub fn reverse(self) -> Self {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+
+    pub fn offset(self) -> Offset {
+        match self {
+            Direction::Up => Offset::new(-1, 0),
+            Direction::Down => Offset::new(1, 0),
+            Direction::Left => Offset::new(0, -1),
+            Direction::Right => Offset::new(0, 1),
+        }
+    }
+}
+
+/// A location on a grid. Being unsigned, it can't step off the top or left edges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Point {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Point {
+    pub const ORIGIN: Self = Self::new(0, 0);
+
+    pub const fn new(row: usize, col: usize) -> Self {
+        Self { row, col }
+    }
+
+    /// Moves by `offset`, unless that would go above the top row or left of the first column.
+    pub fn checked_add(self, offset: Offset) -> Option<Self> {
+        Some(Self::new(
+            self.row.checked_add_signed(offset.row)?,
+            self.col.checked_add_signed(offset.col)?,
+        ))
+    }
+
+    /// Takes a single step, unless that would go above the top row or left of the first column.
+    pub fn step(self, direction: Direction) -> Option<Self> {
+        self.checked_add(direction.offset())
+    }
+
+    /// Takes a single step, as long as it lands inside a grid of the given size.
+    pub fn step_within(self, direction: Direction, width: usize, height: usize) -> Option<Self> {
+        self.step(direction)
+            .filter(|point| point.row < height && point.col < width)
+    }
+
+    /// How far apart two points are when moving in the four directions.
+    pub fn manhattan_distance(self, other: Self) -> usize {
+        self.row.abs_diff(other.row) + self.col.abs_diff(other.col)
+    }
+
+    /// How far apart two points are when diagonal moves are allowed too.
+    pub fn chebyshev_distance(self, other: Self) -> usize {
+        self.row
+            .abs_diff(other.row)
+            .max(self.col.abs_diff(other.col))
+    }
+
+    /// The points above, below, left and right of this one, leaving out any that
+    /// would be off the top or left edges.
+    pub fn neighbors4(self) -> impl Iterator<Item = Self> {
+        Offset::CARDINAL
+            .iter()
+            .filter_map(move |offset| self.checked_add(*offset))
+    }
+
+    /// Like [`Point::neighbors4`], but including the diagonals.
+    pub fn neighbors8(self) -> impl Iterator<Item = Self> {
+        Offset::CARDINAL
+            .iter()
+            .chain(&Offset::DIAGONAL)
+            .filter_map(move |offset| self.checked_add(*offset))
+    }
+}
+
+impl From<(usize, usize)> for Point {
+    fn from((row, col): (usize, usize)) -> Self {
+        Self::new(row, col)
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.row, self.col)
+    }
+}
+
+/// The difference between two points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Offset {
+    pub row: isize,
+    pub col: isize,
+}
+
+impl Offset {
+    /// Up, down, left and right, in the same order as [`Direction::ALL`].
+    pub const CARDINAL: [Offset; 4] = [
+        Offset::new(-1, 0),
+        Offset::new(1, 0),
+        Offset::new(0, -1),
+        Offset::new(0, 1),
+    ];
+
+    pub const DIAGONAL: [Offset; 4] = [
+        Offset::new(-1, -1),
+        Offset::new(-1, 1),
+        Offset::new(1, -1),
+        Offset::new(1, 1),
+    ];
+
+    pub const fn new(row: isize, col: isize) -> Self {
+        Self { row, col }
+    }
+}
+
+impl From<Direction> for Offset {
+    fn from(direction: Direction) -> Self {
+        direction.offset()
+    }
+}
+
+impl Add for Offset {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.row + rhs.row, self.col + rhs.col)
+    }
+}
+
+impl Mul<isize> for Offset {
+    type Output = Self;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        Self::new(self.row * rhs, self.col * rhs)
+    }
+}
+
+impl Neg for Offset {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.row, -self.col)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turning() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.turn_right().turn_right(), direction.reverse());
+            assert_eq!(direction.reverse().offset(), -direction.offset());
+        }
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+    }
+
+    #[test]
+    fn test_stepping() {
+        assert_eq!(Point::ORIGIN.step(Direction::Up), None);
+        assert_eq!(Point::ORIGIN.step(Direction::Right), Some(Point::new(0, 1)));
+        assert_eq!(Point::new(2, 0).step_within(Direction::Down, 1, 3), None);
+        assert_eq!(
+            Point::new(1, 1).checked_add(Offset::from(Direction::Left) * 2),
+            None
+        );
+    }
+
+    #[test]
+    fn test_distances() {
+        let (p1, p2) = (Point::new(1, 6), Point::new(5, 11));
+        assert_eq!(p1.manhattan_distance(p2), 9);
+        assert_eq!(p1.chebyshev_distance(p2), 5);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        assert_eq!(
+            Point::ORIGIN.neighbors4().collect::<Vec<_>>(),
+            vec![Point::new(1, 0), Point::new(0, 1)]
+        );
+        assert_eq!(Point::new(1, 1).neighbors8().count(), 8);
+    }
+}
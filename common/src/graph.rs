@@ -0,0 +1,576 @@
+//! A directed graph stored as adjacency lists, with nodes referred to by their index, and
+//! the searches and orderings that puzzles keep asking for.
+//!
+//! Nodes can optionally be given string labels, which are interned as they're added, so
+//! that puzzle input naming nodes (`AAA = (BBB, CCC)`) can be turned into indices once and
+//! then forgotten about.
+
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    ops::{Index, IndexMut},
+};
+
+/// The index of a node, in the order the nodes were added.
+pub type NodeId = usize;
+
+/// An edge, as seen from the node it leaves.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Edge<E> {
+    pub to: NodeId,
+    pub weight: E,
+}
+
+/// A directed graph with data of type `N` on its nodes and `E` on its edges.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Graph<N, E = ()> {
+    nodes: Vec<N>,
+    /// The edges leaving each node, in the order they were added.
+    edges: Vec<Vec<Edge<E>>>,
+    labels: Vec<Option<String>>,
+    ids: HashMap<String, NodeId>,
+}
+
+impl<N, E> Default for Graph<N, E> {
+    fn default() -> Self {
+        Self {
+            nodes: vec![],
+            edges: vec![],
+            labels: vec![],
+            ids: HashMap::new(),
+        }
+    }
+}
+
+impl<N, E> Graph<N, E> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn add_node(&mut self, node: N) -> NodeId {
+        self.nodes.push(node);
+        self.edges.push(vec![]);
+        self.labels.push(None);
+        self.nodes.len() - 1
+    }
+
+    /// Adds a node that can be looked up by `label` later.
+    ///
+    /// # Panics
+    ///
+    /// If another node already has that label.
+    pub fn add_labeled_node(&mut self, label: &str, node: N) -> NodeId {
+        assert!(
+            !self.ids.contains_key(label),
+            "there's already a node labeled {label:?}"
+        );
+        let id = self.add_node(node);
+        self.labels[id] = Some(label.to_string());
+        self.ids.insert(label.to_string(), id);
+        id
+    }
+
+    /// The node labeled `label`, adding one with default data if there isn't one yet, for
+    /// when edges can mention nodes before they're described.
+    pub fn intern(&mut self, label: &str) -> NodeId
+    where
+        N: Default,
+    {
+        match self.ids.get(label) {
+            Some(id) => *id,
+            None => self.add_labeled_node(label, N::default()),
+        }
+    }
+
+    pub fn id_of(&self, label: &str) -> Option<NodeId> {
+        self.ids.get(label).copied()
+    }
+
+    pub fn label_of(&self, id: NodeId) -> Option<&str> {
+        self.labels[id].as_deref()
+    }
+
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId, weight: E) {
+        self.edges[from].push(Edge { to, weight });
+    }
+
+    /// Adds edges both ways between `a` and `b`.
+    pub fn add_undirected_edge(&mut self, a: NodeId, b: NodeId, weight: E)
+    where
+        E: Clone,
+    {
+        self.add_edge(a, b, weight.clone());
+        self.add_edge(b, a, weight);
+    }
+
+    /// The nodes and their data, in the order they were added.
+    pub fn nodes(&self) -> impl Iterator<Item = (NodeId, &N)> {
+        self.nodes.iter().enumerate()
+    }
+
+    /// The edges leaving `id`.
+    pub fn edges(&self, id: NodeId) -> &[Edge<E>] {
+        &self.edges[id]
+    }
+
+    /// The nodes that the edges leaving `id` go to.
+    pub fn neighbors(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.edges[id].iter().map(|edge| edge.to)
+    }
+
+    /// Every node that can be reached from `start`, along with how many edges it takes to
+    /// get there, closest first.
+    pub fn bfs(&self, start: NodeId) -> Vec<(NodeId, usize)> {
+        let mut seen = vec![false; self.len()];
+        let mut queue = VecDeque::from([(start, 0)]);
+        let mut visited = vec![];
+        seen[start] = true;
+        while let Some((id, depth)) = queue.pop_front() {
+            visited.push((id, depth));
+            for next in self.neighbors(id) {
+                if !seen[next] {
+                    seen[next] = true;
+                    queue.push_back((next, depth + 1));
+                }
+            }
+        }
+        visited
+    }
+
+    /// Every node that can be reached from `start`, in the order a depth-first search
+    /// first comes across them.
+    pub fn dfs(&self, start: NodeId) -> Vec<NodeId> {
+        let mut seen = vec![false; self.len()];
+        let mut stack = vec![start];
+        let mut visited = vec![];
+        while let Some(id) = stack.pop() {
+            if seen[id] {
+                continue;
+            }
+            seen[id] = true;
+            visited.push(id);
+            // pushed backwards, so that the first edge is followed first
+            stack.extend(
+                self.edges[id]
+                    .iter()
+                    .rev()
+                    .map(|edge| edge.to)
+                    .filter(|next| !seen[*next]),
+            );
+        }
+        visited
+    }
+
+    /// The cheapest way to get to every node from `start`, with `cost` giving the cost of
+    /// each edge, or `None` for nodes that can't be reached.
+    pub fn dijkstra(&self, start: NodeId, cost: impl Fn(&E) -> u64) -> Vec<Option<u64>> {
+        let mut best = vec![None; self.len()];
+        let mut queue = BinaryHeap::from([Reverse((0, start))]);
+        while let Some(Reverse((total, id))) = queue.pop() {
+            if best[id].is_some() {
+                continue;
+            }
+            best[id] = Some(total);
+            for edge in self.edges(id) {
+                if best[edge.to].is_none() {
+                    queue.push(Reverse((total + cost(&edge.weight), edge.to)));
+                }
+            }
+        }
+        best
+    }
+
+    /// The cheapest path from `start` to `goal` and its cost, if there is one.
+    ///
+    /// `heuristic` guesses the cost of getting from a node to `goal`, and has to never
+    /// guess too high for the path to be the cheapest. A heuristic that always guesses
+    /// zero makes this Dijkstra's algorithm. Nodes can be expanded more than once when the
+    /// heuristic's guesses drop by more than the cost of an edge, so one that doesn't is
+    /// quicker.
+    pub fn astar(
+        &self,
+        start: NodeId,
+        goal: NodeId,
+        cost: impl Fn(&E) -> u64,
+        heuristic: impl Fn(NodeId) -> u64,
+    ) -> Option<(u64, Vec<NodeId>)> {
+        let mut best: Vec<Option<u64>> = vec![None; self.len()];
+        let mut previous: Vec<Option<NodeId>> = vec![None; self.len()];
+        let mut queue = BinaryHeap::from([Reverse((heuristic(start), 0, start))]);
+        best[start] = Some(0);
+
+        while let Some(Reverse((_, total, id))) = queue.pop() {
+            // a cheaper way here turned up after this was queued; nodes can be reached more
+            // cheaply after they've been expanded when the heuristic isn't consistent
+            if best[id].is_some_and(|best| total > best) {
+                continue;
+            }
+            if id == goal {
+                let mut path = vec![goal];
+                while let Some(prev) = previous[*path.last().unwrap()] {
+                    path.push(prev);
+                }
+                path.reverse();
+                return Some((total, path));
+            }
+
+            for edge in self.edges(id) {
+                let next_total = total + cost(&edge.weight);
+                if best[edge.to].is_none_or(|best| next_total < best) {
+                    best[edge.to] = Some(next_total);
+                    previous[edge.to] = Some(id);
+                    queue.push(Reverse((
+                        next_total + heuristic(edge.to),
+                        next_total,
+                        edge.to,
+                    )));
+                }
+            }
+        }
+        None
+    }
+
+    /// Groups the nodes into strongly connected components, where every node can reach
+    /// every other one in the same group, using Tarjan's algorithm.
+    ///
+    /// The groups come out in reverse topological order: no group has edges to a later one.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<NodeId>> {
+        let mut tarjan = Tarjan {
+            graph: self,
+            next_index: 0,
+            index: vec![None; self.len()],
+            low_link: vec![0; self.len()],
+            on_stack: vec![false; self.len()],
+            stack: vec![],
+            components: vec![],
+        };
+        for id in 0..self.len() {
+            if tarjan.index[id].is_none() {
+                tarjan.visit(id);
+            }
+        }
+        tarjan.components
+    }
+
+    /// The nodes ordered so that every edge goes from an earlier node to a later one, or
+    /// `None` if there's a cycle and that isn't possible.
+    pub fn topological_sort(&self) -> Option<Vec<NodeId>> {
+        let mut incoming = vec![0; self.len()];
+        for edges in &self.edges {
+            for edge in edges {
+                incoming[edge.to] += 1;
+            }
+        }
+
+        let mut ready: Vec<_> = (0..self.len()).filter(|id| incoming[*id] == 0).collect();
+        ready.reverse();
+        let mut order = vec![];
+        while let Some(id) = ready.pop() {
+            order.push(id);
+            for next in self.neighbors(id) {
+                incoming[next] -= 1;
+                if incoming[next] == 0 {
+                    ready.push(next);
+                }
+            }
+        }
+
+        // anything on a cycle never runs out of incoming edges
+        (order.len() == self.len()).then_some(order)
+    }
+
+    /// Groups the nodes that are connected to each other when edges are allowed to be
+    /// followed either way, with each group in order and the groups ordered by their
+    /// first node.
+    pub fn connected_components(&self) -> Vec<Vec<NodeId>> {
+        // union-find, always keeping the smallest node as the root
+        let mut parent: Vec<NodeId> = (0..self.len()).collect();
+        fn root(parent: &mut [NodeId], mut id: NodeId) -> NodeId {
+            while parent[id] != id {
+                parent[id] = parent[parent[id]];
+                id = parent[id];
+            }
+            id
+        }
+        for (from, edges) in self.edges.iter().enumerate() {
+            for edge in edges {
+                let (a, b) = (root(&mut parent, from), root(&mut parent, edge.to));
+                parent[a.max(b)] = a.min(b);
+            }
+        }
+
+        let mut components: Vec<Vec<NodeId>> = vec![];
+        let mut component_of: Vec<Option<usize>> = vec![None; self.len()];
+        for id in 0..self.len() {
+            let root = root(&mut parent, id);
+            match component_of[root] {
+                Some(idx) => components[idx].push(id),
+                None => {
+                    component_of[root] = Some(components.len());
+                    components.push(vec![id]);
+                }
+            }
+        }
+        components
+    }
+}
+
+impl<N, E> Index<NodeId> for Graph<N, E> {
+    type Output = N;
+
+    fn index(&self, id: NodeId) -> &Self::Output {
+        &self.nodes[id]
+    }
+}
+
+impl<N, E> IndexMut<NodeId> for Graph<N, E> {
+    fn index_mut(&mut self, id: NodeId) -> &mut Self::Output {
+        &mut self.nodes[id]
+    }
+}
+
+/// The bookkeeping for [`Graph::strongly_connected_components`].
+struct Tarjan<'a, N, E> {
+    graph: &'a Graph<N, E>,
+    next_index: usize,
+    /// The order nodes were first visited in.
+    index: Vec<Option<usize>>,
+    /// The earliest visited node on the stack that each node is known to reach.
+    low_link: Vec<usize>,
+    on_stack: Vec<bool>,
+    stack: Vec<NodeId>,
+    components: Vec<Vec<NodeId>>,
+}
+
+impl<N, E> Tarjan<'_, N, E> {
+    /// Runs the depth-first search from `root`, keeping its own stack of the nodes being
+    /// visited and how many of their edges have been followed so far, so that long chains
+    /// of nodes can't overflow the call stack.
+    fn visit(&mut self, root: NodeId) {
+        self.enter(root);
+        let mut path = vec![(root, 0)];
+        while let Some((id, edge)) = path.last_mut() {
+            let id = *id;
+            if let Some(next) = self.graph.edges(id).get(*edge).map(|edge| edge.to) {
+                *edge += 1;
+                match self.index[next] {
+                    None => {
+                        self.enter(next);
+                        path.push((next, 0));
+                    }
+                    Some(index) if self.on_stack[next] => {
+                        self.low_link[id] = self.low_link[id].min(index);
+                    }
+                    Some(_) => {}
+                }
+                continue;
+            }
+
+            path.pop();
+            if let Some(&(parent, _)) = path.last() {
+                self.low_link[parent] = self.low_link[parent].min(self.low_link[id]);
+            }
+
+            // nothing here reaches back any further, so it's the root of a component made of
+            // everything above it on the stack
+            if Some(self.low_link[id]) == self.index[id] {
+                let mut component = vec![];
+                loop {
+                    let member = self.stack.pop().unwrap();
+                    self.on_stack[member] = false;
+                    component.push(member);
+                    if member == id {
+                        break;
+                    }
+                }
+                component.reverse();
+                self.components.push(component);
+            }
+        }
+    }
+
+    fn enter(&mut self, id: NodeId) {
+        self.index[id] = Some(self.next_index);
+        self.low_link[id] = self.next_index;
+        self.next_index += 1;
+        self.stack.push(id);
+        self.on_stack[id] = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::{agree, Check};
+
+    /// a -> b -> c -> a, c -> d, and e off on its own.
+    fn example() -> Graph<(), u64> {
+        let mut graph = Graph::new();
+        for label in ["a", "b", "c", "d", "e"] {
+            graph.add_labeled_node(label, ());
+        }
+        for (from, to, weight) in [("a", "b", 1), ("b", "c", 2), ("c", "a", 3), ("c", "d", 10)] {
+            let (from, to) = (graph.intern(from), graph.intern(to));
+            graph.add_edge(from, to, weight);
+        }
+        graph
+    }
+
+    #[test]
+    fn test_labels() {
+        let mut graph = example();
+        assert_eq!(graph.id_of("c"), Some(2));
+        assert_eq!(graph.label_of(3), Some("d"));
+        assert_eq!(graph.intern("f"), 5);
+        assert_eq!(graph.len(), 6);
+    }
+
+    #[test]
+    fn test_searches() {
+        let graph = example();
+        assert_eq!(graph.bfs(1), vec![(1, 0), (2, 1), (0, 2), (3, 2)]);
+        assert_eq!(graph.dfs(0), vec![0, 1, 2, 3]);
+        assert_eq!(
+            graph.dijkstra(1, |weight| *weight),
+            vec![Some(5), Some(0), Some(2), Some(12), None]
+        );
+        assert_eq!(
+            graph.astar(0, 3, |weight| *weight, |_| 0),
+            Some((13, vec![0, 1, 2, 3]))
+        );
+        assert_eq!(graph.astar(0, 4, |weight| *weight, |_| 0), None);
+    }
+
+    #[test]
+    fn test_astar_inconsistent_heuristic() {
+        // the heuristic puts off going through b, so c is first reached the long way round
+        // through a, and has to be expanded again once b finds the shorter way
+        let mut graph = Graph::new();
+        for label in ["s", "a", "b", "c", "g"] {
+            graph.add_labeled_node(label, ());
+        }
+        for (from, to, weight) in [(0, 1, 1), (0, 2, 1), (1, 3, 3), (2, 3, 1), (3, 4, 5)] {
+            graph.add_edge(from, to, weight);
+        }
+        let heuristic = |id| if id == 2 { 6 } else { 0 };
+        assert_eq!(
+            graph.astar(0, 4, |weight| *weight, heuristic),
+            Some((7, vec![0, 2, 3, 4]))
+        );
+    }
+
+    #[test]
+    fn test_components() {
+        let graph = example();
+        assert_eq!(
+            graph.strongly_connected_components(),
+            vec![vec![3], vec![0, 1, 2], vec![4]]
+        );
+        assert_eq!(
+            graph.connected_components(),
+            vec![vec![0, 1, 2, 3], vec![4]]
+        );
+        assert_eq!(graph.topological_sort(), None);
+
+        let mut acyclic = graph.clone();
+        acyclic.edges[2].retain(|edge| edge.to != 0);
+        assert_eq!(acyclic.topological_sort(), Some(vec![0, 1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn test_long_cycle() {
+        let mut graph = Graph::new();
+        let size = 100_000;
+        for _ in 0..size {
+            graph.add_node(());
+        }
+        for id in 0..size {
+            graph.add_edge(id, (id + 1) % size, ());
+        }
+        let components = graph.strongly_connected_components();
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0].len(), size);
+    }
+
+    /// Graphs on `size` nodes as lists of (from, to, weight), on a grid-like set of
+    /// positions so that the distance between positions works as a heuristic.
+    fn build_graph(size: usize, edges: &[(usize, usize, u64)]) -> Graph<(), u64> {
+        let mut graph = Graph::new();
+        for _ in 0..size {
+            graph.add_node(());
+        }
+        for &(from, to, weight) in edges {
+            graph.add_edge(
+                from % size,
+                to % size,
+                weight + (from % size).abs_diff(to % size) as u64,
+            );
+        }
+        graph
+    }
+
+    #[test]
+    fn test_searches_agree() {
+        Check::new().run(
+            |rng| {
+                let size = rng.range(1..8);
+                let edges: Vec<_> = (0..rng.range(0..16))
+                    .map(|_| (rng.range(0..8), rng.range(0..8), rng.below(5)))
+                    .collect();
+                (size, edges, rng.range(0..8))
+            },
+            |(size, edges, goal)| {
+                let size = (*size).max(1);
+                let graph = build_graph(size, edges);
+                let goal = goal % size;
+
+                let distances = graph.dijkstra(0, |weight| *weight);
+                let path = graph.astar(0, goal, |weight| *weight, |id| id.abs_diff(goal) as u64);
+                agree(path.as_ref().map(|(cost, _)| *cost), distances[goal])?;
+
+                // the path has to actually be made of edges, and add up to its cost
+                if let Some((cost, nodes)) = path {
+                    let total: Option<u64> = nodes
+                        .windows(2)
+                        .map(|pair| {
+                            graph
+                                .edges(pair[0])
+                                .iter()
+                                .filter(|edge| edge.to == pair[1])
+                                .map(|edge| edge.weight)
+                                .min()
+                        })
+                        .sum();
+                    agree(total, Some(cost))?;
+                }
+
+                let reachable: Vec<bool> = distances.iter().map(Option::is_some).collect();
+                let mut by_bfs = vec![false; size];
+                graph.bfs(0).iter().for_each(|(id, _)| by_bfs[*id] = true);
+                agree(by_bfs, reachable)?;
+
+                // every node reaches every other one in its component, and topological
+                // sorting only works when they're all on their own without self-loops
+                let components = graph.strongly_connected_components();
+                for component in &components {
+                    for &id in component {
+                        let reached = graph.dfs(id);
+                        agree(component.iter().all(|other| reached.contains(other)), true)?;
+                    }
+                }
+                let acyclic = components.len() == size
+                    && (0..size).all(|id| !graph.neighbors(id).any(|next| next == id));
+                agree(graph.topological_sort().is_some(), acyclic)
+            },
+        );
+    }
+}
@@ -0,0 +1,429 @@
+//! A rectangular grid of cells, the way most of the puzzles with a map lay out their input.
+//!
+//! Cells are stored flat, row after row, and addressed by [`Point`]s with the origin in the
+//! top left corner. Rows are contiguous, so they can be handed out as slices; columns are
+//! only ever iterated over.
+
+use crate::geom::Direction;
+pub use crate::geom::Point;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut, Range},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Lays out `cells` in rows of `width`.
+    pub fn new(width: usize, cells: Vec<T>) -> Self {
+        let height = cells.len().checked_div(width).unwrap_or(0);
+        assert_eq!(
+            width * height,
+            cells.len(),
+            "{} cells don't fill rows of {width}",
+            cells.len()
+        );
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Builds a grid from rows, which all have to be the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+        for (row_idx, row) in rows.into_iter().enumerate() {
+            assert_eq!(
+                row.len(),
+                width,
+                "row {row_idx} is {} wide, but the first row is {width} wide",
+                row.len()
+            );
+            cells.extend(row);
+        }
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses a grid with one row per line, turning each character into a cell.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> T) -> Self {
+        Self::from_rows(
+            input
+                .lines()
+                .map(|line| line.chars().map(&mut cell).collect())
+                .collect(),
+        )
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        point.row < self.height && point.col < self.width
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.contains(point)
+            .then(|| &self.cells[point.row * self.width + point.col])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.contains(point)
+            .then(|| &mut self.cells[point.row * self.width + point.col])
+    }
+
+    /// Gets a cell as if the grid were tiled infinitely in every direction.
+    pub fn get_wrapping(&self, row: isize, col: isize) -> &T {
+        assert!(!self.cells.is_empty(), "an empty grid has nothing to wrap");
+        let row = row.rem_euclid(self.height as isize) as usize;
+        let col = col.rem_euclid(self.width as isize) as usize;
+        &self[Point::new(row, col)]
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        &mut self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator {
+        (0..self.height).map(|row| self.row(row))
+    }
+
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [T]> {
+        // `chunks_mut` would panic on a zero width
+        let width = self.width.max(1);
+        self.cells.chunks_mut(width)
+    }
+
+    pub fn column(&self, col: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        assert!(col < self.width, "column {col} is outside of the grid");
+        (0..self.height).map(move |row| &self.cells[row * self.width + col])
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.width).map(|col| self.column(col))
+    }
+
+    /// Every cell, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    /// Every point in the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| Point::new(row, col)))
+    }
+
+    /// Every cell along with its location, row by row.
+    pub fn indexed(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(&self.cells)
+    }
+
+    /// Finds the first cell, row by row, that matches `predicate`.
+    pub fn position(&self, predicate: impl Fn(&T) -> bool) -> Option<Point> {
+        let idx = self.cells.iter().position(predicate)?;
+        Some(Point::new(idx / self.width, idx % self.width))
+    }
+
+    /// The points above, below, left and right of `point` that are inside the grid.
+    pub fn neighbors4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        point
+            .neighbors4()
+            .filter(|neighbor| self.contains(*neighbor))
+    }
+
+    /// Like [`Grid::neighbors4`], but including the diagonals.
+    pub fn neighbors8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        point
+            .neighbors8()
+            .filter(|neighbor| self.contains(*neighbor))
+    }
+
+    /// Takes a single step from `point`, as long as it stays inside the grid.
+    pub fn step(&self, point: Point, direction: Direction) -> Option<Point> {
+        point.step_within(direction, self.width, self.height)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Builds a grid of the given size by looking up each of its points in this one.
+    fn rearranged(&self, width: usize, height: usize, source: impl Fn(Point) -> Point) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..height)
+            .flat_map(|row| (0..width).map(move |col| Point::new(row, col)))
+            .map(|point| self[source(point)].clone())
+            .collect();
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Swaps rows for columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rearranged(self.height, self.width, |point| {
+            Point::new(point.col, point.row)
+        })
+    }
+
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let height = self.height;
+        self.rearranged(height, self.width, |point| {
+            Point::new(height - 1 - point.col, point.row)
+        })
+    }
+
+    pub fn rotate_counterclockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let width = self.width;
+        self.rearranged(self.height, width, |point| {
+            Point::new(point.col, width - 1 - point.row)
+        })
+    }
+
+    /// Mirrors the grid left to right.
+    pub fn flip_horizontal(&self) -> Self
+    where
+        T: Clone,
+    {
+        let width = self.width;
+        self.rearranged(width, self.height, |point| {
+            Point::new(point.row, width - 1 - point.col)
+        })
+    }
+
+    /// Mirrors the grid top to bottom.
+    pub fn flip_vertical(&self) -> Self
+    where
+        T: Clone,
+    {
+        let height = self.height;
+        self.rearranged(self.width, height, |point| {
+            Point::new(height - 1 - point.row, point.col)
+        })
+    }
+
+    /// A window onto part of the grid, with its own `(0, 0)` at the top left of `rows` and
+    /// `cols`.
+    pub fn view(&self, rows: Range<usize>, cols: Range<usize>) -> GridView<'_, T> {
+        assert!(
+            rows.start <= rows.end && rows.end <= self.height,
+            "rows {rows:?} are outside of the grid"
+        );
+        assert!(
+            cols.start <= cols.end && cols.end <= self.width,
+            "columns {cols:?} are outside of the grid"
+        );
+        GridView {
+            grid: self,
+            rows,
+            cols,
+        }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{point} is outside of the grid"))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut Self::Output {
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{point} is outside of the grid"))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    /// Shows each cell in turn, with a line per row.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (idx, row) in self.rows().enumerate() {
+            if idx > 0 {
+                writeln!(f)?;
+            }
+            row.iter().try_for_each(|cell| write!(f, "{cell}"))?;
+        }
+        Ok(())
+    }
+}
+
+/// A borrowed rectangle of a [`Grid`], from [`Grid::view`].
+#[derive(Debug, Clone)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    rows: Range<usize>,
+    cols: Range<usize>,
+}
+
+impl<'a, T> GridView<'a, T> {
+    pub fn width(&self) -> usize {
+        self.cols.len()
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn get(&self, point: Point) -> Option<&'a T> {
+        (point.row < self.height() && point.col < self.width()).then(|| {
+            &self.grid[Point::new(self.rows.start + point.row, self.cols.start + point.col)]
+        })
+    }
+
+    pub fn row(&self, row: usize) -> &'a [T] {
+        assert!(row < self.height(), "row {row} is outside of the view");
+        &self.grid.row(self.rows.start + row)[self.cols.clone()]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> + '_ {
+        (0..self.height()).map(|row| self.row(row))
+    }
+
+    /// Copies the view out into a grid of its own.
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_rows(self.rows().map(<[T]>::to_vec).collect())
+    }
+}
+
+impl<T> Index<Point> for GridView<'_, T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{point} is outside of the view"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits() -> Grid<u32> {
+        Grid::parse("123\n456", |c| c.to_digit(10).unwrap())
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        let grid = digits();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Point::new(1, 0)], 4);
+        assert_eq!(grid.get(Point::new(2, 0)), None);
+        assert_eq!(grid.get(Point::new(0, 3)), None);
+        assert_eq!(grid.to_string(), "123\n456");
+    }
+
+    #[test]
+    #[should_panic(expected = "row 1 is 2 wide, but the first row is 3 wide")]
+    fn test_parse_ragged() {
+        Grid::parse("123\n45", |c| c);
+    }
+
+    #[test]
+    fn test_get_wrapping() {
+        let grid = digits();
+        assert_eq!(*grid.get_wrapping(-1, -1), 6);
+        assert_eq!(*grid.get_wrapping(2, 4), 2);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = digits();
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            vec![&[1, 2, 3], &[4, 5, 6]]
+        );
+        let columns: Vec<Vec<_>> = grid.columns().map(|col| col.copied().collect()).collect();
+        assert_eq!(columns, vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = digits();
+        assert_eq!(
+            grid.neighbors4(Point::new(0, 0)).collect::<Vec<_>>(),
+            vec![Point::new(1, 0), Point::new(0, 1)]
+        );
+        assert_eq!(grid.neighbors8(Point::new(1, 1)).count(), 5);
+    }
+
+    #[test]
+    fn test_rearranging() {
+        let grid = digits();
+        assert_eq!(grid.transpose().to_string(), "14\n25\n36");
+        assert_eq!(grid.rotate_clockwise().to_string(), "41\n52\n63");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "36\n25\n14");
+        assert_eq!(grid.flip_horizontal().to_string(), "321\n654");
+        assert_eq!(grid.flip_vertical().to_string(), "456\n123");
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+    }
+
+    #[test]
+    fn test_view() {
+        let grid = digits();
+        let view = grid.view(0..2, 1..3);
+        assert_eq!((view.width(), view.height()), (2, 2));
+        assert_eq!(view[Point::new(1, 0)], 5);
+        assert_eq!(view.get(Point::new(0, 2)), None);
+        assert_eq!(view.to_grid().to_string(), "23\n56");
+    }
+}
@@ -0,0 +1,79 @@
+use std::{fmt::Display, path::PathBuf};
+
+/// The Advent of Code website, which puzzle inputs are fetched from
+/// and answers are submitted to, unless the config says otherwise.
+pub const SITE_URL: &str = "https://adventofcode.com";
+
+/// Identifies a single day's puzzle across all years of the event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PuzzleId {
+    pub year: u16,
+    pub day: u32,
+}
+
+impl PuzzleId {
+    pub fn new(year: u16, day: u32) -> Self {
+        Self { year, day }
+    }
+
+    /// The directory of the crate for this puzzle, relative to the workspace root.
+    /// The 2023 puzzles predate multi-year support and live at the top level
+    /// (eg. `puzzle05`), while other years get a directory of their own (eg. `2024/puzzle05`).
+    pub fn crate_dir(&self) -> PathBuf {
+        let crate_dir = format!("puzzle{:02}", self.day);
+        if self.year == crate::puzzle::DEFAULT_YEAR {
+            PathBuf::from(crate_dir)
+        } else {
+            PathBuf::from(self.year.to_string()).join(crate_dir)
+        }
+    }
+
+    pub fn puzzle_url(&self, base_url: &str) -> String {
+        format!("{base_url}/{}/day/{}", self.year, self.day)
+    }
+
+    pub fn input_url(&self, base_url: &str) -> String {
+        format!("{}/input", self.puzzle_url(base_url))
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{:02}", self.year, self.day)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crate_dir() {
+        assert_eq!(
+            PuzzleId::new(2023, 5).crate_dir(),
+            PathBuf::from("puzzle05")
+        );
+        assert_eq!(
+            PuzzleId::new(2024, 12).crate_dir(),
+            PathBuf::from("2024/puzzle12")
+        );
+    }
+
+    #[test]
+    fn test_urls() {
+        let id = PuzzleId::new(2022, 7);
+        assert_eq!(
+            id.input_url(SITE_URL),
+            "https://adventofcode.com/2022/day/7/input"
+        );
+        assert_eq!(
+            id.puzzle_url(SITE_URL),
+            "https://adventofcode.com/2022/day/7"
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(PuzzleId::new(2023, 5).to_string(), "2023/05");
+    }
+}
@@ -0,0 +1,210 @@
+//! Sets of integers stored as the ranges they cover, for puzzles that push whole ranges of
+//! values around instead of one value at a time.
+
+use std::{
+    iter::Sum,
+    ops::{Range, Sub},
+};
+
+/// A set of values, kept as sorted, disjoint, non-empty ranges. Ranges that touch are
+/// merged, so two sets with the same values always compare equal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T: Ord + Copy> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self { ranges: vec![] }
+    }
+
+    fn normalized(mut ranges: Vec<Range<T>>) -> Self {
+        ranges.retain(|range| range.start < range.end);
+        ranges.sort_by_key(|range| range.start);
+
+        let mut merged: Vec<Range<T>> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+        Self { ranges: merged }
+    }
+
+    /// The ranges in the set, in order.
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The smallest value in the set.
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|range| range.start)
+    }
+
+    /// The smallest range that covers the whole set.
+    pub fn bounds(&self) -> Option<Range<T>> {
+        Some(self.ranges.first()?.start..self.ranges.last()?.end)
+    }
+
+    /// How many values are in the set.
+    pub fn len(&self) -> T
+    where
+        T: Sub<Output = T> + Sum,
+    {
+        self.ranges
+            .iter()
+            .map(|range| range.end - range.start)
+            .sum()
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        let idx = self.ranges.partition_point(|range| range.end <= *value);
+        self.ranges
+            .get(idx)
+            .is_some_and(|range| range.start <= *value)
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.difference(other).is_empty()
+    }
+
+    pub fn insert(&mut self, range: Range<T>) {
+        let mut ranges = std::mem::take(&mut self.ranges);
+        ranges.push(range);
+        *self = Self::normalized(ranges);
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        Self::normalized(self.ranges.iter().chain(&other.ranges).cloned().collect())
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (mut ours, mut theirs) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+        let mut ranges = vec![];
+        while let (Some(our), Some(their)) = (ours.peek(), theirs.peek()) {
+            let overlap = our.start.max(their.start)..our.end.min(their.end);
+            if overlap.start < overlap.end {
+                ranges.push(overlap);
+            }
+            // whichever range ends first can't overlap anything else
+            if our.end <= their.end {
+                ours.next();
+            } else {
+                theirs.next();
+            }
+        }
+        Self { ranges }
+    }
+
+    /// The values in `bounds` that aren't in this set.
+    pub fn complement(&self, bounds: Range<T>) -> Self {
+        let mut ranges = vec![];
+        let mut start = bounds.start;
+        for range in &self.ranges {
+            if range.start >= bounds.end {
+                break;
+            }
+            if start < range.start {
+                ranges.push(start..range.start);
+            }
+            start = start.max(range.end);
+        }
+        if start < bounds.end {
+            ranges.push(start..bounds.end);
+        }
+        Self { ranges }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        match self.bounds() {
+            Some(bounds) => self.intersection(&other.complement(bounds)),
+            None => Self::new(),
+        }
+    }
+}
+
+impl<T: Ord + Copy> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord + Copy> From<Range<T>> for IntervalSet<T> {
+    fn from(range: Range<T>) -> Self {
+        Self::normalized(vec![range])
+    }
+}
+
+impl<T: Ord + Copy> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        Self::normalized(iter.into_iter().collect())
+    }
+}
+
+impl<'a, T> IntoIterator for &'a IntervalSet<T> {
+    type Item = &'a Range<T>;
+    type IntoIter = std::slice::Iter<'a, Range<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ranges.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::{agree, Check};
+
+    #[test]
+    fn test_normalized() {
+        let set: IntervalSet<i64> = [5..8, 1..3, 3..4, 7..10, 12..12].into_iter().collect();
+        assert_eq!(set.ranges(), &[1..4, 5..10]);
+        assert_eq!(set.len(), 8);
+        assert_eq!(set.min(), Some(1));
+        assert!(set.contains(&3) && set.contains(&5) && !set.contains(&4));
+    }
+
+    #[test]
+    fn test_operations() {
+        let set1: IntervalSet<u64> = [1..5, 8..12].into_iter().collect();
+        let set2: IntervalSet<u64> = [3..9, 11..20].into_iter().collect();
+
+        assert_eq!(set1.union(&set2), IntervalSet::from(1..20));
+        assert_eq!(set1.intersection(&set2).ranges(), &[3..5, 8..9, 11..12]);
+        assert_eq!(set1.difference(&set2).ranges(), &[1..3, 9..11]);
+        assert_eq!(set1.complement(0..10).ranges(), &[0..1, 5..8]);
+        assert!(IntervalSet::from(9..11).is_subset(&set1));
+        assert!(!IntervalSet::from(4..9).is_subset(&set1));
+    }
+
+    /// Sets of values below 32, as bitmasks.
+    fn to_set(mask: u32) -> IntervalSet<u32> {
+        (0..32)
+            .filter(|bit| mask & (1 << bit) != 0)
+            .map(|bit| bit..bit + 1)
+            .collect()
+    }
+
+    #[test]
+    fn test_operations_match_bitmasks() {
+        Check::new().run(
+            |rng| (rng.next_u64() as u32, rng.next_u64() as u32),
+            |&(mask1, mask2)| {
+                let (set1, set2) = (to_set(mask1), to_set(mask2));
+                agree(set1.union(&set2), to_set(mask1 | mask2))?;
+                agree(set1.intersection(&set2), to_set(mask1 & mask2))?;
+                agree(set1.difference(&set2), to_set(mask1 & !mask2))?;
+                agree(set1.complement(0..32), to_set(!mask1))?;
+                agree(set1.len(), mask1.count_ones())
+            },
+        );
+    }
+}
@@ -1 +1,21 @@
+pub mod answer;
+pub mod check;
+pub mod config;
+pub mod crypt;
+pub mod cycle;
+pub mod diagnostic;
+pub mod generate;
+pub mod geom;
+pub mod graph;
+pub mod grid;
+pub mod id;
+pub mod interval;
+pub mod math;
+pub mod memo;
+pub mod num;
+pub mod parallel;
+pub mod parse;
+pub mod piecewise;
 pub mod puzzle;
+pub mod registry;
+pub mod rng;
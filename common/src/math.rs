@@ -0,0 +1,291 @@
+//! Number theory for puzzles where several things go round in loops of different lengths
+//! and the question is when they all line up.
+//!
+//! `gcd` and `lcm` work on any of the primitive integers that puzzles tend to use, while
+//! the modular arithmetic is done in `i128` so that products of two `u64`-sized moduli
+//! don't overflow along the way. Anything that can still overflow has a checked variant.
+
+use std::{
+    fmt::Display,
+    ops::{Div, Rem},
+};
+
+/// The primitive integers the functions in this module work on.
+pub trait Integer: Copy + Ord + Div<Output = Self> + Rem<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+
+    /// The absolute value, which for the signed types panics on their smallest value.
+    fn abs(self) -> Self;
+
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! integer {
+    ($($t:ty),* ; $abs:expr) => {
+        $(impl Integer for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn abs(self) -> Self {
+                $abs(self)
+            }
+
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+        })*
+    };
+}
+
+integer!(u32, u64, usize ; |n| n);
+integer!(i32, i64, i128 ; |n: Self| n.checked_abs().expect("absolute value overflowed"));
+
+/// The greatest common divisor, which is never negative, and is zero only when both
+/// `a` and `b` are.
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The least common multiple, which is never negative.
+///
+/// # Panics
+///
+/// If the result doesn't fit in `T`; see [`checked_lcm`].
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    checked_lcm(a, b).expect("least common multiple overflowed")
+}
+
+/// The least common multiple, or `None` if it doesn't fit in `T`.
+pub fn checked_lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    (a.abs() / gcd(a, b)).checked_mul(b.abs())
+}
+
+/// The greatest common divisor of all of `values`, which is zero if there aren't any.
+pub fn gcd_all<T: Integer>(values: impl IntoIterator<Item = T>) -> T {
+    values.into_iter().fold(T::ZERO, gcd)
+}
+
+/// The least common multiple of all of `values`, which is one if there aren't any.
+///
+/// # Panics
+///
+/// If the result doesn't fit in `T`; see [`checked_lcm_all`].
+pub fn lcm_all<T: Integer>(values: impl IntoIterator<Item = T>) -> T {
+    checked_lcm_all(values).expect("least common multiple overflowed")
+}
+
+/// The least common multiple of all of `values`, or `None` if it doesn't fit in `T`.
+pub fn checked_lcm_all<T: Integer>(values: impl IntoIterator<Item = T>) -> Option<T> {
+    values
+        .into_iter()
+        .try_fold(T::ONE, |acc, value| checked_lcm(acc, value))
+}
+
+/// Finds `(g, x, y)` such that `a * x + b * y == g`, where `g` is the greatest common
+/// divisor of `a` and `b`.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+
+    // the remainders can come out negative when the inputs are
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// The `x` in `0..modulus` with `a * x` congruent to one, if `a` and `modulus` are coprime.
+pub fn mod_inverse(a: i128, modulus: i128) -> Option<i128> {
+    let (g, x, _) = extended_gcd(a.rem_euclid(modulus), modulus);
+    (g == 1).then(|| x.rem_euclid(modulus))
+}
+
+/// All the integers that leave `residue` when divided by `modulus`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Congruence {
+    /// Always in `0..modulus`.
+    pub residue: i128,
+    pub modulus: i128,
+}
+
+impl Congruence {
+    /// # Panics
+    ///
+    /// If `modulus` isn't positive.
+    pub fn new(residue: i128, modulus: i128) -> Self {
+        assert!(modulus > 0, "modulus {modulus} is not positive");
+        Self {
+            residue: residue.rem_euclid(modulus),
+            modulus,
+        }
+    }
+
+    pub fn contains(&self, value: i128) -> bool {
+        value.rem_euclid(self.modulus) == self.residue
+    }
+
+    /// The smallest of the integers that is at least `bound`.
+    pub fn smallest_at_least(&self, bound: i128) -> i128 {
+        bound + (self.residue - bound).rem_euclid(self.modulus)
+    }
+
+    /// The integers in both congruences, which (if there are any) are another congruence,
+    /// modulo the least common multiple of the two moduli.
+    pub fn combine(&self, other: &Self) -> Result<Self, CrtError> {
+        let (g, _, _) = extended_gcd(self.modulus, other.modulus);
+        let difference = other.residue - self.residue;
+        if difference % g != 0 {
+            return Err(CrtError::NoSolution);
+        }
+
+        // we need a `k` with `self.residue + k * self.modulus` in `other`, which comes down
+        // to solving `k * (self.modulus / g) = difference / g` modulo `other.modulus / g`
+        let reduced = other.modulus / g;
+        let inverse = mod_inverse(self.modulus / g, reduced).expect("dividing out the gcd");
+        let k = (difference / g)
+            .rem_euclid(reduced)
+            .checked_mul(inverse)
+            .ok_or(CrtError::Overflow)?
+            % reduced;
+
+        let modulus = checked_lcm(self.modulus, other.modulus).ok_or(CrtError::Overflow)?;
+        // `k * self.modulus` is less than the lcm, so this can't overflow
+        Ok(Self::new(self.residue + k * self.modulus, modulus))
+    }
+}
+
+impl Display for Congruence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (mod {})", self.residue, self.modulus)
+    }
+}
+
+/// Why [`crt`] couldn't combine some congruences.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CrtError {
+    /// No integer is in all of them.
+    NoSolution,
+    /// The combined modulus doesn't fit in an `i128`.
+    Overflow,
+}
+
+impl Display for CrtError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CrtError::NoSolution => write!(f, "the congruences have no solution in common"),
+            CrtError::Overflow => write!(f, "the combined modulus overflowed"),
+        }
+    }
+}
+
+impl std::error::Error for CrtError {}
+
+/// The Chinese remainder theorem: the integers in all of `congruences`, whose moduli
+/// don't need to be coprime. With no congruences at all, that's every integer.
+pub fn crt(congruences: impl IntoIterator<Item = Congruence>) -> Result<Congruence, CrtError> {
+    congruences
+        .into_iter()
+        .try_fold(Congruence::new(0, 1), |acc, congruence| {
+            acc.combine(&congruence)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::{agree, Check};
+
+    #[test]
+    fn test_gcd_and_lcm() {
+        assert_eq!(gcd(12u64, 18), 6);
+        assert_eq!(gcd(-12i64, 18), 6);
+        assert_eq!(gcd(0u32, 0), 0);
+        assert_eq!(lcm(4usize, 6), 12);
+        assert_eq!(lcm(-4i128, 6), 12);
+        assert_eq!(gcd_all([12u64, 18, 27]), 3);
+        assert_eq!(lcm_all([2u64, 3, 4, 5]), 60);
+        assert_eq!(checked_lcm(u64::MAX, u64::MAX - 1), None);
+        assert_eq!(checked_lcm_all([2u32, 3, u32::MAX]), None);
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        for (a, b) in [(240, 46), (-240, 46), (17, 0), (0, -5)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a, b));
+            assert_eq!(a * x + b * y, g);
+        }
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+    }
+
+    #[test]
+    fn test_crt() {
+        let solution = crt([
+            Congruence::new(2, 3),
+            Congruence::new(3, 5),
+            Congruence::new(2, 7),
+        ]);
+        assert_eq!(solution, Ok(Congruence::new(23, 105)));
+
+        // the moduli share a factor of 2, which the residues agree on
+        let solution = crt([Congruence::new(3, 4), Congruence::new(1, 6)]);
+        assert_eq!(solution, Ok(Congruence::new(7, 12)));
+
+        let solution = crt([Congruence::new(0, 4), Congruence::new(1, 6)]);
+        assert_eq!(solution, Err(CrtError::NoSolution));
+
+        let big = i128::MAX / 3;
+        let solution = crt([Congruence::new(0, big), Congruence::new(0, big - 1)]);
+        assert_eq!(solution, Err(CrtError::Overflow));
+    }
+
+    #[test]
+    fn test_crt_matches_search() {
+        Check::new().run(
+            |rng| {
+                (0..rng.range(0..4))
+                    .map(|_| (rng.range_i64(-20..20), rng.range_i64(1..12)))
+                    .collect::<Vec<_>>()
+            },
+            |congruences| {
+                let congruences: Vec<_> = congruences
+                    .iter()
+                    .map(|&(residue, modulus)| {
+                        Congruence::new(residue.into(), modulus.max(1).into())
+                    })
+                    .collect();
+                // the moduli are all below 12, so their lcm divides 27720, and any solution
+                // turns up again within that many values
+                let expected = (0..27_720)
+                    .find(|value| congruences.iter().all(|c| c.contains(*value)))
+                    .ok_or(CrtError::NoSolution);
+                agree(
+                    crt(congruences.iter().copied()).map(|solution| solution.residue),
+                    expected,
+                )
+            },
+        );
+    }
+}
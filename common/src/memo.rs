@@ -0,0 +1,171 @@
+//! Memoization for recursive solvers, backed by a hash map, that keeps count of how much
+//! work it saved.
+//!
+//! Keys are whatever identifies a subproblem, and work best when they're cheap, like a
+//! couple of indices into the puzzle input rather than copies of the bits of it that are
+//! left to solve.
+
+use std::{collections::HashMap, fmt::Display, hash::Hash};
+
+/// Results of a function that have already been worked out.
+///
+/// ```
+/// use common::memo::Memo;
+///
+/// fn fibonacci(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+///     if n < 2 {
+///         return n;
+///     }
+///     memo.get_or_insert_with(n, |memo| fibonacci(n - 1, memo) + fibonacci(n - 2, memo))
+/// }
+///
+/// let mut memo = Memo::new();
+/// assert_eq!(fibonacci(90, &mut memo), 2_880_067_194_370_816_120);
+/// assert_eq!(memo.stats().misses, 89);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    hits: u64,
+    misses: u64,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self {
+            cache: HashMap::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The result for `key`, working it out with `compute` if it isn't known yet.
+    /// `compute` gets the memo back, so that it can look up smaller subproblems.
+    pub fn get_or_insert_with(&mut self, key: K, compute: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            self.hits += 1;
+            return value.clone();
+        }
+
+        self.misses += 1;
+        let value = compute(self);
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    /// How many results are stored.
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Forgets every result, along with the stats.
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn stats(&self) -> Stats {
+        Stats {
+            hits: self.hits,
+            misses: self.misses,
+            entries: self.cache.len(),
+        }
+    }
+}
+
+/// How a [`Memo`] has been used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Stats {
+    /// Lookups that found a result that was already worked out.
+    pub hits: u64,
+    /// Lookups that had to work the result out.
+    pub misses: u64,
+    /// Results stored.
+    pub entries: usize,
+}
+
+impl Stats {
+    /// The fraction of lookups that were hits, or zero if there weren't any.
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            lookups => self.hits as f64 / lookups as f64,
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses ({:.1}% hit rate), {} entries",
+            self.hits,
+            self.misses,
+            self.hit_rate() * 100.0,
+            self.entries
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Ways of climbing `n` stairs one or two at a time.
+    fn climb(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+        if n < 2 {
+            return 1;
+        }
+        memo.get_or_insert_with(n, |memo| climb(n - 1, memo) + climb(n - 2, memo))
+    }
+
+    #[test]
+    fn test_memoizes() {
+        let mut memo = Memo::new();
+        assert_eq!(climb(10, &mut memo), 89);
+        // everything from 2 to 10 gets worked out once, and all but the last two of them
+        // get asked for again on the way back up
+        assert_eq!(
+            memo.stats(),
+            Stats {
+                hits: 7,
+                misses: 9,
+                entries: 9
+            }
+        );
+        assert_eq!(memo.get(&5), Some(&8));
+
+        assert_eq!(climb(10, &mut memo), 89);
+        assert_eq!(memo.stats().hits, 8);
+    }
+
+    #[test]
+    fn test_stats() {
+        let mut memo: Memo<u64, u64> = Memo::new();
+        assert_eq!(memo.stats().hit_rate(), 0.0);
+
+        climb(4, &mut memo);
+        let stats = memo.stats();
+        assert_eq!(
+            stats.to_string(),
+            "1 hits, 3 misses (25.0% hit rate), 3 entries"
+        );
+
+        memo.clear();
+        assert!(memo.is_empty());
+        assert_eq!(memo.stats(), Stats::default());
+    }
+}
@@ -0,0 +1,270 @@
+//! Exact arithmetic, for puzzles whose numbers are too big for floating point to be trusted
+//! with: arbitrarily large integers, fractions of them, and integer square roots.
+
+pub use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Signed};
+use std::{
+    cmp::Ordering,
+    fmt::Display,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+/// The largest `r` with `r * r <= n`.
+pub fn isqrt(n: u64) -> u64 {
+    // the float estimate is within one or two of the answer, so just nudge it into place
+    let mut root = (n as f64).sqrt() as u64;
+    while root.checked_mul(root).is_none_or(|square| square > n) {
+        root -= 1;
+    }
+    while (root + 1)
+        .checked_mul(root + 1)
+        .is_some_and(|square| square <= n)
+    {
+        root += 1;
+    }
+    root
+}
+
+/// The square root of `n`, if it's a whole number.
+pub fn exact_sqrt(n: u64) -> Option<u64> {
+    let root = isqrt(n);
+    (root * root == n).then_some(root)
+}
+
+/// A fraction, always kept in lowest terms with a positive denominator, so that equal
+/// fractions look the same.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Rational<T = BigInt> {
+    numer: T,
+    denom: T,
+}
+
+impl<T: Clone + Integer + Signed> Rational<T> {
+    /// # Panics
+    ///
+    /// If `denom` is zero.
+    pub fn new(numer: T, denom: T) -> Self {
+        assert!(!denom.is_zero(), "denominator is zero");
+        let divisor = numer.gcd(&denom);
+        let sign = if denom.is_negative() {
+            -T::one()
+        } else {
+            T::one()
+        };
+        Self {
+            numer: numer / divisor.clone() * sign.clone(),
+            denom: denom / divisor * sign,
+        }
+    }
+
+    pub fn from_integer(value: T) -> Self {
+        Self {
+            numer: value,
+            denom: T::one(),
+        }
+    }
+
+    pub fn numer(&self) -> &T {
+        &self.numer
+    }
+
+    pub fn denom(&self) -> &T {
+        &self.denom
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denom.is_one()
+    }
+
+    /// The largest integer that isn't greater than the fraction.
+    pub fn floor(&self) -> T {
+        self.numer.div_floor(&self.denom)
+    }
+
+    /// The smallest integer that isn't less than the fraction.
+    pub fn ceil(&self) -> T {
+        self.numer.div_ceil(&self.denom)
+    }
+
+    pub fn is_positive(&self) -> bool {
+        self.numer.is_positive()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.numer.is_negative()
+    }
+
+    /// One over the fraction.
+    ///
+    /// # Panics
+    ///
+    /// If the fraction is zero.
+    pub fn recip(&self) -> Self {
+        Self::new(self.denom.clone(), self.numer.clone())
+    }
+}
+
+impl<T: Clone + Integer + Signed + From<i64>> From<i64> for Rational<T> {
+    fn from(value: i64) -> Self {
+        Self::from_integer(value.into())
+    }
+}
+
+impl<T: Clone + Integer + Signed> Add for Rational<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(
+            self.numer * rhs.denom.clone() + rhs.numer * self.denom.clone(),
+            self.denom * rhs.denom,
+        )
+    }
+}
+
+impl<T: Clone + Integer + Signed> Sub for Rational<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl<T: Clone + Integer + Signed> Mul for Rational<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::new(self.numer * rhs.numer, self.denom * rhs.denom)
+    }
+}
+
+impl<T: Clone + Integer + Signed> Div for Rational<T> {
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// If `rhs` is zero.
+    fn div(self, rhs: Self) -> Self::Output {
+        Self::new(self.numer * rhs.denom, self.denom * rhs.numer)
+    }
+}
+
+impl<T: Clone + Integer + Signed> Neg for Rational<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            numer: -self.numer,
+            denom: self.denom,
+        }
+    }
+}
+
+impl<T: Clone + Integer + Signed> PartialOrd for Rational<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Clone + Integer + Signed> Ord for Rational<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // the denominators are positive, so cross-multiplying keeps the order
+        (self.numer.clone() * other.denom.clone()).cmp(&(other.numer.clone() * self.denom.clone()))
+    }
+}
+
+impl<T: Display + One + PartialEq> Display for Rational<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.denom.is_one() {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::{agree, Check};
+
+    fn ratio(numer: i64, denom: i64) -> Rational {
+        Rational::new(numer.into(), denom.into())
+    }
+
+    #[test]
+    fn test_isqrt() {
+        assert_eq!(isqrt(0), 0);
+        assert_eq!(isqrt(15), 3);
+        assert_eq!(isqrt(16), 4);
+        assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
+        // big enough that `f64` rounds the square root up
+        let root = u32::MAX as u64;
+        assert_eq!(isqrt(root * root - 1), root - 1);
+        assert_eq!(exact_sqrt(1 << 62), Some(1 << 31));
+        assert_eq!(exact_sqrt((1 << 62) - 1), None);
+    }
+
+    #[test]
+    fn test_isqrt_matches_squares() {
+        Check::new().run(
+            |rng| rng.next_u64(),
+            |&n| {
+                let root = isqrt(n) as u128;
+                agree(
+                    root * root <= n as u128 && (root + 1) * (root + 1) > n as u128,
+                    true,
+                )
+            },
+        );
+    }
+
+    #[test]
+    fn test_lowest_terms() {
+        let half = ratio(-3, -6);
+        assert_eq!(half, ratio(1, 2));
+        assert_eq!(half.numer(), &BigInt::from(1));
+        assert_eq!(ratio(4, -6).to_string(), "-2/3");
+        assert_eq!(ratio(6, 3).to_string(), "2");
+        assert!(ratio(6, 3).is_integer());
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(ratio(1, 2) + ratio(1, 3), ratio(5, 6));
+        assert_eq!(ratio(1, 2) - ratio(1, 3), ratio(1, 6));
+        assert_eq!(ratio(2, 3) * ratio(3, 4), ratio(1, 2));
+        assert_eq!(ratio(2, 3) / ratio(-4, 3), ratio(-1, 2));
+        assert!(ratio(-1, 3) < ratio(-1, 4));
+        assert_eq!(ratio(-7, 2).floor(), BigInt::from(-4));
+        assert_eq!(ratio(-7, 2).ceil(), BigInt::from(-3));
+    }
+
+    #[test]
+    fn test_exact_where_floats_are_not() {
+        // at this size, floats are only accurate to about a sixteenth
+        let big = Rational::<BigInt>::from(400_000_000_000_000);
+        let tenth = ratio(1, 10);
+        assert!(big.clone() + tenth.clone() > big);
+        assert_eq!(big.clone() + tenth - big, ratio(1, 10));
+    }
+
+    #[test]
+    fn test_order_matches_cross_multiplication() {
+        Check::new().run(
+            |rng| {
+                (
+                    rng.range_i64(-100..100),
+                    rng.range_i64(1..100),
+                    rng.range_i64(-100..100),
+                    rng.range_i64(1..100),
+                )
+            },
+            |&(a, b, c, d)| {
+                let (b, d) = (b.max(1), d.max(1));
+                let expected = (a * d).cmp(&(c * b));
+                agree(ratio(a, b).cmp(&ratio(c, d)), expected)
+            },
+        );
+    }
+}
@@ -0,0 +1,63 @@
+//! Work on independent lines or blocks of an input, spread across every core when the
+//! `parallel` feature is on and done one after another when it isn't.
+//!
+//! Results always come back in the same order as the items, and are combined in that order,
+//! so answers don't depend on how the work happened to be split up between threads.
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::iter::Sum;
+
+/// Applies `f` to every item, keeping the results in the order of the items.
+pub fn map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync + Send,
+{
+    #[cfg(feature = "parallel")]
+    let results = items.par_iter().map(f).collect();
+    #[cfg(not(feature = "parallel"))]
+    let results = items.iter().map(f).collect();
+    results
+}
+
+/// Applies `f` to every item and adds up the results.
+pub fn sum<T, R, F>(items: &[T], f: F) -> R
+where
+    T: Sync,
+    R: Send + Sum,
+    F: Fn(&T) -> R + Sync + Send,
+{
+    map(items, f).into_iter().sum()
+}
+
+/// Sets how many threads the parallel solvers use, rather than one per core.
+/// Only takes effect before the first parallel solver runs.
+#[cfg(feature = "parallel")]
+pub fn set_threads(threads: usize) -> Result<(), rayon::ThreadPoolBuildError> {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build_global()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_keeps_order() {
+        let items: Vec<u64> = (0..1000).collect();
+        assert_eq!(
+            map(&items, |n| n * 2),
+            (0..2000).step_by(2).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_sum() {
+        let lines = ["1abc2", "pqr3stu8vwx", "a1b2c3d4e5f"];
+        let total: usize = sum(&lines, |line| line.len());
+        assert_eq!(total, 27);
+    }
+}
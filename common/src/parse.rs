@@ -0,0 +1,406 @@
+//! nom parsers for the shapes that keep turning up in puzzle inputs: numbers separated by
+//! spaces or commas, `Label: values` lines, `name = (a, b)` lines, character grids and
+//! blank-line-separated blocks of any of those.
+//!
+//! They fail with an [`Error`] that says what was expected where, which [`finish`] turns
+//! into a [`ParseError`] pointing into the input:
+//!
+//! ```
+//! use common::parse::{finish, labeled, spaced, tag, unsigned};
+//!
+//! let seeds = finish("seeds: 79 14 55", labeled(tag("seeds"), spaced(unsigned::<u64>)));
+//! assert_eq!(seeds, Ok(("seeds", vec![79, 14, 55])));
+//!
+//! let err = finish("seeds 79 14", labeled(tag("seeds"), spaced(unsigned::<u64>))).unwrap_err();
+//! assert_eq!((err.span, err.expected.as_str()), (5..6, "`:`"));
+//! ```
+
+use crate::{
+    diagnostic::{offset_in, ParseError},
+    grid::Grid,
+};
+use nom::{
+    character::complete::{char, digit1, multispace0, one_of, space0, space1},
+    combinator::{all_consuming, map_res, opt, recognize},
+    error::{ContextError, ErrorKind, FromExternalError},
+    multi::separated_list1,
+    sequence::{delimited, pair, separated_pair, terminated},
+    IResult, Parser,
+};
+use std::{borrow::Cow, str::FromStr};
+
+/// What a parser expected to find, and the rest of the input from where it didn't.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error<'a> {
+    pub input: &'a str,
+    pub expected: Cow<'static, str>,
+}
+
+pub type PResult<'a, T> = IResult<&'a str, T, Error<'a>>;
+
+impl<'a> Error<'a> {
+    pub fn new(input: &'a str, expected: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            input,
+            expected: expected.into(),
+        }
+    }
+
+    /// The equivalent [`ParseError`], given the `source` the parser was started on.
+    pub fn into_parse_error(self, source: &str) -> ParseError {
+        let start = offset_in(source, self.input);
+        // point at the character the parser stopped at
+        let len = self.input.chars().next().map_or(0, char::len_utf8);
+        ParseError::new(start..start + len, self.expected)
+    }
+}
+
+fn describe(kind: ErrorKind) -> Cow<'static, str> {
+    match kind {
+        ErrorKind::Digit => "a digit".into(),
+        ErrorKind::Alpha => "a letter".into(),
+        ErrorKind::AlphaNumeric => "a letter or digit".into(),
+        ErrorKind::Space | ErrorKind::MultiSpace => "whitespace".into(),
+        ErrorKind::CrLf => "a line break".into(),
+        ErrorKind::Eof => "the end of the input".into(),
+        kind => kind.description().to_lowercase().into(),
+    }
+}
+
+impl<'a> nom::error::ParseError<&'a str> for Error<'a> {
+    fn from_error_kind(input: &'a str, kind: ErrorKind) -> Self {
+        Self::new(input, describe(kind))
+    }
+
+    fn append(_: &'a str, _: ErrorKind, other: Self) -> Self {
+        other
+    }
+
+    fn from_char(input: &'a str, c: char) -> Self {
+        Self::new(input, format!("`{c}`"))
+    }
+
+    /// Between two alternatives that both failed, the one that got further along is the
+    /// more useful to report.
+    fn or(self, other: Self) -> Self {
+        if self.input.len() < other.input.len() {
+            self
+        } else {
+            other
+        }
+    }
+}
+
+impl<'a> ContextError<&'a str> for Error<'a> {
+    fn add_context(input: &'a str, ctx: &'static str, other: Self) -> Self {
+        // a failure right where the context starts is better described by the context
+        if other.input.len() == input.len() {
+            Self::new(input, ctx)
+        } else {
+            other
+        }
+    }
+}
+
+impl<'a, E> FromExternalError<&'a str, E> for Error<'a> {
+    fn from_external_error(input: &'a str, kind: ErrorKind, _: E) -> Self {
+        Self::new(input, describe(kind))
+    }
+}
+
+/// Runs `parser` over the whole of `input`, which may only have whitespace left over.
+pub fn finish<'a, T>(
+    input: &'a str,
+    parser: impl Parser<&'a str, T, Error<'a>>,
+) -> Result<T, ParseError> {
+    all_consuming(terminated(parser, multispace0))(input)
+        .map(|(_, parsed)| parsed)
+        .map_err(|err| match err {
+            nom::Err::Error(err) | nom::Err::Failure(err) => err.into_parse_error(input),
+            nom::Err::Incomplete(_) => ParseError::at_end(input, "more input"),
+        })
+}
+
+/// Labels the error from `parser` with what it was meant to find, if it failed straight away.
+pub fn expect<'a, T>(
+    expected: &'static str,
+    mut parser: impl Parser<&'a str, T, Error<'a>>,
+) -> impl FnMut(&'a str) -> PResult<'a, T> {
+    move |input| {
+        parser.parse(input).map_err(|err| {
+            err.map(|err| {
+                if err.input.len() == input.len() {
+                    Error::new(input, expected)
+                } else {
+                    err
+                }
+            })
+        })
+    }
+}
+
+/// Matches `literal` exactly, expecting it by name when it's missing.
+pub fn tag<'a>(literal: &'static str) -> impl FnMut(&'a str) -> PResult<'a, &'a str> {
+    move |input: &'a str| match input.strip_prefix(literal) {
+        Some(rest) => Ok((rest, &input[..literal.len()])),
+        None => Err(nom::Err::Error(Error::new(
+            input,
+            format!("`{}`", literal.escape_debug()),
+        ))),
+    }
+}
+
+/// A number without a sign.
+pub fn unsigned<'a, T: FromStr>(input: &'a str) -> PResult<'a, T> {
+    expect("a number", map_res(digit1, str::parse))(input)
+}
+
+/// A number, which may start with a `-` or `+`.
+pub fn signed<'a, T: FromStr>(input: &'a str) -> PResult<'a, T> {
+    expect(
+        "a number",
+        map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse),
+    )(input)
+}
+
+/// One or more `item`s separated by spaces, ignoring any spaces before the first one.
+pub fn spaced<'a, T>(
+    item: impl Parser<&'a str, T, Error<'a>>,
+) -> impl FnMut(&'a str) -> PResult<'a, Vec<T>> {
+    let mut items = separated_list1(space1, item);
+    move |input| {
+        let (rest, _) = space0(input)?;
+        items(rest)
+    }
+}
+
+/// One or more `item`s separated by commas, with or without spaces after them.
+pub fn comma_separated<'a, T>(
+    item: impl Parser<&'a str, T, Error<'a>>,
+) -> impl FnMut(&'a str) -> PResult<'a, Vec<T>> {
+    separated_list1(pair(char(','), space0), item)
+}
+
+/// A `label: values` line, like `seeds: 79 14 55 13` or `Card 1: 41 48 83`.
+pub fn labeled<'a, L, T>(
+    label: impl Parser<&'a str, L, Error<'a>>,
+    values: impl Parser<&'a str, T, Error<'a>>,
+) -> impl FnMut(&'a str) -> PResult<'a, (L, T)> {
+    separated_pair(label, pair(char(':'), space0), values)
+}
+
+/// A `name = (left, right)` line, like `AAA = (BBB, CCC)`.
+pub fn named_pair<'a, N, T>(
+    name: impl Parser<&'a str, N, Error<'a>>,
+    item: impl Parser<&'a str, T, Error<'a>> + Clone,
+) -> impl FnMut(&'a str) -> PResult<'a, (N, (T, T))> {
+    separated_pair(
+        name,
+        tag(" = "),
+        delimited(
+            char('('),
+            separated_pair(item.clone(), tag(", "), item),
+            char(')'),
+        ),
+    )
+}
+
+/// Runs `item` on each of a run of lines, stopping at a blank line or the end of the input.
+/// Lines may end in `\n` or `\r\n`.
+pub fn lines<'a, T>(
+    item: impl Parser<&'a str, T, Error<'a>>,
+) -> impl FnMut(&'a str) -> PResult<'a, Vec<T>> {
+    separated_strictly(strip_line_ending, item, |rest| {
+        rest.is_empty() || strip_line_ending(rest).is_some()
+    })
+}
+
+/// Runs `block` on each of a run of blocks separated by blank lines.
+pub fn blocks<'a, T>(
+    block: impl Parser<&'a str, T, Error<'a>>,
+) -> impl FnMut(&'a str) -> PResult<'a, Vec<T>> {
+    separated_strictly(
+        |input| strip_line_ending(input).and_then(strip_line_ending),
+        block,
+        |rest| rest.trim().is_empty(),
+    )
+}
+
+fn strip_line_ending(input: &str) -> Option<&str> {
+    input
+        .strip_prefix("\r\n")
+        .or_else(|| input.strip_prefix('\n'))
+}
+
+/// Like `separated_list1`, but once a separator has been found, the next item has to parse
+/// (unless `is_end` says there isn't one), rather than quietly ending the list at an item
+/// with a typo in it.
+fn separated_strictly<'a, T>(
+    separator: impl Fn(&'a str) -> Option<&'a str>,
+    mut item: impl Parser<&'a str, T, Error<'a>>,
+    is_end: impl Fn(&str) -> bool,
+) -> impl FnMut(&'a str) -> PResult<'a, Vec<T>> {
+    move |input| {
+        let (mut rest, first) = item.parse(input)?;
+        let mut items = vec![first];
+        while let Some(next) = separator(rest) {
+            if is_end(next) {
+                break;
+            }
+            let (next_rest, next_item) = item.parse(next).map_err(|err| match err {
+                nom::Err::Error(err) => nom::Err::Failure(err),
+                err => err,
+            })?;
+            items.push(next_item);
+            rest = next_rest;
+        }
+        Ok((rest, items))
+    }
+}
+
+/// A grid with a row per line, which ends at a blank line or the end of the input. `cell`
+/// turns each character into a cell, or returns `None` for characters that don't belong,
+/// in which case the error says `expected` them instead.
+pub fn char_grid<'a, T>(
+    expected: &'static str,
+    cell: impl Fn(char) -> Option<T>,
+) -> impl FnMut(&'a str) -> PResult<'a, Grid<T>> {
+    let row = move |input: &'a str| {
+        let end = input.find('\n').unwrap_or(input.len());
+        let line = input[..end].strip_suffix('\r').unwrap_or(&input[..end]);
+        if line.is_empty() {
+            return Err(nom::Err::Error(Error::new(input, expected)));
+        }
+        let cells = line
+            .char_indices()
+            .map(|(idx, c)| {
+                cell(c).ok_or_else(|| nom::Err::Error(Error::new(&input[idx..], expected)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok((&input[line.len()..], (input, cells)))
+    };
+    let mut rows = lines(row);
+
+    move |input| {
+        let (rest, rows) = rows(input)?;
+        let width = rows[0].1.len();
+        if let Some((row, cells)) = rows.iter().find(|(_, cells)| cells.len() != width) {
+            // point just past the shorter of the two rows
+            let end = row
+                .char_indices()
+                .nth(cells.len().min(width))
+                .map_or(row.len(), |(idx, _)| idx);
+            let expected = if cells.len() < width {
+                "another cell, to match the width of the first row"
+            } else {
+                "the end of the row, to match the width of the first row"
+            };
+            return Err(nom::Err::Failure(Error::new(&row[end..], expected)));
+        }
+        Ok((
+            rest,
+            Grid::from_rows(rows.into_iter().map(|(_, cells)| cells).collect()),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::{
+        character::complete::{alpha1, alphanumeric1},
+        sequence::preceded,
+    };
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(finish("-12", signed::<i64>), Ok(-12));
+        assert_eq!(finish("+7", signed::<i32>), Ok(7));
+        assert_eq!(
+            finish("-12", unsigned::<u64>),
+            Err(ParseError::new(0..1, "a number"))
+        );
+        assert_eq!(
+            finish("  1  2 3\n", spaced(unsigned::<u8>)),
+            Ok(vec![1, 2, 3])
+        );
+        assert_eq!(
+            finish("1,2, -3", comma_separated(signed::<i8>)),
+            Ok(vec![1, 2, -3])
+        );
+    }
+
+    #[test]
+    fn test_labeled() {
+        let card = preceded(pair(tag("Card"), space1), unsigned::<u32>);
+        assert_eq!(
+            finish("Card  4: 41 48", labeled(card, spaced(unsigned::<u32>))),
+            Ok((4, vec![41, 48]))
+        );
+        assert_eq!(
+            finish("Time 7 15", labeled(tag("Time"), spaced(unsigned::<u32>))),
+            Err(ParseError::new(4..5, "`:`"))
+        );
+    }
+
+    #[test]
+    fn test_named_pair() {
+        assert_eq!(
+            finish("11A = (11B, XXX)", named_pair(alphanumeric1, alphanumeric1)),
+            Ok(("11A", ("11B", "XXX")))
+        );
+        assert_eq!(
+            finish("AAA = (BBB CCC)", named_pair(alpha1, alpha1)),
+            Err(ParseError::new(10..11, "`, `"))
+        );
+    }
+
+    #[test]
+    fn test_lines_and_blocks() {
+        assert_eq!(
+            finish("1 2\n3\n\n4\n", blocks(lines(spaced(unsigned::<u8>)))),
+            Ok(vec![vec![vec![1, 2], vec![3]], vec![vec![4]]])
+        );
+        assert_eq!(
+            finish(
+                "1 2\r\n3\r\n\r\n4\r\n",
+                blocks(lines(spaced(unsigned::<u8>)))
+            ),
+            Ok(vec![vec![vec![1, 2], vec![3]], vec![vec![4]]])
+        );
+        // a line that doesn't parse is an error, rather than the end of the list
+        assert_eq!(
+            finish("1\n2\nx3\n", lines(unsigned::<u8>)),
+            Err(ParseError::new(4..5, "a number"))
+        );
+    }
+
+    #[test]
+    fn test_char_grid() {
+        let cell = |c| match c {
+            '.' => Some(false),
+            '#' => Some(true),
+            _ => None,
+        };
+        let grids = finish("#.\n.#\n\n..\n##", blocks(char_grid("`.` or `#`", cell))).unwrap();
+        assert_eq!(grids.len(), 2);
+        assert_eq!(grids[1].row(1), &[true, true]);
+        let crlf = finish(
+            "#.\r\n.#\r\n\r\n..\r\n##",
+            blocks(char_grid("`.` or `#`", cell)),
+        );
+        assert_eq!(crlf.unwrap(), grids);
+
+        assert_eq!(
+            finish("#.\n.?", char_grid("`.` or `#`", cell)),
+            Err(ParseError::new(4..5, "`.` or `#`"))
+        );
+        assert_eq!(
+            finish("#.\n.\n..", char_grid("`.` or `#`", cell)),
+            Err(ParseError::new(
+                4..5,
+                "another cell, to match the width of the first row"
+            ))
+        );
+    }
+}
@@ -0,0 +1,371 @@
+//! Maps on the integers that shift each of a handful of ranges by a fixed amount and leave
+//! everything else where it is, the kind of map that day 5's almanac is made of.
+//!
+//! Since each piece only shifts its values, the map is increasing on every piece, so a
+//! whole range of inputs can be pushed through it (or pulled back through it) a piece at a
+//! time without looking at individual values.
+
+use crate::interval::IntervalSet;
+use std::ops::Range;
+
+/// Shifts every value in `domain` by `offset`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Piece {
+    pub domain: Range<i64>,
+    pub offset: i64,
+}
+
+impl Piece {
+    pub fn new(domain: Range<i64>, offset: i64) -> Self {
+        Self { domain, offset }
+    }
+
+    /// Where the piece sends its domain.
+    pub fn range(&self) -> Range<i64> {
+        shifted(&self.domain, self.offset)
+    }
+}
+
+/// A map made of [`Piece`]s with disjoint domains, which is the identity outside of them.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct PiecewiseLinearMap {
+    /// Sorted by domain, none of them empty.
+    pieces: Vec<Piece>,
+}
+
+fn shifted(range: &Range<i64>, offset: i64) -> Range<i64> {
+    range.start + offset..range.end + offset
+}
+
+impl PiecewiseLinearMap {
+    /// The identity map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a map out of pieces whose domains may overlap, in which case values are
+    /// mapped by the first piece that contains them.
+    pub fn from_pieces(pieces: impl IntoIterator<Item = Piece>) -> Self {
+        let mut covered = IntervalSet::new();
+        let mut disjoint = vec![];
+        for Piece { domain, offset } in pieces {
+            let uncovered = IntervalSet::from(domain.clone()).difference(&covered);
+            disjoint.extend(
+                uncovered
+                    .ranges()
+                    .iter()
+                    .map(|range| Piece::new(range.clone(), offset)),
+            );
+            covered.insert(domain);
+        }
+        disjoint.sort_by_key(|piece| piece.domain.start);
+        Self { pieces: disjoint }
+    }
+
+    pub fn pieces(&self) -> &[Piece] {
+        &self.pieces
+    }
+
+    /// Everything that one of the pieces applies to.
+    pub fn domains(&self) -> IntervalSet<i64> {
+        self.pieces
+            .iter()
+            .map(|piece| piece.domain.clone())
+            .collect()
+    }
+
+    pub fn apply(&self, input: i64) -> i64 {
+        let idx = self
+            .pieces
+            .partition_point(|piece| piece.domain.end <= input);
+        match self.pieces.get(idx) {
+            Some(piece) if piece.domain.start <= input => input + piece.offset,
+            _ => input,
+        }
+    }
+
+    /// Splits `range` into the parts that get shifted by the same amount, in order.
+    fn segments(&self, range: &Range<i64>) -> Vec<(Range<i64>, i64)> {
+        let mut segments = vec![];
+        let mut start = range.start;
+        let first = self
+            .pieces
+            .partition_point(|piece| piece.domain.end <= start);
+        for piece in &self.pieces[first..] {
+            if piece.domain.start >= range.end {
+                break;
+            }
+            if start < piece.domain.start {
+                segments.push((start..piece.domain.start, 0));
+            }
+            let end = piece.domain.end.min(range.end);
+            segments.push((start.max(piece.domain.start)..end, piece.offset));
+            start = end;
+        }
+        if start < range.end {
+            segments.push((start..range.end, 0));
+        }
+        segments
+    }
+
+    /// The map that applies `first`, then `second`.
+    pub fn compose(first: &Self, second: &Self) -> Self {
+        // push each of `first`'s pieces through `second`, which can split it up
+        let mut pieces: Vec<Piece> = first
+            .pieces
+            .iter()
+            .flat_map(|piece| {
+                second
+                    .segments(&piece.range())
+                    .into_iter()
+                    .map(move |(range, offset)| {
+                        Piece::new(shifted(&range, -piece.offset), piece.offset + offset)
+                    })
+            })
+            .collect();
+
+        // and wherever `first` leaves the input alone, `second` applies by itself
+        let untouched = first.domains();
+        for piece in &second.pieces {
+            let domain = IntervalSet::from(piece.domain.clone()).difference(&untouched);
+            pieces.extend(
+                domain
+                    .ranges()
+                    .iter()
+                    .map(|range| Piece::new(range.clone(), piece.offset)),
+            );
+        }
+
+        pieces.sort_by_key(|piece| piece.domain.start);
+        Self { pieces }
+    }
+
+    /// Merges neighbouring pieces that shift by the same amount, and drops the ones that
+    /// don't shift at all, without changing the map.
+    pub fn simplified(&self) -> Self {
+        let mut pieces: Vec<Piece> = vec![];
+        for piece in self.pieces.iter().filter(|piece| piece.offset != 0) {
+            match pieces.last_mut() {
+                Some(last)
+                    if last.domain.end == piece.domain.start && last.offset == piece.offset =>
+                {
+                    last.domain.end = piece.domain.end
+                }
+                _ => pieces.push(piece.clone()),
+            }
+        }
+        Self { pieces }
+    }
+
+    /// Where the map sends `inputs`.
+    pub fn image(&self, inputs: &IntervalSet<i64>) -> IntervalSet<i64> {
+        inputs
+            .ranges()
+            .iter()
+            .flat_map(|range| self.segments(range))
+            .map(|(range, offset)| shifted(&range, offset))
+            .collect()
+    }
+
+    /// The inputs that the map sends into `outputs`.
+    pub fn preimage(&self, outputs: &IntervalSet<i64>) -> IntervalSet<i64> {
+        let moved = self.pieces.iter().flat_map(|piece| {
+            let reached = outputs.intersection(&IntervalSet::from(piece.range()));
+            let offset = piece.offset;
+            reached
+                .ranges()
+                .iter()
+                .map(|range| shifted(range, -offset))
+                .collect::<Vec<_>>()
+        });
+        let unmoved = outputs.difference(&self.domains());
+        moved.chain(unmoved.ranges().iter().cloned()).collect()
+    }
+
+    /// The inverse map, if every output comes from exactly one input.
+    pub fn inverse(&self) -> Option<Self> {
+        // the pieces have to land on separate outputs, and between them cover exactly
+        // the values they moved, or some outputs would be hit twice (or never)
+        let ranges: IntervalSet<i64> = self.pieces.iter().map(Piece::range).collect();
+        let total_moved: i64 = self
+            .pieces
+            .iter()
+            .map(|piece| piece.domain.end - piece.domain.start)
+            .sum();
+        if ranges.len() != total_moved || ranges != self.domains() {
+            return None;
+        }
+
+        Some(Self::from_pieces(
+            self.pieces
+                .iter()
+                .map(|piece| Piece::new(piece.range(), -piece.offset)),
+        ))
+    }
+
+    /// The smallest output over `inputs`, if there are any.
+    pub fn min_over(&self, inputs: &IntervalSet<i64>) -> Option<i64> {
+        // each segment is increasing, so its smallest output is at its first input
+        inputs
+            .ranges()
+            .iter()
+            .flat_map(|range| self.segments(range))
+            .map(|(range, offset)| range.start + offset)
+            .min()
+    }
+
+    /// The largest output over `inputs`, if there are any.
+    pub fn max_over(&self, inputs: &IntervalSet<i64>) -> Option<i64> {
+        inputs
+            .ranges()
+            .iter()
+            .flat_map(|range| self.segments(range))
+            .map(|(range, offset)| range.end - 1 + offset)
+            .max()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::{agree, Check};
+
+    /// The two maps from the top of the day 5 example.
+    fn seed_to_soil() -> PiecewiseLinearMap {
+        PiecewiseLinearMap::from_pieces([Piece::new(98..100, -48), Piece::new(50..98, 2)])
+    }
+
+    fn soil_to_fertilizer() -> PiecewiseLinearMap {
+        PiecewiseLinearMap::from_pieces([
+            Piece::new(15..52, -15),
+            Piece::new(52..54, -15),
+            Piece::new(0..15, 39),
+        ])
+    }
+
+    #[test]
+    fn test_apply() {
+        let map = seed_to_soil();
+        assert_eq!(map.apply(79), 81);
+        assert_eq!(map.apply(14), 14);
+        assert_eq!(map.apply(99), 51);
+    }
+
+    #[test]
+    fn test_overlapping_pieces() {
+        let map = PiecewiseLinearMap::from_pieces([Piece::new(0..10, 5), Piece::new(5..20, -5)]);
+        assert_eq!(
+            map.pieces(),
+            &[Piece::new(0..10, 5), Piece::new(10..20, -5)]
+        );
+    }
+
+    #[test]
+    fn test_compose() {
+        let (first, second) = (seed_to_soil(), soil_to_fertilizer());
+        let composition = PiecewiseLinearMap::compose(&first, &second);
+        for input in -5..110 {
+            assert_eq!(composition.apply(input), second.apply(first.apply(input)));
+        }
+    }
+
+    #[test]
+    fn test_simplified() {
+        let map = soil_to_fertilizer().simplified();
+        assert_eq!(
+            map.pieces(),
+            &[Piece::new(0..15, 39), Piece::new(15..54, -15)]
+        );
+    }
+
+    #[test]
+    fn test_image_and_preimage() {
+        let map = seed_to_soil();
+        let seeds: IntervalSet<i64> = [45..55, 97..99].into_iter().collect();
+        // 97 goes to 99 and 98 to 50, next to the seeds that are left alone
+        let soil: IntervalSet<i64> = [45..51, 52..57, 99..100].into_iter().collect();
+
+        assert_eq!(map.image(&seeds), soil);
+        assert_eq!(map.preimage(&soil), seeds);
+        assert_eq!(map.min_over(&seeds), Some(45));
+        assert_eq!(map.max_over(&seeds), Some(99));
+    }
+
+    #[test]
+    fn test_inverse() {
+        let map = seed_to_soil();
+        let inverse = map.inverse().unwrap();
+        for input in 0..110 {
+            assert_eq!(inverse.apply(map.apply(input)), input);
+        }
+
+        // 0..10 and 10..20 both end up on 10..20
+        let map = PiecewiseLinearMap::from_pieces([Piece::new(0..10, 10)]);
+        assert_eq!(map.inverse(), None);
+    }
+
+    /// Maps as lists of (start, length, offset), over inputs below 40.
+    fn build_map(pieces: &[(i64, i64, i64)]) -> PiecewiseLinearMap {
+        PiecewiseLinearMap::from_pieces(pieces.iter().map(|&(start, length, offset)| {
+            Piece::new(
+                start.abs() % 30..start.abs() % 30 + length.abs() % 10,
+                offset,
+            )
+        }))
+    }
+
+    #[test]
+    fn test_matches_values_one_at_a_time() {
+        Check::new().run(
+            |rng| {
+                let mut map = || {
+                    (0..rng.range(0..4))
+                        .map(|_| {
+                            (
+                                rng.range_i64(0..30),
+                                rng.range_i64(0..10),
+                                rng.range_i64(-10..10),
+                            )
+                        })
+                        .collect::<Vec<_>>()
+                };
+                (map(), map(), rng.range_i64(0..40), rng.range_i64(0..10))
+            },
+            |(first, second, start, length)| {
+                let (first, second) = (build_map(first), build_map(second));
+                let inputs = IntervalSet::from(*start..start + length);
+                let values = || *start..start + length;
+
+                let composition = PiecewiseLinearMap::compose(&first, &second);
+                for input in -20..60 {
+                    agree(composition.apply(input), second.apply(first.apply(input)))?;
+                    agree(
+                        composition.simplified().apply(input),
+                        composition.apply(input),
+                    )?;
+                }
+                agree(
+                    first.image(&inputs),
+                    values()
+                        .map(|input| first.apply(input)..first.apply(input) + 1)
+                        .collect(),
+                )?;
+                agree(
+                    first.preimage(&inputs),
+                    (-20..60)
+                        .filter(|input| inputs.contains(&first.apply(*input)))
+                        .map(|input| input..input + 1)
+                        .collect(),
+                )?;
+                agree(
+                    first.min_over(&inputs),
+                    values().map(|input| first.apply(input)).min(),
+                )?;
+                agree(
+                    first.max_over(&inputs),
+                    values().map(|input| first.apply(input)).max(),
+                )
+            },
+        );
+    }
+}
@@ -1,12 +1,70 @@
+use crate::{
+    config::{ColorChoice, Config, OutputFormat},
+    crypt::{self, InputError, InputKey},
+    diagnostic::ParseError,
+    id::PuzzleId,
+};
 use clap::{Parser, ValueEnum};
 use colored::Colorize;
+use std::{
+    fmt::Display,
+    path::PathBuf,
+    process,
+    time::{Duration, Instant},
+};
 
-#[derive(ValueEnum, Clone)]
-enum PuzzlePartName {
+/// The year this workspace started out with. Puzzles belong to it
+/// unless they say otherwise.
+pub const DEFAULT_YEAR: u16 = 2023;
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PuzzlePartName {
     A,
     B,
 }
 
+/// Broad categories of techniques, so that prior art is easy to find
+/// when starting a new puzzle.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tag {
+    Parsing,
+    Grid,
+    Graph,
+    Intervals,
+    Dp,
+    Geometry,
+    Math,
+    Sorting,
+    Simulation,
+    Cycles,
+    Hashing,
+}
+
+impl Display for Tag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = self.to_possible_value().expect("no tags are skipped");
+        write!(f, "{}", value.get_name())
+    }
+}
+
+/// How far along the implementation of a puzzle part is.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// Not solved yet.
+    Todo,
+    /// Solved, but too slow to run casually.
+    Slow,
+    /// Solved.
+    Done,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = self.to_possible_value().expect("no statuses are skipped");
+        write!(f, "{}", value.get_name())
+    }
+}
+
 #[derive(Parser)]
 struct PuzzleArgs {
     #[arg(long, short)]
@@ -14,44 +72,174 @@ struct PuzzleArgs {
 
     #[arg(long = "example", short = 'e')]
     use_example: bool,
+
+    /// Read the input from this file instead of the one given by the config.
+    /// If it doesn't exist, its encrypted version (with an `.enc` extension) is used.
+    #[arg(long, conflicts_with = "use_example")]
+    input: Option<PathBuf>,
+
+    /// How to print the results (overrides the config).
+    #[arg(long)]
+    format: Option<OutputFormat>,
+
+    /// Whether to use colors (overrides the config).
+    #[arg(long)]
+    color: Option<ColorChoice>,
+
+    /// How many threads to solve with, rather than one per core.
+    #[cfg(feature = "parallel")]
+    #[arg(long)]
+    threads: Option<usize>,
 }
 
 pub trait PuzzlePart {
+    /// The parsed input this part works from, shared with the other part of the puzzle.
+    /// It may borrow from the input text.
+    type Input<'a>;
+
     /// A description of the value(s) computed for this puzzle,
     /// just for the sake of more meaningful output.
     fn description() -> &'static str;
 
-    /// Do all of the work necessary to transform the input text into
+    /// How far along the implementation of this part is.
+    fn status() -> Status {
+        Status::Done
+    }
+
+    /// Do all of the work necessary to transform the parsed input into
     /// the solution text.
-    fn solve(input: &str) -> String;
+    fn solve(input: &Self::Input<'_>) -> String;
+}
+
+/// The answer to one part of a puzzle, along with how long it took to get there.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solution {
+    pub answer: String,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
 }
 
 pub trait Puzzle {
-    type PartA: PuzzlePart;
-    type PartB: PuzzlePart;
+    /// The model of the input that both parts work from. It may borrow from the input text.
+    type Input<'a>;
+    type PartA: for<'a> PuzzlePart<Input<'a> = Self::Input<'a>>;
+    type PartB: for<'a> PuzzlePart<Input<'a> = Self::Input<'a>>;
 
     /// The name of the puzzle, usually just the number (eg. "01")
     fn name() -> &'static str;
 
-    /// Based on command line args, this executes the solver for one or both
+    /// The title of the puzzle as it appears on the Advent of Code site.
+    fn title() -> &'static str;
+
+    /// The kinds of techniques used to solve this puzzle.
+    fn tags() -> &'static [Tag] {
+        &[]
+    }
+
+    /// The year of the event this puzzle is from.
+    fn year() -> u16 {
+        DEFAULT_YEAR
+    }
+
+    /// Turn the input text into the model that both parts work from.
+    /// This only happens once, even when both parts are solved.
+    fn parse(input: &str) -> Self::Input<'_>;
+
+    /// Like `parse`, but reports malformed input as an error pointing into the text instead
+    /// of panicking. Puzzles whose parsers check their input override this.
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Ok(Self::parse(input))
+    }
+
+    /// Parses the input and solves one part with it, timing each step.
+    fn solve_part<P: for<'a> PuzzlePart<Input<'a> = Self::Input<'a>>>(input: &str) -> Solution {
+        let (parsed, parse_time) = timed(|| {
+            Self::try_parse(input).unwrap_or_else(|err| panic!("{}", err.render(input, "input")))
+        });
+        let (answer, solve_time) = timed(|| P::solve(&parsed));
+
+        Solution {
+            answer,
+            parse_time,
+            solve_time,
+        }
+    }
+
+    /// Based on command line args and the config file, this executes the solver for one or both
     /// parts of a day's puzzles, using either the primary input or the example input.
-    fn run(input: &str, example: &str) {
+    fn run() {
         let args = PuzzleArgs::parse();
+        let config = Config::load().unwrap_or_else(|err| exit_with_error(err));
+
+        args.color.unwrap_or(config.color).apply();
+        #[cfg(feature = "parallel")]
+        if let Some(threads) = args.threads {
+            crate::parallel::set_threads(threads).unwrap_or_else(|err| exit_with_error(err));
+        }
+        let format = args.format.unwrap_or(config.format);
+
+        let day = Self::name().parse().expect("puzzle names are day numbers");
+        let id = PuzzleId::new(Self::year(), day);
+        let is_primary_input = args.input.is_none() && !args.use_example;
+        let path = match args.input {
+            Some(path) => path,
+            None if args.use_example => config.example_path(id),
+            None => config.input_path(id),
+        };
+        let key = InputKey::load(&config);
+        let input = crypt::read_input(&path, key.as_ref()).unwrap_or_else(|err| match err {
+            InputError::NotFound(_) if is_primary_input => {
+                exit_with_error(format!("{err} (download it from {})", config.input_url(id)))
+            }
+            err => exit_with_error(err),
+        });
 
-        let input = if args.use_example { example } else { input };
+        let (parsed, parse_time) = timed(|| Self::try_parse(&input));
+        let parsed = parsed
+            .unwrap_or_else(|err| exit_with_error(err.render(&input, &path.display().to_string())));
+        if format == OutputFormat::Text {
+            println!("{}", format!("Parsed input in {parse_time:.2?}").dimmed());
+        }
 
         match args.part {
-            Some(PuzzlePartName::A) => Self::process::<Self::PartA>("A", input, args.use_example),
-            Some(PuzzlePartName::B) => Self::process::<Self::PartB>("B", input, args.use_example),
+            Some(PuzzlePartName::A) => {
+                Self::process::<Self::PartA>("A", &parsed, parse_time, args.use_example, format)
+            }
+            Some(PuzzlePartName::B) => {
+                Self::process::<Self::PartB>("B", &parsed, parse_time, args.use_example, format)
+            }
             None => {
-                Self::process::<Self::PartA>("A", input, args.use_example);
-                println!("{}", "---".dimmed());
-                Self::process::<Self::PartB>("B", input, args.use_example);
+                Self::process::<Self::PartA>("A", &parsed, parse_time, args.use_example, format);
+                if format == OutputFormat::Text {
+                    println!("{}", "---".dimmed());
+                }
+                Self::process::<Self::PartB>("B", &parsed, parse_time, args.use_example, format);
             }
         };
     }
 
-    fn process<P: PuzzlePart>(part_name: &str, input: &str, is_example: bool) {
+    fn process<P: for<'a> PuzzlePart<Input<'a> = Self::Input<'a>>>(
+        part_name: &str,
+        input: &Self::Input<'_>,
+        parse_time: Duration,
+        is_example: bool,
+        format: OutputFormat,
+    ) {
+        if format == OutputFormat::Json {
+            let (res, solve_time) = timed(|| P::solve(input));
+            let output = serde_json::json!({
+                "year": Self::year(),
+                "puzzle": Self::name(),
+                "part": part_name,
+                "example": is_example,
+                "answer": res,
+                "parse_ms": parse_time.as_secs_f64() * 1000.0,
+                "solve_ms": solve_time.as_secs_f64() * 1000.0,
+            });
+            println!("{output}");
+            return;
+        }
+
         let puzzle_name = format!("Puzzle {} Part {part_name}", Self::name()).blue();
         let example_note = if is_example {
             format!(" ({})", "example input".yellow())
@@ -63,7 +251,25 @@ pub trait Puzzle {
         let description = format!("\"{}\"", <P as PuzzlePart>::description()).dimmed();
         println!("{description}");
 
-        let res = <P as PuzzlePart>::solve(input);
+        let (res, solve_time) = timed(|| P::solve(input));
         println!("Solution: {}", res.magenta().bold());
+        println!("{}", format!("Solved in {solve_time:.2?}").dimmed());
     }
 }
+
+/// Prints a puzzle's parsed input back out as input text, which parses into the same model.
+/// Keeping the two in step lets the models be inspected and hand-edited as puzzle text.
+pub trait InputPrinter: Puzzle {
+    fn print(input: &Self::Input<'_>) -> String;
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let value = f();
+    (value, start.elapsed())
+}
+
+fn exit_with_error(message: impl Display) -> ! {
+    eprintln!("{}", message.to_string().red());
+    process::exit(1)
+}
@@ -0,0 +1,277 @@
+use crate::{
+    diagnostic::ParseError,
+    generate::InputGenerator,
+    id::PuzzleId,
+    puzzle::{InputPrinter, Puzzle, PuzzlePart, PuzzlePartName, Solution, Status, Tag},
+    rng::Rng,
+};
+use serde::Serialize;
+
+/// A type-erased handle to one part of a puzzle. Its `solve` parses the input
+/// before solving, since the parsed model's type is erased along with the puzzle's.
+#[derive(Clone, Copy)]
+pub struct PartEntry {
+    pub description: fn() -> &'static str,
+    pub status: Status,
+    pub solve: fn(&str) -> Solution,
+}
+
+impl PartEntry {
+    pub fn of<Pz: Puzzle, P: for<'a> PuzzlePart<Input<'a> = Pz::Input<'a>>>() -> Self {
+        Self {
+            description: P::description,
+            status: P::status(),
+            solve: Pz::solve_part::<P>,
+        }
+    }
+}
+
+/// Parses an input and converts the result to JSON, see `PuzzleEntry::with_json`.
+pub type ToJson = fn(&str) -> Result<serde_json::Value, ParseError>;
+
+/// Parses an input and prints the result back out, see `PuzzleEntry::with_printer`.
+pub type Reprint = fn(&str) -> Result<String, ParseError>;
+
+/// A type-erased handle to a `Puzzle`, so that puzzles defined in
+/// different crates can be collected and looked up at runtime.
+#[derive(Clone, Copy)]
+pub struct PuzzleEntry {
+    pub year: u16,
+    pub name: &'static str,
+    pub title: &'static str,
+    pub tags: &'static [Tag],
+    pub part_a: PartEntry,
+    pub part_b: PartEntry,
+    pub generate: Option<fn(usize, &mut Rng) -> String>,
+    /// Parses an input and converts the parsed model to JSON, for puzzles whose
+    /// model can be serialized.
+    pub to_json: Option<ToJson>,
+    /// Parses an input and prints the parsed model back out, for puzzles whose model can
+    /// be printed.
+    pub reprint: Option<Reprint>,
+}
+
+impl PuzzleEntry {
+    pub fn of<P: Puzzle>() -> Self {
+        Self {
+            year: P::year(),
+            name: P::name(),
+            title: P::title(),
+            tags: P::tags(),
+            part_a: PartEntry::of::<P, P::PartA>(),
+            part_b: PartEntry::of::<P, P::PartB>(),
+            generate: None,
+            to_json: None,
+            reprint: None,
+        }
+    }
+
+    /// Registers a generator of random inputs for this puzzle.
+    pub fn with_generator<G: InputGenerator>(self) -> Self {
+        Self {
+            generate: Some(G::generate),
+            ..self
+        }
+    }
+
+    /// Registers the puzzle's parsed model as serializable, for dumping it as JSON.
+    pub fn with_json<P: Puzzle>(self) -> Self
+    where
+        for<'a> P::Input<'a>: Serialize,
+    {
+        Self {
+            to_json: Some(parsed_json::<P>),
+            ..self
+        }
+    }
+
+    /// Registers a printer that turns this puzzle's parsed model back into input text.
+    pub fn with_printer<P: InputPrinter>(self) -> Self {
+        Self {
+            reprint: Some(reprinted::<P>),
+            ..self
+        }
+    }
+
+    pub fn part(&self, part: PuzzlePartName) -> &PartEntry {
+        match part {
+            PuzzlePartName::A => &self.part_a,
+            PuzzlePartName::B => &self.part_b,
+        }
+    }
+
+    /// The day number of this puzzle, if its name is numeric.
+    pub fn day(&self) -> Option<u32> {
+        self.name.parse().ok()
+    }
+
+    pub fn id(&self) -> Option<PuzzleId> {
+        self.day().map(|day| PuzzleId::new(self.year, day))
+    }
+}
+
+fn parsed_json<P: Puzzle>(input: &str) -> Result<serde_json::Value, ParseError>
+where
+    for<'a> P::Input<'a>: Serialize,
+{
+    let parsed = P::try_parse(input)?;
+    // the models are plain data, so the only way this could fail is a bug in a derive
+    Ok(serde_json::to_value(&parsed).expect("parsed input should be serializable"))
+}
+
+fn reprinted<P: InputPrinter>(input: &str) -> Result<String, ParseError> {
+    Ok(P::print(&P::try_parse(input)?))
+}
+
+/// All of the puzzles known to a runner.
+pub struct Registry(Vec<PuzzleEntry>);
+
+impl Registry {
+    pub fn new(entries: Vec<PuzzleEntry>) -> Self {
+        Self(entries)
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &PuzzleEntry> {
+        self.0.iter()
+    }
+
+    /// Looks up a puzzle by year and day, accepting either "1" or "01" for the day.
+    pub fn find(&self, year: u16, day: &str) -> Option<&PuzzleEntry> {
+        let id = PuzzleId::new(year, day.parse().ok()?);
+        self.0.iter().find(|entry| entry.id() == Some(id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestPuzzle {}
+    struct OtherYearPuzzle {}
+    struct TestPartA {}
+    struct TestPartB {}
+
+    impl PuzzlePart for TestPartA {
+        type Input<'a> = Vec<&'a str>;
+
+        fn description() -> &'static str {
+            "Count the characters."
+        }
+
+        fn solve(input: &Self::Input<'_>) -> String {
+            input
+                .iter()
+                .map(|line| line.len())
+                .sum::<usize>()
+                .to_string()
+        }
+    }
+
+    impl PuzzlePart for TestPartB {
+        type Input<'a> = Vec<&'a str>;
+
+        fn description() -> &'static str {
+            "Count the lines."
+        }
+
+        fn solve(input: &Self::Input<'_>) -> String {
+            input.len().to_string()
+        }
+    }
+
+    fn parse_lines(input: &str) -> Vec<&str> {
+        input.lines().collect()
+    }
+
+    impl Puzzle for TestPuzzle {
+        type Input<'a> = Vec<&'a str>;
+        type PartA = TestPartA;
+        type PartB = TestPartB;
+
+        fn name() -> &'static str {
+            "07"
+        }
+
+        fn title() -> &'static str {
+            "Test Puzzle"
+        }
+
+        fn parse(input: &str) -> Self::Input<'_> {
+            parse_lines(input)
+        }
+    }
+
+    impl Puzzle for OtherYearPuzzle {
+        type Input<'a> = Vec<&'a str>;
+        type PartA = TestPartA;
+        type PartB = TestPartB;
+
+        fn name() -> &'static str {
+            "07"
+        }
+
+        fn title() -> &'static str {
+            "Test Puzzle From Another Year"
+        }
+
+        fn year() -> u16 {
+            2015
+        }
+
+        fn parse(input: &str) -> Self::Input<'_> {
+            parse_lines(input)
+        }
+    }
+
+    #[test]
+    fn test_find_entry() {
+        let registry = Registry::new(vec![
+            PuzzleEntry::of::<TestPuzzle>(),
+            PuzzleEntry::of::<OtherYearPuzzle>(),
+        ]);
+
+        assert_eq!(registry.find(2023, "7").map(|entry| entry.year), Some(2023));
+        assert_eq!(
+            registry.find(2015, "07").map(|entry| entry.year),
+            Some(2015)
+        );
+        assert!(registry.find(2023, "8").is_none());
+        assert!(registry.find(2016, "7").is_none());
+        assert!(registry.find(2023, "seven").is_none());
+    }
+
+    #[test]
+    fn test_solve_entry() {
+        let entry = PuzzleEntry::of::<TestPuzzle>();
+
+        assert_eq!((entry.part(PuzzlePartName::A).solve)("ab\ncde").answer, "5");
+        assert_eq!((entry.part(PuzzlePartName::B).solve)("ab\ncde").answer, "2");
+    }
+
+    impl InputPrinter for TestPuzzle {
+        fn print(input: &Self::Input<'_>) -> String {
+            input.join("\n")
+        }
+    }
+
+    #[test]
+    fn test_entry_reprint() {
+        let entry = PuzzleEntry::of::<TestPuzzle>();
+        assert!(entry.reprint.is_none());
+
+        let reprint = entry.with_printer::<TestPuzzle>().reprint.unwrap();
+        assert_eq!(reprint("ab\ncde\n").unwrap(), "ab\ncde");
+    }
+
+    #[test]
+    fn test_entry_to_json() {
+        let entry = PuzzleEntry::of::<TestPuzzle>();
+        assert!(entry.to_json.is_none());
+
+        let to_json = entry.with_json::<TestPuzzle>().to_json.unwrap();
+        assert_eq!(
+            to_json("ab\ncde").unwrap(),
+            serde_json::json!(["ab", "cde"])
+        );
+    }
+}
@@ -0,0 +1,86 @@
+//! A small seeded random number generator, so that generated inputs and test cases
+//! can be reproduced exactly from their seed.
+
+use std::ops::Range;
+
+/// SplitMix64. Not suitable for anything that needs to be unpredictable,
+/// but fast and well-distributed, which is all we need.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..bound`.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "cannot pick a number below zero");
+        // the bias from the modulo is negligible for the bounds we use
+        self.next_u64() % bound
+    }
+
+    pub fn range(&mut self, range: Range<usize>) -> usize {
+        range.start + self.below((range.end - range.start) as u64) as usize
+    }
+
+    pub fn range_i64(&mut self, range: Range<i64>) -> i64 {
+        range.start + self.below(range.start.abs_diff(range.end)) as i64
+    }
+
+    /// Returns true with the given probability.
+    pub fn chance(&mut self, probability: f64) -> bool {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64 <= probability
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0..items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for idx in (1..items.len()).rev() {
+            items.swap(idx, self.range(0..idx + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_numbers() {
+        let mut rng1 = Rng::new(42);
+        let mut rng2 = Rng::new(42);
+        let mut rng3 = Rng::new(43);
+
+        let numbers1: Vec<_> = (0..10).map(|_| rng1.next_u64()).collect();
+        let numbers2: Vec<_> = (0..10).map(|_| rng2.next_u64()).collect();
+        let numbers3: Vec<_> = (0..10).map(|_| rng3.next_u64()).collect();
+
+        assert_eq!(numbers1, numbers2);
+        assert_ne!(numbers1, numbers3);
+    }
+
+    #[test]
+    fn test_ranges() {
+        let mut rng = Rng::new(7);
+        for _ in 0..1000 {
+            assert!((3..9).contains(&rng.range(3..9)));
+            assert!((-5..5).contains(&rng.range_i64(-5..5)));
+        }
+
+        let mut items = [1, 2, 3, 4, 5];
+        rng.shuffle(&mut items);
+        items.sort();
+        assert_eq!(items, [1, 2, 3, 4, 5]);
+    }
+}
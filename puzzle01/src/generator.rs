@@ -0,0 +1,32 @@
+use crate::Puzzle01;
+use common::{generate::InputGenerator, rng::Rng};
+
+const DIGIT_WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+impl InputGenerator for Puzzle01 {
+    /// `size` is the number of lines.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        (0..size)
+            .map(|_| calibration_line(rng))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// A jumble of letters, digits and spelled out digits, with at least one
+/// numerical digit so that part a has something to find.
+fn calibration_line(rng: &mut Rng) -> String {
+    let mut pieces: Vec<String> = (0..rng.range(1..8))
+        .map(|_| match rng.below(3) {
+            0 => char::from(b'a' + rng.below(26) as u8).to_string(),
+            1 => rng.range(1..10).to_string(),
+            _ => rng.choose(&DIGIT_WORDS).to_string(),
+        })
+        .collect();
+
+    let idx = rng.range(0..pieces.len() + 1);
+    pieces.insert(idx, rng.range(1..10).to_string());
+    pieces.concat()
+}
@@ -1,6 +1,41 @@
+use common::puzzle::{InputPrinter, Puzzle, Tag};
+use puzzle01a::Puzzle01a;
+use puzzle01b::Puzzle01b;
+
+pub mod generator;
 pub mod puzzle01a;
 pub mod puzzle01b;
 
+pub struct Puzzle01 {}
+
+impl Puzzle for Puzzle01 {
+    type Input<'a> = Vec<String>;
+    type PartA = Puzzle01a;
+    type PartB = Puzzle01b;
+
+    fn name() -> &'static str {
+        "01"
+    }
+
+    fn title() -> &'static str {
+        "Trebuchet?!"
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Parsing]
+    }
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input.lines().map(String::from).collect()
+    }
+}
+
+impl InputPrinter for Puzzle01 {
+    fn print(input: &Self::Input<'_>) -> String {
+        input.join("\n")
+    }
+}
+
 pub fn find_digits(s: &str) -> Vec<u32> {
     let mut digits = vec![];
     let mut haystack = s;
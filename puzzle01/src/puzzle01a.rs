@@ -1,16 +1,16 @@
-use common::puzzle::PuzzlePart;
+use common::{answer::Answer, parallel, puzzle::PuzzlePart};
 
 pub struct Puzzle01a {}
 
 impl PuzzlePart for Puzzle01a {
+    type Input<'a> = Vec<String>;
+
     fn description() -> &'static str {
         "Sum the two digit numbers formed from the first and last numerical digits in each line."
     }
 
-    fn solve(input: &str) -> String {
-        let mut sum: u32 = 0;
-
-        for line in input.lines() {
+    fn solve(input: &Self::Input<'_>) -> String {
+        parallel::sum(input, |line| {
             let first_digit = line.chars().find_map(|c| c.to_digit(10)).unwrap();
             let last_digit = line
                 .chars()
@@ -19,10 +19,8 @@ impl PuzzlePart for Puzzle01a {
                 .to_digit(10)
                 .unwrap();
 
-            let line_value = first_digit * 10 + last_digit;
-            sum += line_value;
-        }
-
-        sum.to_string()
+            Answer::from(first_digit * 10 + last_digit)
+        })
+        .to_string()
     }
 }
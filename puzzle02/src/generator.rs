@@ -0,0 +1,26 @@
+use crate::Puzzle02;
+use common::{generate::InputGenerator, rng::Rng};
+
+impl InputGenerator for Puzzle02 {
+    /// `size` is the number of games.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        (1..=size)
+            .map(|id| {
+                let sets: Vec<String> = (0..rng.range(1..7)).map(|_| color_set(rng)).collect();
+                format!("Game {id}: {}", sets.join("; "))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+fn color_set(rng: &mut Rng) -> String {
+    let mut colors = ["red", "green", "blue"];
+    rng.shuffle(&mut colors);
+
+    colors[..rng.range(1..4)]
+        .iter()
+        .map(|color| format!("{} {color}", rng.range(1..21)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
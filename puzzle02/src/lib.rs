@@ -1,8 +1,55 @@
+use common::puzzle::{InputPrinter, Puzzle, Tag};
+use game::Game;
+use parser::parse_game;
+use puzzle02a::Puzzle02a;
+use puzzle02b::Puzzle02b;
+
+pub mod generator;
 pub mod puzzle02a;
 pub mod puzzle02b;
 
+pub struct Puzzle02 {}
+
+impl Puzzle for Puzzle02 {
+    type Input<'a> = Vec<Game>;
+    type PartA = Puzzle02a;
+    type PartB = Puzzle02b;
+
+    fn name() -> &'static str {
+        "02"
+    }
+
+    fn title() -> &'static str {
+        "Cube Conundrum"
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Parsing]
+    }
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input
+            .lines()
+            .map(|line| parse_game(line).unwrap().1)
+            .collect()
+    }
+}
+
+impl InputPrinter for Puzzle02 {
+    fn print(input: &Self::Input<'_>) -> String {
+        input
+            .iter()
+            .map(Game::to_string)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
 pub mod game {
-    #[derive(Debug)]
+    use std::fmt::Display;
+
+    #[derive(Debug, PartialEq, Eq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct Game {
         pub id: u32,
         pub set: Vec<ColorSet>,
@@ -17,13 +64,28 @@ pub mod game {
     }
 
     #[derive(Debug, Clone)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub enum Color {
         Red,
         Green,
         Blue,
     }
 
-    #[derive(Debug, Clone, Copy)]
+    impl Display for Game {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "Game {}: ", self.id)?;
+            for (idx, color_set) in self.set.iter().enumerate() {
+                if idx > 0 {
+                    write!(f, "; ")?;
+                }
+                write!(f, "{color_set}")?;
+            }
+            Ok(())
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct ColorSet(pub u32, pub u32, pub u32);
 
     impl std::ops::Add<ColorSet> for ColorSet {
@@ -33,6 +95,25 @@ pub mod game {
             ColorSet(self.0 + rhs.0, self.1 + rhs.1, self.2 + rhs.2)
         }
     }
+
+    impl Display for ColorSet {
+        /// Leaves out colors that weren't drawn, though a set with nothing in it
+        /// needs one of them to show up as zero.
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let amounts = [(self.0, "red"), (self.1, "green"), (self.2, "blue")];
+            let drawn: Vec<_> = amounts
+                .iter()
+                .filter(|(amount, _)| *amount > 0)
+                .map(|(amount, color)| format!("{amount} {color}"))
+                .collect();
+
+            if drawn.is_empty() {
+                write!(f, "0 red")
+            } else {
+                write!(f, "{}", drawn.join(", "))
+            }
+        }
+    }
 }
 
 pub mod parser {
@@ -1,5 +1,5 @@
-use crate::parser::parse_game;
-use common::puzzle::PuzzlePart;
+use crate::game::Game;
+use common::{answer::Answer, parallel, puzzle::PuzzlePart};
 
 // constraints
 const MAX_RED: u32 = 12;
@@ -9,24 +9,23 @@ const MAX_BLUE: u32 = 14;
 pub struct Puzzle02a {}
 
 impl PuzzlePart for Puzzle02a {
+    type Input<'a> = Vec<Game>;
+
     fn description() -> &'static str {
         "Sum the ids of possible games."
     }
 
-    fn solve(input: &str) -> String {
-        let mut id_sum: u32 = 0;
-
-        for line in input.lines() {
-            let (_, game) = parse_game(line).unwrap();
-
+    fn solve(input: &Self::Input<'_>) -> String {
+        parallel::sum(input, |game| {
             // puzzle 1: compute maxes to find which games were possible given the constraints
             let color_maxes = game.color_maxes();
 
             if color_maxes.0 <= MAX_RED && color_maxes.1 <= MAX_GREEN && color_maxes.2 <= MAX_BLUE {
-                id_sum += game.id;
+                Answer::from(game.id)
+            } else {
+                Answer::ZERO
             }
-        }
-
-        id_sum.to_string()
+        })
+        .to_string()
     }
 }
@@ -1,13 +1,24 @@
-use std::ops::Range;
+use std::{fmt::Display, ops::Range};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Element {
     Number(u32),
     Symbol(char),
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ElementSpan {
     pub element: Element,
     pub range: Range<usize>,
 }
+
+impl Display for Element {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Element::Number(n) => write!(f, "{n}"),
+            Element::Symbol(c) => write!(f, "{c}"),
+        }
+    }
+}
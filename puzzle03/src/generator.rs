@@ -0,0 +1,39 @@
+use crate::Puzzle03;
+use common::{generate::InputGenerator, rng::Rng};
+
+const SYMBOLS: [char; 10] = ['#', '$', '-', '*', '+', '&', '/', '@', '%', '='];
+
+impl InputGenerator for Puzzle03 {
+    /// `size` is the width and height of the schematic.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        (0..size)
+            .map(|_| schematic_line(size, rng))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+fn schematic_line(width: usize, rng: &mut Rng) -> String {
+    let mut line = String::new();
+    while line.len() < width {
+        let remaining = width - line.len();
+        if rng.chance(0.15) {
+            // gears are the most interesting symbol, so favor them
+            line.push(if rng.chance(0.5) {
+                '*'
+            } else {
+                *rng.choose(&SYMBOLS)
+            });
+        } else if rng.chance(0.2) {
+            let digits = rng.range(1..4).min(remaining);
+            let number = rng.range(10usize.pow(digits as u32 - 1)..10usize.pow(digits as u32));
+            line.push_str(&number.to_string());
+        }
+
+        // always follow up with a dot, so that numbers don't run together
+        if line.len() < width {
+            line.push('.');
+        }
+    }
+    line
+}
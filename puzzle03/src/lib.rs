@@ -1,5 +1,60 @@
+use common::puzzle::{InputPrinter, Puzzle, Tag};
+use parser::SchematicLine;
+use puzzle03a::Puzzle03a;
+use puzzle03b::Puzzle03b;
+use schematic::SchematicGraph;
+
 pub mod element;
+pub mod generator;
 pub mod parser;
 pub mod puzzle03a;
 pub mod puzzle03b;
 pub mod schematic;
+
+pub struct Puzzle03 {}
+
+impl Puzzle for Puzzle03 {
+    type Input<'a> = SchematicGraph;
+    type PartA = Puzzle03a;
+    type PartB = Puzzle03b;
+
+    fn name() -> &'static str {
+        "03"
+    }
+
+    fn title() -> &'static str {
+        "Gear Ratios"
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Grid, Tag::Graph]
+    }
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input
+            .lines()
+            .map(SchematicLine::parse_from_str)
+            .collect::<Vec<_>>()
+            .into()
+    }
+}
+
+impl InputPrinter for Puzzle03 {
+    /// Pads every line with dots to the width of the longest one, so that lines with
+    /// nothing on them aren't lost.
+    fn print(input: &Self::Input<'_>) -> String {
+        let width = input
+            .lines()
+            .iter()
+            .map(SchematicLine::width)
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        input
+            .lines()
+            .iter()
+            .map(|line| format!("{line:.<width$}", line = line.to_string()))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
@@ -6,8 +6,10 @@ use nom::{
     multi::many1,
     IResult,
 };
+use std::fmt::Display;
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SchematicLine(Vec<ElementSpan>);
 
 impl SchematicLine {
@@ -31,8 +33,30 @@ impl SchematicLine {
         SchematicLine(elements)
     }
 
-    pub fn into_spans(self) -> impl Iterator<Item = ElementSpan> {
-        self.0.into_iter()
+    pub fn spans(&self) -> impl Iterator<Item = &ElementSpan> {
+        self.0.iter()
+    }
+
+    /// How far into the line its last element ends.
+    pub fn width(&self) -> usize {
+        self.0.last().map_or(0, |span| span.range.end)
+    }
+}
+
+impl Display for SchematicLine {
+    /// Fills the gaps between elements with dots, padding numbers with zeros if they
+    /// took up more room than they need to. Any dots after the last element are lost.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut position = 0;
+        for ElementSpan { element, range } in &self.0 {
+            write!(f, "{}", ".".repeat(range.start - position))?;
+            match element {
+                Element::Number(n) => write!(f, "{n:0width$}", width = range.len())?,
+                Element::Symbol(_) => write!(f, "{element}")?,
+            }
+            position = range.end;
+        }
+        Ok(())
     }
 }
 
@@ -66,3 +90,17 @@ fn parse_num(input: &str) -> IResult<&str, u32> {
 fn parse_dots(input: &str) -> IResult<&str, ()> {
     value((), many1(char('.')))(input)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_line() {
+        let line = SchematicLine::parse_from_str("467..114..");
+        assert_eq!(line.to_string(), "467..114");
+
+        let line = SchematicLine::parse_from_str("...$.*007.");
+        assert_eq!(line.to_string(), "...$.*007");
+    }
+}
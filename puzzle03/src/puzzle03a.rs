@@ -1,29 +1,25 @@
-use crate::{element::Element, parser::SchematicLine, schematic::SchematicGraph};
+use crate::{element::Element, schematic::SchematicGraph};
 use common::puzzle::PuzzlePart;
 
 pub struct Puzzle03a {}
 
 impl PuzzlePart for Puzzle03a {
+    type Input<'a> = SchematicGraph;
+
     fn description() -> &'static str {
         "Sum the numbers of 'parts' in the schematic."
     }
 
-    fn solve(input: &str) -> String {
-        let schematic: SchematicGraph = input
-            .lines()
-            .map(SchematicLine::parse_from_str)
-            .collect::<Vec<_>>()
-            .into();
-
+    fn solve(schematic: &Self::Input<'_>) -> String {
         let sum_of_part_numbers: u32 = schematic
             .nodes()
-            .filter(|node| {
+            .filter(|(id, _)| {
                 schematic
-                    .neighbors_of(node)
-                    .any(|node| matches!(node.element(), Element::Symbol(..)))
+                    .neighbors_of(*id)
+                    .any(|element| matches!(element, Element::Symbol(..)))
             })
-            .filter_map(|node| {
-                if let Element::Number(n) = node.element() {
+            .filter_map(|(_, element)| {
+                if let Element::Number(n) = element {
                     Some(n)
                 } else {
                     None
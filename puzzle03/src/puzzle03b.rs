@@ -1,37 +1,27 @@
-use crate::{
-    element::Element,
-    parser::SchematicLine,
-    schematic::{SchematicGraph, SchematicNode},
-};
+use crate::{element::Element, schematic::SchematicGraph};
 use common::puzzle::PuzzlePart;
 
 pub struct Puzzle03b {}
 
 impl PuzzlePart for Puzzle03b {
+    type Input<'a> = SchematicGraph;
+
     fn description() -> &'static str {
         "Sum the 'gear ratios' of particular parts in the schematic."
     }
 
-    fn solve(input: &str) -> String {
-        let schematic: SchematicGraph = input
-            .lines()
-            .map(SchematicLine::parse_from_str)
-            .collect::<Vec<_>>()
-            .into();
-
+    fn solve(schematic: &Self::Input<'_>) -> String {
         let sum_of_gear_ratios: u32 = schematic
             .nodes()
-            .filter(|node| matches!(node.element(), Element::Symbol('*')))
-            .filter_map(|node| {
-                let mut neighbors = schematic.neighbors_of(node);
+            .filter(|(_, element)| matches!(element, Element::Symbol('*')))
+            .filter_map(|(id, _)| {
+                let mut neighbors = schematic.neighbors_of(id);
 
                 // look for _exactly_ two neighbors
-                let Some(Element::Number(num1)) = neighbors.next().map(SchematicNode::element)
-                else {
+                let Some(Element::Number(num1)) = neighbors.next() else {
                     return None;
                 };
-                let Some(Element::Number(num2)) = neighbors.next().map(SchematicNode::element)
-                else {
+                let Some(Element::Number(num2)) = neighbors.next() else {
                     return None;
                 };
                 if neighbors.next().is_none() {
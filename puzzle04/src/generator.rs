@@ -0,0 +1,41 @@
+use crate::Puzzle04;
+use common::{generate::InputGenerator, rng::Rng};
+
+const WINNING_NUMBERS: usize = 5;
+const PLAYER_NUMBERS: usize = 8;
+
+impl InputGenerator for Puzzle04 {
+    /// `size` is the number of scratch cards.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        (1..=size)
+            .map(|id| {
+                // cards never win copies of cards past the end of the table
+                let max_matches = WINNING_NUMBERS.min(size - id);
+                scratch_card(id, rng.range(0..max_matches + 1), rng)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+fn scratch_card(id: usize, matches: usize, rng: &mut Rng) -> String {
+    let mut numbers: Vec<usize> = (1..100).collect();
+    rng.shuffle(&mut numbers);
+
+    let winning = &numbers[..WINNING_NUMBERS];
+    let mut player: Vec<usize> = winning[..matches]
+        .iter()
+        .chain(&numbers[WINNING_NUMBERS..WINNING_NUMBERS + PLAYER_NUMBERS - matches])
+        .copied()
+        .collect();
+    rng.shuffle(&mut player);
+
+    let format = |numbers: &[usize]| {
+        numbers
+            .iter()
+            .map(|n| format!("{n:>2}"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    format!("Card {id:>3}: {} | {}", format(winning), format(&player))
+}
@@ -1,15 +1,61 @@
+use common::{
+    answer::Answer,
+    puzzle::{InputPrinter, Puzzle, Tag},
+};
+use puzzle04a::Puzzle04a;
+use puzzle04b::Puzzle04b;
+use std::fmt::Display;
+
+pub mod generator;
 pub mod puzzle04a;
 pub mod puzzle04b;
 
+pub struct Puzzle04 {}
+
+impl Puzzle for Puzzle04 {
+    type Input<'a> = Vec<ScratchCard>;
+    type PartA = Puzzle04a;
+    type PartB = Puzzle04b;
+
+    fn name() -> &'static str {
+        "04"
+    }
+
+    fn title() -> &'static str {
+        "Scratchcards"
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Parsing, Tag::Dp]
+    }
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input.lines().map(ScratchCard::parse_from_str).collect()
+    }
+}
+
+impl InputPrinter for Puzzle04 {
+    fn print(input: &Self::Input<'_>) -> String {
+        input
+            .iter()
+            .map(ScratchCard::to_string)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
-struct ScratchCard {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScratchCard {
+    id: u32,
     winning_nums: Vec<u32>,
     player_nums: Vec<u32>,
 }
 
 impl ScratchCard {
     fn parse_from_str(input: &str) -> Self {
-        let (_, input) = input.split_once(": ").unwrap();
+        let (card, input) = input.split_once(": ").unwrap();
+        let id = card.trim_start_matches("Card").trim().parse().unwrap();
         let (winning_num_str, player_num_str) = input.split_once(" | ").unwrap();
 
         let winning_nums: Vec<_> = winning_num_str
@@ -22,6 +68,7 @@ impl ScratchCard {
             .collect();
 
         Self {
+            id,
             winning_nums,
             player_nums,
         }
@@ -34,14 +81,32 @@ impl ScratchCard {
             .count()
     }
 
-    fn score(&self) -> u32 {
+    fn score(&self) -> Answer {
         match self.matches() {
-            0 => 0,
-            n => 2u32.pow(n as u32 - 1),
+            0 => Answer::ZERO,
+            n => Answer::from(2).pow(n as u32 - 1),
         }
     }
 }
 
+impl Display for ScratchCard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let format = |nums: &[u32]| {
+            nums.iter()
+                .map(|n| format!("{n:>2}"))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        write!(
+            f,
+            "Card {}: {} | {}",
+            self.id,
+            format(&self.winning_nums),
+            format(&self.player_nums)
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -54,6 +119,7 @@ mod tests {
         assert_eq!(
             card,
             ScratchCard {
+                id: 11,
                 winning_nums: vec![1, 2],
                 player_nums: vec![3, 4],
             }
@@ -68,6 +134,7 @@ mod tests {
         assert_eq!(
             card,
             ScratchCard {
+                id: 1,
                 winning_nums: vec![41, 48, 83, 86, 17,],
                 player_nums: vec![83, 86, 6, 31, 17, 9, 48, 53],
             }
@@ -77,6 +144,7 @@ mod tests {
     #[test]
     fn test_compute_matches() {
         let card = ScratchCard {
+            id: 1,
             winning_nums: vec![41, 48, 83, 86, 17],
             player_nums: vec![83, 86, 6, 31, 17, 9, 48, 53],
         };
@@ -87,20 +155,28 @@ mod tests {
     #[test]
     fn test_compute_score() {
         let card = ScratchCard {
+            id: 1,
             winning_nums: vec![41, 48, 83, 86, 17],
             player_nums: vec![83, 86, 6, 31, 17, 9, 48, 53],
         };
 
-        assert_eq!(card.score(), 8)
+        assert_eq!(card.score(), Answer::from(8))
     }
 
     #[test]
     fn test_compute_score_no_matches() {
         let card = ScratchCard {
+            id: 1,
             winning_nums: vec![1, 2],
             player_nums: vec![3, 4],
         };
 
-        assert_eq!(card.score(), 0)
+        assert_eq!(card.score(), Answer::ZERO)
+    }
+
+    #[test]
+    fn test_display_card() {
+        let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53";
+        assert_eq!(ScratchCard::parse_from_str(input).to_string(), input);
     }
 }
@@ -1,19 +1,16 @@
 use crate::ScratchCard;
-use common::puzzle::PuzzlePart;
+use common::{parallel, puzzle::PuzzlePart};
 
 pub struct Puzzle04a {}
 
 impl PuzzlePart for Puzzle04a {
+    type Input<'a> = Vec<ScratchCard>;
+
     fn description() -> &'static str {
         "Sum the scores of scratchy cards."
     }
 
-    fn solve(input: &str) -> String {
-        input
-            .lines()
-            .map(ScratchCard::parse_from_str)
-            .map(|card| card.score())
-            .sum::<u32>()
-            .to_string()
+    fn solve(input: &Self::Input<'_>) -> String {
+        parallel::sum(input, ScratchCard::score).to_string()
     }
 }
@@ -1,42 +1,39 @@
 use crate::ScratchCard;
-use common::puzzle::PuzzlePart;
+use common::{answer::Answer, parallel, puzzle::PuzzlePart};
 
 pub struct Puzzle04b {}
 
 impl PuzzlePart for Puzzle04b {
+    type Input<'a> = Vec<ScratchCard>;
+
     fn description() -> &'static str {
         "Count the total number of cards obtained by a process where winning cards grants you more cards."
     }
 
-    fn solve(input: &str) -> String {
-        let matches: Vec<usize> = input
-            .lines()
-            .map(ScratchCard::parse_from_str)
-            .map(|card| card.matches())
-            .collect();
-
-        (0..matches.len())
-            .map(|index| recursive_count(&matches[index..]))
-            .sum::<usize>()
-            .to_string()
+    fn solve(input: &Self::Input<'_>) -> String {
+        let matches: Vec<usize> = input.iter().map(|card| card.matches()).collect();
+
+        let starts: Vec<usize> = (0..matches.len()).collect();
+        parallel::sum(&starts, |&start| recursive_count(&matches[start..])).to_string()
     }
 }
 
-fn recursive_count(list: &[usize]) -> usize {
+fn recursive_count(list: &[usize]) -> Answer {
     if list.is_empty() {
-        return 0;
+        return Answer::ZERO;
     }
 
     // one to count the current card
     // then, for each match in the current card, call this function with a slice of the same list
     //   that's offset by incremental amounts
-    1 + (0..list[0])
-        .map(|start| recursive_count(&list[start + 1..]))
-        .sum::<usize>()
+    Answer::ONE
+        + (0..list[0])
+            .map(|start| recursive_count(&list[start + 1..]))
+            .sum::<Answer>()
 }
 
 #[test]
 fn test_recursive_count() {
     let list = vec![2, 1, 0];
-    assert_eq!(recursive_count(&list), 4);
+    assert_eq!(recursive_count(&list), Answer::from(4));
 }
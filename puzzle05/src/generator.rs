@@ -0,0 +1,47 @@
+use crate::{Puzzle05, CATEGORIES};
+use common::{generate::InputGenerator, rng::Rng};
+
+/// Every number in the real inputs fits in a u32, so stick to that range.
+const LIMIT: i64 = 1 << 32;
+
+impl InputGenerator for Puzzle05 {
+    /// `size` is both the number of seed ranges and the number of lines in each map.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let seeds: Vec<String> = (0..size)
+            .map(|_| {
+                let start = rng.range_i64(0..LIMIT - 1);
+                let length = rng.range_i64(1..(LIMIT - start).min(LIMIT / size as i64) + 1);
+                format!("{start} {length}")
+            })
+            .collect();
+
+        let mut blocks = vec![format!("seeds: {}", seeds.join(" "))];
+        for names in CATEGORIES.windows(2) {
+            blocks.push(format!(
+                "{}-to-{} map:\n{}",
+                names[0],
+                names[1],
+                map(size, rng)
+            ));
+        }
+        blocks.join("\n\n")
+    }
+}
+
+/// A map of `size` fragments whose source ranges don't overlap.
+fn map(size: usize, rng: &mut Rng) -> String {
+    let mut bounds: Vec<i64> = (0..size * 2).map(|_| rng.range_i64(0..LIMIT)).collect();
+    bounds.sort();
+    bounds.dedup();
+
+    let mut fragments: Vec<String> = bounds
+        .chunks_exact(2)
+        .map(|bounds| {
+            let (source_start, length) = (bounds[0], bounds[1] - bounds[0]);
+            let dest_start = rng.range_i64(0..LIMIT - length);
+            format!("{dest_start} {source_start} {length}")
+        })
+        .collect();
+    rng.shuffle(&mut fragments);
+    fragments.join("\n")
+}
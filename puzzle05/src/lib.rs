@@ -1,11 +1,69 @@
+use common::{
+    diagnostic::ParseError,
+    interval::IntervalSet,
+    piecewise::{Piece, PiecewiseLinearMap},
+    puzzle::{InputPrinter, Puzzle, Tag},
+};
+use puzzle05a::Puzzle05a;
+use puzzle05b::Puzzle05b;
 use std::ops::Range;
 
+pub mod generator;
 pub mod parser;
 pub mod puzzle05a;
 pub mod puzzle05b;
 
+/// What each map converts between, in order. Only used for the headers of the maps,
+/// which the solvers don't care about.
+const CATEGORIES: [&str; 8] = [
+    "seed",
+    "soil",
+    "fertilizer",
+    "water",
+    "light",
+    "temperature",
+    "humidity",
+    "location",
+];
+
+pub struct Puzzle05 {}
+
+impl Puzzle for Puzzle05 {
+    type Input<'a> = MapSet;
+    type PartA = Puzzle05a;
+    type PartB = Puzzle05b;
+
+    fn name() -> &'static str {
+        "05"
+    }
+
+    fn title() -> &'static str {
+        "If You Give A Seed A Fertilizer"
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Intervals]
+    }
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        Self::try_parse(input).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        MapSet::parse_from_str(input)
+    }
+}
+
+impl InputPrinter for Puzzle05 {
+    fn print(input: &Self::Input<'_>) -> String {
+        input.to_string()
+    }
+}
+
+/// One line of a map, which shifts the numbers in `domain` by `offset`.
 #[derive(Debug, PartialEq, Eq)]
-struct MapFragment {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MapFragment {
     domain: Range<i64>,
     offset: i64,
 }
@@ -17,263 +75,304 @@ impl MapFragment {
             offset: range_start - domain_start,
         }
     }
-
-    /// Computes the result of applying this fragment to an input value if the value
-    /// is contained within its domain, and returns `None` otherwise.
-    pub fn compute(&self, input: i64) -> Option<i64> {
-        if self.domain.contains(&input) {
-            Some(input + self.offset)
-        } else {
-            None
-        }
-    }
 }
 
+/// A map as it's written in the almanac, which is kept around to print it back out.
+/// Everything else goes through the [`PiecewiseLinearMap`] it converts to, where the
+/// first fragment containing a number is the one that maps it, and numbers outside
+/// every fragment map to themselves.
 #[derive(Debug, PartialEq, Eq)]
-struct Map(Vec<MapFragment>);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Map(Vec<MapFragment>);
 
 impl Map {
     pub fn new(fragments: Vec<MapFragment>) -> Self {
         Self(fragments)
     }
+}
 
-    /// Computes the result of applying this map to an input value by using the
-    /// first fragment that contains the input in its domain. If no matching fragment is found,
-    /// the input value is returned.
-    pub fn compute(&self, input: i64) -> i64 {
-        self.0
-            .iter()
-            .find_map(|fragment| fragment.compute(input))
-            .unwrap_or(input)
+impl From<&Map> for PiecewiseLinearMap {
+    fn from(map: &Map) -> Self {
+        PiecewiseLinearMap::from_pieces(
+            map.0
+                .iter()
+                .map(|fragment| Piece::new(fragment.domain.clone(), fragment.offset)),
+        )
     }
+}
 
-    /// This computes a single `Map` that is the result of applying two `Maps` in succession.
-    pub fn compose(first: &Self, second: &Self) -> Self {
-        // the "critical points" are where the domain of the composition needs
-        // to be broken into subintervals.
-        // critical points include the start/end points of the domain of the either Map,
-        // as well as _preimages_ of the start/endpoints of the domain of the second Map under the first
-        let mut critical_points: Vec<i64> = vec![];
-        for MapFragment {
-            domain: first_domain,
-            offset,
-        } in &first.0
-        {
-            critical_points.push(first_domain.start);
-            critical_points.push(first_domain.end);
-
-            let range = (first_domain.start + offset)..(first_domain.end + offset);
-
-            for MapFragment {
-                domain: second_domain,
-                ..
-            } in &second.0
-            {
-                // compute preimages of endpoints of the second Map's domain that lie
-                // in the range of the first
-                if range.contains(&second_domain.start) {
-                    critical_points.push(second_domain.start - offset);
-                }
-                if range.contains(&second_domain.end) {
-                    critical_points.push(second_domain.end - offset);
-                }
-            }
-        }
+/// The two parts read the numbers on the "seeds" line differently.
+#[derive(Debug, Clone, Copy)]
+pub enum SeedStrategy {
+    /// Each number is a seed.
+    IndividualSeeds,
+    /// Each pair of numbers is the start and length of a range of seeds.
+    PairedRanges,
+}
 
-        for MapFragment { domain, .. } in &second.0 {
-            critical_points.push(domain.start);
-            critical_points.push(domain.end);
+impl SeedStrategy {
+    pub fn seed_ranges(self, seeds: &[i64]) -> Vec<Range<i64>> {
+        match self {
+            SeedStrategy::IndividualSeeds => seeds.iter().map(|&seed| seed..seed + 1).collect(),
+            SeedStrategy::PairedRanges => seeds
+                .chunks_exact(2)
+                .map(|chunk| chunk[0]..chunk[0] + chunk[1])
+                .collect(),
         }
-
-        // get organized
-        critical_points.sort();
-        critical_points.dedup();
-
-        let mut fragments: Vec<MapFragment> = vec![];
-        // now look at pairs of consecutive critical points and determine the offset for
-        // the composition on each subinterval
-        // because we've broken up the domain using critical points, this can be computed by
-        // just looking at the first element in the interval
-        critical_points.windows(2).for_each(|window| {
-            if let [start, end] = window {
-                // compute using the first map and then the second
-                let output = second.compute(first.compute(*start));
-                let offset = output - *start;
-
-                fragments.push(MapFragment {
-                    domain: (*start..*end),
-                    offset,
-                });
-            }
-        });
-
-        Map(fragments)
-    }
-
-    fn smallest_output_over_interval(&self, interval: &Range<i64>) -> Option<i64> {
-        // this relies on the fact that each fragment of the map is increasing, so its smallest
-        // value on some set is always at the smallest input in that set
-        //
-        // sooo, we check every fragment's domain to see if it intersects the given interval.
-        // if so, compute the output at the smallest input value in the intersection.
-        // then take a min
-        self.0
-            .iter()
-            .filter_map(|MapFragment { domain, offset }| {
-                if interval.contains(&domain.start) {
-                    Some(domain.start + offset)
-                } else if domain.contains(&interval.start) {
-                    Some(interval.start + offset)
-                } else {
-                    None
-                }
-            })
-            .min()
     }
 }
 
 #[derive(Debug, PartialEq, Eq)]
-struct MapSet {
-    seeds: Vec<Range<i64>>,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MapSet {
+    seeds: Vec<i64>,
     maps: Vec<Map>,
 }
 
 impl MapSet {
-    pub fn new(seeds: Vec<Range<i64>>, maps: Vec<Map>) -> Self {
+    pub fn new(seeds: Vec<i64>, maps: Vec<Map>) -> Self {
         Self { seeds, maps }
     }
 
-    pub fn seed_outputs(&self) -> Vec<i64> {
-        self.seeds
+    pub fn maps(&self) -> &[Map] {
+        &self.maps
+    }
+
+    /// Every seed's output, found by running it through each map in turn.
+    pub fn seed_outputs(&self, strategy: SeedStrategy) -> Vec<i64> {
+        let maps: Vec<_> = self.maps.iter().map(PiecewiseLinearMap::from).collect();
+        strategy
+            .seed_ranges(&self.seeds)
             .iter()
             .flat_map(|seeds| {
                 seeds
                     .clone()
-                    .map(|seed| self.maps.iter().fold(seed, |value, map| map.compute(value)))
+                    .map(|seed| maps.iter().fold(seed, |value, map| map.apply(value)))
             })
             .collect()
     }
 
-    // keeping this around for posterity
-    #[allow(dead_code)]
-    pub fn smallest_output_old(&self) -> i64 {
-        *self.seed_outputs().iter().min().unwrap()
+    /// The brute force version of `smallest_output`, which runs every seed through
+    /// the maps one at a time. Only useful for checking the fast version.
+    pub fn smallest_output_old(&self, strategy: SeedStrategy) -> i64 {
+        *self.seed_outputs(strategy).iter().min().unwrap()
     }
 
-    pub fn smallest_output(&self) -> i64 {
+    pub fn smallest_output(&self, strategy: SeedStrategy) -> i64 {
         // compute the composition of the whoooole set
-        let composition: Map = self
+        let composition = self
             .maps
             .iter()
-            .fold(Map::new(vec![]), |acc, curr| Map::compose(&acc, curr));
+            .map(PiecewiseLinearMap::from)
+            .fold(PiecewiseLinearMap::new(), |acc, curr| {
+                PiecewiseLinearMap::compose(&acc, &curr)
+            });
 
         // then use it to compute seed values
-        self.seeds
-            .iter()
-            .filter_map(|interval| composition.smallest_output_over_interval(interval))
-            .min()
-            .unwrap()
+        let seeds: IntervalSet<i64> = strategy.seed_ranges(&self.seeds).into_iter().collect();
+        composition.min_over(&seeds).unwrap()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use common::{
+        check::{agree, Check},
+        rng::Rng,
+    };
 
     #[test]
-    fn test_map_fragment_compute() {
-        let fragment = MapFragment::new(50, 48, 52);
+    fn test_map_apply() {
+        let fragment1 = MapFragment::new(98, 2, 50);
+        let fragment2 = MapFragment::new(50, 48, 52);
+
+        let map = PiecewiseLinearMap::from(&Map::new(vec![fragment1, fragment2]));
 
-        assert_eq!(fragment.compute(79), Some(81));
-        assert_eq!(fragment.compute(11), None);
+        assert_eq!(map.apply(79), 81);
+        assert_eq!(map.apply(14), 14);
+        assert_eq!(map.apply(99), 51);
     }
 
     #[test]
-    fn test_map_compute() {
-        let fragment1 = MapFragment::new(98, 2, 50);
-        let fragment2 = MapFragment::new(50, 48, 52);
+    fn test_overlapping_fragments() {
+        // the first fragment that contains a number is the one that maps it
+        let fragment1 = MapFragment::new(10, 5, 100);
+        let fragment2 = MapFragment::new(0, 20, 200);
 
-        let map = Map::new(vec![fragment1, fragment2]);
+        let map = PiecewiseLinearMap::from(&Map::new(vec![fragment1, fragment2]));
 
-        assert_eq!(map.compute(79), 81);
-        assert_eq!(map.compute(14), 14);
-        assert_eq!(map.compute(99), 51);
+        assert_eq!(map.apply(12), 102);
+        assert_eq!(map.apply(5), 205);
+        assert_eq!(map.apply(17), 217);
     }
 
     #[test]
-    fn test_map_set_compute() {
+    fn test_seed_ranges() {
+        let seeds = [79, 14, 55, 13];
+
+        assert_eq!(
+            SeedStrategy::IndividualSeeds.seed_ranges(&seeds),
+            vec![79..80, 14..15, 55..56, 13..14]
+        );
+        assert_eq!(
+            SeedStrategy::PairedRanges.seed_ranges(&seeds),
+            vec![79..93, 55..68]
+        );
+    }
+
+    #[test]
+    fn test_seed_outputs() {
         let fragment1 = MapFragment::new(50, 48, 52);
         let map1 = Map::new(vec![fragment1]);
 
         let fragment2 = MapFragment::new(40, 30, 10);
         let map2 = Map::new(vec![fragment2]);
 
-        let map_set = MapSet::new(vec![(60..62), (0..1)], vec![map1, map2]);
+        let map_set = MapSet::new(vec![60, 2, 0, 1], vec![map1, map2]);
 
-        assert_eq!(map_set.seed_outputs(), vec![32, 33, 0]);
+        assert_eq!(
+            map_set.seed_outputs(SeedStrategy::PairedRanges),
+            vec![32, 33, 0]
+        );
     }
 
     #[test]
-    fn test_map_compose_disjoint_range() {
-        // (10..30) => +40    (range is 50..70)
-        let fragment1 = MapFragment::new(10, 20, 50);
-        let map1 = Map::new(vec![fragment1]);
-
-        // (15..25) => -12    (neither endpoint in range)
-        let fragment2 = MapFragment::new(15, 10, 3);
-        let map2 = Map::new(vec![fragment2]);
+    fn test_smallest_output_includes_unmapped_seeds() {
+        // seeds that no fragment covers keep their own number, which here is smaller
+        // than anything the fragments give, whether it's before or after them
+        let map_set = MapSet::new(vec![2, 8], vec![Map::new(vec![MapFragment::new(3, 7, 50)])]);
+        assert_eq!(map_set.smallest_output(SeedStrategy::PairedRanges), 2);
+
+        let map_set = MapSet::new(vec![0, 6], vec![Map::new(vec![MapFragment::new(0, 3, 50)])]);
+        assert_eq!(map_set.smallest_output(SeedStrategy::PairedRanges), 3);
+        assert_eq!(map_set.smallest_output(SeedStrategy::IndividualSeeds), 6);
+    }
 
-        // (10..30) => +40    (same domain as first)
-        let composition = Map::compose(&map1, &map2);
-        let composition_outputs: Vec<_> = (0..30).map(|n| composition.compute(n)).collect();
+    /// Composes the two maps, checking that the result agrees with applying them one at a
+    /// time, and returns its pieces.
+    fn composed_pieces(map1: Map, map2: Map) -> Vec<Piece> {
+        let composition = PiecewiseLinearMap::compose(&(&map1).into(), &(&map2).into());
+        let composition_outputs: Vec<_> = (0..30).map(|n| composition.apply(n)).collect();
+
+        let map_set = MapSet::new(vec![0, 30], vec![map1, map2]);
+        assert_eq!(
+            composition_outputs,
+            map_set.seed_outputs(SeedStrategy::PairedRanges)
+        );
+        composition.pieces().to_vec()
+    }
 
-        #[allow(clippy::single_range_in_vec_init)]
-        let map_set = MapSet::new(vec![(0..30)], vec![map1, map2]);
+    #[test]
+    fn test_map_compose_disjoint_range() {
+        // 10..30 => +40, so it lands on 50..70
+        let map1 = Map::new(vec![MapFragment::new(10, 20, 50)]);
+        // 15..25 => -12, which is inside the first map's domain and misses its range
+        let map2 = Map::new(vec![MapFragment::new(15, 10, 3)]);
 
-        assert_eq!(composition_outputs, map_set.seed_outputs())
+        // the second map never gets a say
+        assert_eq!(composed_pieces(map1, map2), vec![Piece::new(10..30, 40)]);
     }
 
     #[test]
     fn test_map_compose_range_contains_domain() {
-        // (10..30) => +2    (range is 12..32)
-        let fragment1 = MapFragment::new(10, 20, 12);
-        let map1 = Map::new(vec![fragment1]);
-
-        // (15..25) => -12   (both endpoints contained in range)
-        let fragment2 = MapFragment::new(15, 10, 3);
-        let map2 = Map::new(vec![fragment2]);
-
-        // (10..13) => +2            (start of domain of first, preimage of start of domain of second)
-        // (13..23) => +2 -12 = -10  (..., preimage of end of domain of second)
-        // (23..30) => +2            (..., end of domain of first)
-        let composition = Map::compose(&map1, &map2);
-        let composition_outputs: Vec<_> = (0..30).map(|n| composition.compute(n)).collect();
-
-        #[allow(clippy::single_range_in_vec_init)]
-        let map_set = MapSet::new(vec![(0..30)], vec![map1, map2]);
-
-        assert_eq!(composition_outputs, map_set.seed_outputs())
+        // 10..30 => +2, so it lands on 12..32
+        let map1 = Map::new(vec![MapFragment::new(10, 20, 12)]);
+        // 15..25 => -12, which is all inside the first map's range
+        let map2 = Map::new(vec![MapFragment::new(15, 10, 3)]);
+
+        // whatever the first map sends into 15..25 gets both shifts
+        assert_eq!(
+            composed_pieces(map1, map2),
+            vec![
+                Piece::new(10..13, 2),
+                Piece::new(13..23, -10),
+                Piece::new(23..30, 2),
+            ]
+        );
     }
 
     #[test]
     fn test_map_compose_range_overlaps_domain() {
-        // (10..20) => +2   (range is 12..22)
-        let fragment1 = MapFragment::new(10, 10, 12);
-        let map1 = Map::new(vec![fragment1]);
-
-        // (15..25) => -12  (first endpoint contained in range of first)
-        let fragment2 = MapFragment::new(15, 10, 3);
-        let map2 = Map::new(vec![fragment2]);
+        // 10..20 => +2, so it lands on 12..22
+        let map1 = Map::new(vec![MapFragment::new(10, 10, 12)]);
+        // 15..25 => -12, which overlaps the end of the first map's range
+        let map2 = Map::new(vec![MapFragment::new(15, 10, 3)]);
+
+        // 20..25 is outside the first map, so only the second one moves it
+        assert_eq!(
+            composed_pieces(map1, map2),
+            vec![
+                Piece::new(10..13, 2),
+                Piece::new(13..20, -10),
+                Piece::new(20..25, -12),
+            ]
+        );
+    }
 
-        // (10..13) => +2
-        // (13..20) => +2 -12 = -10
-        // (20..25) => -12
-        let composition = Map::compose(&map1, &map2);
-        let composition_outputs: Vec<_> = (0..30).map(|n| composition.compute(n)).collect();
+    /// Seed ranges as (start, length - 1) and maps as lists of (gap, length - 1, destination),
+    /// where each fragment's source starts `gap` after the end of the previous one.
+    /// Any numbers at all describe a valid almanac this way, however they get shrunk.
+    type AlmanacData = (Vec<(i64, i64)>, Vec<Vec<(i64, i64, i64)>>);
 
-        #[allow(clippy::single_range_in_vec_init)]
-        let map_set = MapSet::new(vec![(0..30)], vec![map1, map2]);
+    fn build_map_set((seeds, maps): &AlmanacData) -> MapSet {
+        let seeds = seeds
+            .iter()
+            .flat_map(|(start, length)| [start.abs(), length.abs() + 1])
+            .collect();
+        let maps = maps
+            .iter()
+            .map(|fragments| {
+                let mut source_start = 0;
+                Map::new(
+                    fragments
+                        .iter()
+                        .map(|(gap, length, destination)| {
+                            let fragment = MapFragment::new(
+                                source_start + gap.abs(),
+                                length.abs() + 1,
+                                destination.abs(),
+                            );
+                            source_start += gap.abs() + length.abs() + 1;
+                            fragment
+                        })
+                        .collect(),
+                )
+            })
+            .collect();
+        MapSet::new(seeds, maps)
+    }
 
-        assert_eq!(composition_outputs, map_set.seed_outputs())
+    #[test]
+    fn test_smallest_output_matches_brute_force() {
+        let number = |rng: &mut Rng| rng.range_i64(0..30);
+        Check::new().run(
+            |rng| {
+                let seeds = (0..rng.range(1..4))
+                    .map(|_| (number(rng), number(rng)))
+                    .collect();
+                let maps = (0..rng.range(1..5))
+                    .map(|_| {
+                        (0..rng.range(0..4))
+                            .map(|_| (number(rng), number(rng), number(rng)))
+                            .collect()
+                    })
+                    .collect();
+                (seeds, maps)
+            },
+            |almanac: &AlmanacData| {
+                if almanac.0.is_empty() {
+                    return Ok(());
+                }
+                let map_set = build_map_set(almanac);
+                for strategy in [SeedStrategy::IndividualSeeds, SeedStrategy::PairedRanges] {
+                    agree(
+                        map_set.smallest_output(strategy),
+                        map_set.smallest_output_old(strategy),
+                    )?;
+                }
+                Ok(())
+            },
+        );
     }
 }
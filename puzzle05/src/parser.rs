@@ -1,80 +1,70 @@
-use std::ops::Range;
-
-use crate::{Map, MapFragment, MapSet};
+use crate::{Map, MapFragment, MapSet, CATEGORIES};
+use common::diagnostic::ParseError;
+use std::fmt::Display;
+
+fn parse_number(input: &str) -> Result<i64, ParseError> {
+    input
+        .parse::<i64>()
+        .map_err(|_| ParseError::at(input, input, "a number"))
+}
 
 impl MapFragment {
     /// Assumes that input is a single line consisting of three numbers separated by whitespace.
-    pub fn parse_from_str(input: &str) -> Self {
+    pub fn parse_from_str(input: &str) -> Result<Self, ParseError> {
         let mut number_strs = input.split_whitespace();
+        let mut next_number = |what: &str| {
+            let number_str = number_strs.next().ok_or_else(|| {
+                ParseError::at_end(input, format!("a {what} after the previous number"))
+            })?;
+            parse_number(number_str).map_err(|err| err.within(input, number_str))
+        };
 
-        let dest_offset = number_strs.next().unwrap().parse::<i64>().unwrap();
-        let source_start = number_strs.next().unwrap().parse::<i64>().unwrap();
-        let source_length = number_strs.next().unwrap().parse::<i64>().unwrap();
+        let dest_offset = next_number("destination start")?;
+        let source_start = next_number("source start")?;
+        let source_length = next_number("range length")?;
+
+        if let Some(extra) = number_strs.next() {
+            return Err(ParseError::at(
+                input,
+                extra,
+                "the end of the line after three numbers",
+            ));
+        }
 
-        MapFragment::new(source_start, source_length, dest_offset)
+        Ok(MapFragment::new(source_start, source_length, dest_offset))
     }
 }
 
 impl Map {
     /// Assumes the input is a set of lines, each of which consists of three numbers.
     #[allow(dead_code)]
-    fn parse_from_str(input: &str) -> Self {
-        let fragments = input.lines().map(MapFragment::parse_from_str).collect();
-        Map::new(fragments)
+    fn parse_from_str(input: &str) -> Result<Self, ParseError> {
+        let fragments = input
+            .lines()
+            .map(|line| MapFragment::parse_from_str(line).map_err(|err| err.within(input, line)))
+            .collect::<Result<_, _>>()?;
+        Ok(Map::new(fragments))
     }
 }
 
-pub enum SeedParseStrategy {
-    IndividualSeeds,
-    PairedRanges,
-}
-
 impl MapSet {
     /// Assumes the input contains the prefix "seeds: " followed by a list of whitespace-separated values.
-    fn parse_individual_seeds(input: &str) -> Vec<Range<i64>> {
+    fn parse_seeds(input: &str) -> Result<Vec<i64>, ParseError> {
         input
             .strip_prefix("seeds: ")
-            .unwrap()
+            .ok_or_else(|| ParseError::at(input, input, "a `seeds: ` line"))?
             .split_whitespace()
-            .map(|s| {
-                let start = s.parse::<i64>().unwrap();
-                start..start + 1
-            })
-            .collect()
-    }
-
-    /// Assumes the input contains the prefix "seeds: " followed by a list of whitespace-separated values.
-    fn parse_seed_range_pairs(input: &str) -> Vec<Range<i64>> {
-        input
-            .strip_prefix("seeds: ")
-            .unwrap()
-            .split_whitespace()
-            .collect::<Vec<_>>()
-            .chunks_exact(2)
-            .map(|chunk| match chunk {
-                [s, t] => {
-                    let start = s.parse::<i64>().unwrap();
-                    let length = t.parse::<i64>().unwrap();
-                    start..start + length
-                }
-                _ => unreachable!(),
-            })
+            .map(|s| parse_number(s).map_err(|err| err.within(input, s)))
             .collect()
     }
 
     /// Assumes that the input is in the form of the entire input for this file:
     /// - starts with a "seed: " line
     /// - remainder consists of blocks each with a header line, the rest of which describes a map
-    pub fn parse_from_str(input: &str, seed_strategy: SeedParseStrategy) -> Self {
+    pub fn parse_from_str(input: &str) -> Result<Self, ParseError> {
         let mut lines = input.lines();
-        let seeds = match seed_strategy {
-            SeedParseStrategy::IndividualSeeds => {
-                MapSet::parse_individual_seeds(lines.next().unwrap())
-            }
-            SeedParseStrategy::PairedRanges => {
-                MapSet::parse_seed_range_pairs(lines.next().unwrap())
-            }
-        };
+        let seeds_line = lines.next().unwrap_or(input);
+        let seeds = MapSet::parse_seeds(seeds_line).map_err(|err| err.within(input, seeds_line))?;
 
         // skip the next empty line
         let lines = lines.skip(1);
@@ -98,24 +88,67 @@ impl MapSet {
             }
 
             // otherwise parse a map fragment
-            current_map_frags.push(MapFragment::parse_from_str(line));
+            current_map_frags
+                .push(MapFragment::parse_from_str(line).map_err(|err| err.within(input, line))?);
         }
         maps.push(Map(current_map_frags));
 
-        MapSet::new(seeds, maps)
+        Ok(MapSet::new(seeds, maps))
+    }
+}
+
+impl Display for MapFragment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (source_start, length) = (self.domain.start, self.domain.end - self.domain.start);
+        write!(f, "{} {source_start} {length}", source_start + self.offset)
+    }
+}
+
+impl Display for Map {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (idx, fragment) in self.0.iter().enumerate() {
+            if idx > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{fragment}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for MapSet {
+    /// Map headers aren't kept, so they're named after the usual categories (or
+    /// numbered, past the end of them).
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let seeds: Vec<_> = self.seeds.iter().map(i64::to_string).collect();
+        write!(f, "seeds: {}", seeds.join(" "))?;
+
+        let category = |idx: usize| {
+            CATEGORIES
+                .get(idx)
+                .map_or_else(|| format!("category{idx}"), |name| name.to_string())
+        };
+        for (idx, map) in self.maps.iter().enumerate() {
+            write!(f, "\n\n{}-to-{} map:", category(idx), category(idx + 1))?;
+            if !map.0.is_empty() {
+                write!(f, "\n{map}")?;
+            }
+        }
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{parser::SeedParseStrategy, Map, MapFragment, MapSet};
+    use crate::{Map, MapFragment, MapSet};
+    use common::diagnostic::ParseError;
 
     #[test]
     fn test_parse_fragment() {
         let input = "50 98 2";
 
         assert_eq!(
-            MapFragment::parse_from_str(input),
+            MapFragment::parse_from_str(input).unwrap(),
             MapFragment::new(98, 2, 50)
         );
     }
@@ -126,7 +159,7 @@ mod tests {
         52 50 48";
 
         assert_eq!(
-            Map::parse_from_str(input),
+            Map::parse_from_str(input).unwrap(),
             Map::new(vec![
                 MapFragment::new(98, 2, 50),
                 MapFragment::new(50, 48, 52),
@@ -135,24 +168,14 @@ mod tests {
     }
 
     #[test]
-    fn test_parse_individual_seeds() {
+    fn test_parse_seeds() {
         let input = "seeds: 79 14 55 13";
 
-        assert_eq!(
-            MapSet::parse_individual_seeds(input),
-            vec![79..80, 14..15, 55..56, 13..14]
-        );
+        assert_eq!(MapSet::parse_seeds(input).unwrap(), vec![79, 14, 55, 13]);
     }
 
     #[test]
-    fn test_parse_seed_ranges() {
-        let input = "seeds: 79 14 55 13";
-
-        assert_eq!(MapSet::parse_seed_range_pairs(input), vec![79..93, 55..68]);
-    }
-
-    #[test]
-    fn test_parse_map_set_individual_seeds() {
+    fn test_parse_map_set() {
         let input = r"seeds: 79 14 55 13
 
             seed-to-soil map:
@@ -164,9 +187,9 @@ mod tests {
             37 52 2";
 
         assert_eq!(
-            MapSet::parse_from_str(input, SeedParseStrategy::IndividualSeeds),
+            MapSet::parse_from_str(input).unwrap(),
             MapSet::new(
-                vec![79..80, 14..15, 55..56, 13..14],
+                vec![79, 14, 55, 13],
                 vec![
                     Map::new(vec![
                         MapFragment::new(98, 2, 50),
@@ -182,32 +205,35 @@ mod tests {
     }
 
     #[test]
-    fn test_parse_map_set_paired_seeds() {
-        let input = r"seeds: 79 14 55 13
-
-            seed-to-soil map:
-            50 98 2
-            52 50 48
-
-            soil-to-fertilizer map:
-            0 15 37
-            37 52 2";
+    fn test_parse_errors() {
+        assert_eq!(
+            MapFragment::parse_from_str("50 98").unwrap_err(),
+            ParseError::new(5..5, "a range length after the previous number")
+        );
+        assert_eq!(
+            MapSet::parse_seeds("seeds: 79 1x4").unwrap_err(),
+            ParseError::new(10..13, "a number")
+        );
 
+        let input = "seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n52 5O 48";
         assert_eq!(
-            MapSet::parse_from_str(input, SeedParseStrategy::PairedRanges),
-            MapSet::new(
-                vec![79..93, 55..68],
-                vec![
-                    Map::new(vec![
-                        MapFragment::new(98, 2, 50),
-                        MapFragment::new(50, 48, 52),
-                    ]),
-                    Map::new(vec![
-                        MapFragment::new(15, 37, 0),
-                        MapFragment::new(52, 2, 37),
-                    ]),
-                ]
-            ),
+            MapSet::parse_from_str(input)
+                .unwrap_err()
+                .render(input, "input"),
+            [
+                "error: expected a number",
+                " --> input:5:4",
+                "  |",
+                "5 | 52 5O 48",
+                "  |    ^^",
+            ]
+            .join("\n")
         );
     }
+
+    #[test]
+    fn test_display_map_set() {
+        let input = "seeds: 79 14 55 13\n\nseed-to-soil map:\n50 98 2\n52 50 48";
+        assert_eq!(MapSet::parse_from_str(input).unwrap().to_string(), input);
+    }
 }
@@ -1,15 +1,18 @@
-use crate::{parser::SeedParseStrategy, MapSet};
+use crate::{MapSet, SeedStrategy};
 use common::puzzle::PuzzlePart;
 
 pub struct Puzzle05b {}
 
 impl PuzzlePart for Puzzle05b {
+    type Input<'a> = MapSet;
+
     fn description() -> &'static str {
         "Find the smallest location that can be obtained by passing a set of seeds through a series of maps, but with many more seeds."
     }
 
-    fn solve(input: &str) -> String {
-        let map_set = MapSet::parse_from_str(input, SeedParseStrategy::PairedRanges);
-        map_set.smallest_output().to_string()
+    fn solve(input: &Self::Input<'_>) -> String {
+        input
+            .smallest_output(SeedStrategy::PairedRanges)
+            .to_string()
     }
 }
@@ -0,0 +1,25 @@
+use crate::{InputPrinter, Puzzle06};
+use common::{generate::InputGenerator, rng::Rng};
+
+/// How many digits the times can have between them, so that part b's one big race still
+/// has a time whose square fits in a u64.
+const TIME_DIGITS: usize = 9;
+
+impl InputGenerator for Puzzle06 {
+    /// `size` is the number of races, up to 9, since part b reads them all as one race.
+    ///
+    /// Every race can be won, as in the real inputs, so each record distance is less than
+    /// the furthest a boat can go in the time.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let count = size.clamp(1, TIME_DIGITS);
+        let races: Vec<(u64, u64)> = (0..count)
+            .map(|_| {
+                let digits = rng.range(1..TIME_DIGITS / count + 1) as u32;
+                let time = rng.range(10usize.pow(digits - 1).max(2)..10usize.pow(digits)) as u64;
+                let furthest = time / 2 * (time - time / 2);
+                (time, rng.below(furthest))
+            })
+            .collect();
+        Puzzle06::print(&races)
+    }
+}
@@ -1,7 +1,62 @@
+use common::puzzle::{InputPrinter, Puzzle, Tag};
+use puzzle06a::Puzzle06a;
+use puzzle06b::Puzzle06b;
+
+pub mod generator;
 pub mod puzzle06a;
 pub mod puzzle06b;
 
+pub struct Puzzle06 {}
+
+impl Puzzle for Puzzle06 {
+    type Input<'a> = Vec<(u64, u64)>;
+    type PartA = Puzzle06a;
+    type PartB = Puzzle06b;
+
+    fn name() -> &'static str {
+        "06"
+    }
+
+    fn title() -> &'static str {
+        "Wait For It"
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Math]
+    }
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        parser::parse(input)
+    }
+}
+
+impl InputPrinter for Puzzle06 {
+    /// Lines the times up with their distances, the way the puzzle input does.
+    fn print(input: &Self::Input<'_>) -> String {
+        let width = input
+            .iter()
+            .flat_map(|&(time, distance)| [time, distance])
+            .map(|n| n.to_string().len())
+            .max()
+            .unwrap_or(0);
+        let row = |label: &str, numbers: Vec<u64>| {
+            let numbers: String = numbers.iter().map(|n| format!(" {n:>width$}")).collect();
+            format!("{label:<9}{numbers}")
+        };
+        [
+            row("Time:", input.iter().map(|&(time, _)| time).collect()),
+            row(
+                "Distance:",
+                input.iter().map(|&(_, distance)| distance).collect(),
+            ),
+        ]
+        .join("\n")
+    }
+}
+
 mod math_things {
+    use common::{answer::Checked, num::isqrt};
+
     // If the race is T seconds long, and you charge it for x seconds, then
     // it will travel at x mm/s for (T-x) seconds, meaning it travels
     // a total of x * (T-x) mm.
@@ -12,22 +67,27 @@ mod math_things {
     //     x^2 - Tx + D < 0
     // Its solutions are the integer values for which:
     //     (T - sqrt(T^2 - 4*D)) / 2  <  x  < (T + sqrt(T^2 - 4*D)) / 2
-    // If those two numbers are not integers themselves, the number of integers
-    // between them is the difference of their floors.
-    // If they are integers, the number of integers between them is one less
-    // than their differences.
-    // Sooo let's just do all that!
+    // Rather than trust floating point with the square root, we take its integer part,
+    // which puts us at most a step or two before the first winning charge time, and walk
+    // forward from there. The winning times are symmetric around T/2, so the last one
+    // is as far from T as the first one is from 0.
     pub fn number_of_ways_to_win(time: u64, distance: u64) -> u64 {
-        let disc = (time * time - 4 * distance) as f64;
-        let disc_sqrt = disc.powf(0.5);
-        let left = (time as f64 - disc_sqrt) / 2.0;
-        let right = (time as f64 + disc_sqrt) / 2.0;
-
-        // if disc_sqrt is an integer...
-        if disc_sqrt.floor() == disc_sqrt {
-            (right - left) as u64 - 1
+        // no more than T^2/4, so this can't overflow once T^2 hasn't
+        let wins = |charge: u64| charge * (time - charge) > distance;
+
+        // if the discriminant is negative, no charge time goes far enough
+        let Some(disc) = time.times(time).checked_sub(4.times(distance)) else {
+            return 0;
+        };
+        let mut first = (time - isqrt(disc)) / 2;
+        while first <= time / 2 && !wins(first) {
+            first += 1;
+        }
+
+        if first > time / 2 {
+            0
         } else {
-            (right.floor() as u64) - (left.floor() as u64)
+            time - 2 * first + 1
         }
     }
 
@@ -36,6 +96,32 @@ mod math_things {
         assert_eq!(number_of_ways_to_win(7, 9), 4);
         assert_eq!(number_of_ways_to_win(15, 40), 8);
         assert_eq!(number_of_ways_to_win(30, 200), 9);
+        assert_eq!(number_of_ways_to_win(4, 4), 0);
+        assert_eq!(number_of_ways_to_win(4, 5), 0);
+        assert_eq!(number_of_ways_to_win(1 << 31, 1 << 61), 0);
+        assert_eq!(number_of_ways_to_win(1 << 31, (1 << 60) - 1), 1);
+    }
+
+    #[cfg(feature = "checked")]
+    #[test]
+    #[should_panic(expected = "u64 overflowed in multiplication")]
+    fn test_number_of_ways_to_win_overflow() {
+        number_of_ways_to_win(1 << 32, 0);
+    }
+
+    #[test]
+    fn test_number_of_ways_to_win_matches_counting() {
+        use common::check::{agree, Check};
+
+        Check::new().run(
+            |rng| (rng.below(200), rng.below(10_000)),
+            |&(time, distance)| {
+                let expected = (0..=time)
+                    .filter(|charge| charge * (time - charge) > distance)
+                    .count() as u64;
+                agree(number_of_ways_to_win(time, distance), expected)
+            },
+        );
     }
 }
 
@@ -48,14 +134,6 @@ mod parser {
             .collect()
     }
 
-    fn parse_line_ignore_spaces(input: &str) -> u64 {
-        let (_, nums) = input.split_once(':').unwrap();
-        nums.split_whitespace()
-            .collect::<String>()
-            .parse::<u64>()
-            .unwrap()
-    }
-
     pub fn parse(input: &str) -> Vec<(u64, u64)> {
         // there should be only two lines
         let line1 = input.lines().next().unwrap();
@@ -66,14 +144,18 @@ mod parser {
         times.into_iter().zip(distances).collect()
     }
 
-    pub fn parse_ignore_spaces(input: &str) -> (u64, u64) {
-        // there should be only two lines
-        let line1 = input.lines().next().unwrap();
-        let line2 = input.lines().nth(1).unwrap();
+    /// Reads the races as one big race, as if the spaces between their numbers weren't there.
+    pub fn join_races(races: &[(u64, u64)]) -> (u64, u64) {
+        let join = |nums: Vec<u64>| {
+            nums.iter()
+                .map(u64::to_string)
+                .collect::<String>()
+                .parse::<u64>()
+                .unwrap()
+        };
 
-        let times = parse_line_ignore_spaces(line1);
-        let distances = parse_line_ignore_spaces(line2);
-        (times, distances)
+        let (times, distances) = races.iter().copied().unzip();
+        (join(times), join(distances))
     }
 
     #[test]
@@ -86,11 +168,7 @@ mod parser {
     }
 
     #[test]
-    fn test_parse_line_ignore_spaces() {
-        assert_eq!(parse_line_ignore_spaces("Time:      7  15   30 "), 71530);
-        assert_eq!(
-            parse_line_ignore_spaces("Distance:   291   1172   1176   2026"),
-            291117211762026
-        );
+    fn test_join_races() {
+        assert_eq!(join_races(&[(7, 9), (15, 40), (30, 200)]), (71530, 940200));
     }
 }
@@ -1,18 +1,20 @@
-use crate::{math_things::number_of_ways_to_win, parser::parse};
-use common::puzzle::PuzzlePart;
+use crate::math_things::number_of_ways_to_win;
+use common::{answer::Answer, puzzle::PuzzlePart};
 
 pub struct Puzzle06a {}
 
 impl PuzzlePart for Puzzle06a {
+    type Input<'a> = Vec<(u64, u64)>;
+
     fn description() -> &'static str {
         "Compute the product of numbers of ways to win a boat race."
     }
 
-    fn solve(input: &str) -> String {
-        parse(input)
-            .into_iter()
-            .map(|(time, distance)| number_of_ways_to_win(time, distance))
-            .product::<u64>()
+    fn solve(input: &Self::Input<'_>) -> String {
+        input
+            .iter()
+            .map(|&(time, distance)| Answer::from(number_of_ways_to_win(time, distance)))
+            .product::<Answer>()
             .to_string()
     }
 }
@@ -1,16 +1,18 @@
 use common::puzzle::PuzzlePart;
 
-use crate::{math_things::number_of_ways_to_win, parser::parse_ignore_spaces};
+use crate::{math_things::number_of_ways_to_win, parser::join_races};
 
 pub struct Puzzle06b {}
 
 impl PuzzlePart for Puzzle06b {
+    type Input<'a> = Vec<(u64, u64)>;
+
     fn description() -> &'static str {
         "Compute the number of ways to win just one big boat race."
     }
 
-    fn solve(input: &str) -> String {
-        let (time, distance) = parse_ignore_spaces(input);
+    fn solve(input: &Self::Input<'_>) -> String {
+        let (time, distance) = join_races(input);
         number_of_ways_to_win(time, distance).to_string()
     }
 }
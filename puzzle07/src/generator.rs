@@ -0,0 +1,19 @@
+use crate::Puzzle07;
+use common::{generate::InputGenerator, rng::Rng};
+
+const CARDS: [char; 13] = [
+    '2', '3', '4', '5', '6', '7', '8', '9', 'T', 'J', 'Q', 'K', 'A',
+];
+
+impl InputGenerator for Puzzle07 {
+    /// `size` is the number of hands.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        (0..size)
+            .map(|_| {
+                let hand: String = (0..5).map(|_| *rng.choose(&CARDS)).collect();
+                format!("{hand} {}", rng.range(1..1001))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
@@ -1,10 +1,49 @@
+use common::puzzle::{InputPrinter, Puzzle, Tag};
+use puzzle07a::Puzzle07a;
+use puzzle07b::Puzzle07b;
 use std::cmp::Ordering;
 
+pub mod generator;
 pub mod parser;
 pub mod puzzle07a;
 pub mod puzzle07b;
 
+pub struct Puzzle07 {}
+
+impl Puzzle for Puzzle07 {
+    type Input<'a> = Vec<(Hand, u32)>;
+    type PartA = Puzzle07a;
+    type PartB = Puzzle07b;
+
+    fn name() -> &'static str {
+        "07"
+    }
+
+    fn title() -> &'static str {
+        "Camel Cards"
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Sorting]
+    }
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input.lines().map(parser::parse_line).collect()
+    }
+}
+
+impl InputPrinter for Puzzle07 {
+    fn print(input: &Self::Input<'_>) -> String {
+        input
+            .iter()
+            .map(|(hand, bid)| format!("{hand} {bid}"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Card {
     Number(u32),
     Jack,
@@ -52,6 +91,7 @@ mod card_tests {
 }
 
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Hand([Card; 5]);
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
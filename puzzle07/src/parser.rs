@@ -1,4 +1,6 @@
 use crate::{Card, Hand};
+use std::fmt::Display;
+
 fn parse_card(ch: char) -> Card {
     if let Some(digit) = ch.to_digit(10) {
         Card::Number(digit)
@@ -25,6 +27,25 @@ fn parse_hand(input: &str) -> Hand {
     ])
 }
 
+impl Display for Card {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Card::Number(10) => write!(f, "T"),
+            Card::Number(n) => write!(f, "{n}"),
+            Card::Jack => write!(f, "J"),
+            Card::Queen => write!(f, "Q"),
+            Card::King => write!(f, "K"),
+            Card::Ace => write!(f, "A"),
+        }
+    }
+}
+
+impl Display for Hand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.iter().try_for_each(|card| write!(f, "{card}"))
+    }
+}
+
 pub fn parse_line(input: &str) -> (Hand, u32) {
     let (hand_str, num_str) = input.split_once(' ').unwrap();
     (parse_hand(hand_str), num_str.parse().unwrap())
@@ -74,4 +95,9 @@ mod tests {
             )
         )
     }
+
+    #[test]
+    fn test_display_hand() {
+        assert_eq!(parse_hand("T55J5").to_string(), "T55J5");
+    }
 }
@@ -1,21 +1,22 @@
-use common::puzzle::PuzzlePart;
+use common::{answer::Answer, puzzle::PuzzlePart};
 
-use crate::{parser::parse_line, Hand, HandRep};
+use crate::{Hand, HandRep};
 
 pub struct Puzzle07a {}
 
 impl PuzzlePart for Puzzle07a {
+    type Input<'a> = Vec<(Hand, u32)>;
+
     fn description() -> &'static str {
         "Sum the 'ranks' of a set of poker hands."
     }
 
-    fn solve(input: &str) -> String {
-        let mut hands: Vec<(Hand, HandRep, u32)> = input
-            .lines()
-            .map(parse_line)
+    fn solve(input: &Self::Input<'_>) -> String {
+        let mut hands: Vec<(&Hand, HandRep, u32)> = input
+            .iter()
             .map(|(hand, num)| {
                 let rep = hand.rep();
-                (hand, rep, num)
+                (hand, rep, *num)
             })
             .collect();
 
@@ -30,8 +31,8 @@ impl PuzzlePart for Puzzle07a {
         hands
             .iter()
             .enumerate()
-            .map(|(rank, (_, _, num))| (rank as u32 + 1) * *num)
-            .sum::<u32>()
+            .map(|(rank, (_, _, num))| Answer::from(rank + 1) * Answer::from(*num))
+            .sum::<Answer>()
             .to_string()
     }
 }
@@ -0,0 +1,96 @@
+use crate::Puzzle08;
+use common::{generate::InputGenerator, rng::Rng};
+
+/// The letters that names of nodes other than starts and ends can finish with.
+const MIDDLE_LETTERS: &[u8] = b"BCDEFGHIJKLMNOPQRSTUVWXY";
+
+/// How many of those names there are with three letters.
+const THREE_LETTER_NAMES: usize = 26 * 26 * MIDDLE_LETTERS.len();
+
+impl InputGenerator for Puzzle08 {
+    /// `size` is the number of steps, and the number of ghosts up to 6, which is as many
+    /// as the real inputs have.
+    ///
+    /// Like in the real inputs, each ghost goes round a loop of its own that's a whole
+    /// number of times as long as the steps. Its end node is at the end of the loop and
+    /// leads the same way as its start node, so that it's back on an end node every time
+    /// round. That way every ghost (including the one from `AAA` to `ZZZ`) gets to an end
+    /// node, and they all get to one at once eventually.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let steps: Vec<char> = (0..size.max(1))
+            .map(|_| if rng.chance(0.5) { 'L' } else { 'R' })
+            .collect();
+
+        let mut prefixes = vec![String::from("AA")];
+        while prefixes.len() < size.clamp(1, 6) {
+            let prefix = format!("{}{}", letter(rng), letter(rng));
+            if !prefixes.contains(&prefix) {
+                prefixes.push(prefix);
+            }
+        }
+
+        // each ghost's loop, from its start node round to its end node
+        let loop_lengths: Vec<usize> = prefixes
+            .iter()
+            .map(|_| steps.len() * rng.range(1..5))
+            .collect();
+        let count: usize = loop_lengths.iter().map(|len| len - 1).sum();
+        let mut ids: Vec<usize> = (0..count.max(THREE_LETTER_NAMES)).collect();
+        rng.shuffle(&mut ids);
+        let mut middle_names: Vec<String> = ids[..count].iter().copied().map(middle_name).collect();
+
+        let mut loops: Vec<Vec<String>> = vec![];
+        for (prefix, len) in prefixes.iter().zip(&loop_lengths) {
+            let mut nodes = vec![format!("{prefix}A")];
+            nodes.extend(middle_names.drain(..len - 1));
+            // part a goes from `AAA` to `ZZZ` rather than `AAZ`
+            nodes.push(if prefix == "AA" {
+                "ZZZ".into()
+            } else {
+                format!("{prefix}Z")
+            });
+            loops.push(nodes);
+        }
+
+        // the way out of a node that the ghost doesn't take can go anywhere at all
+        let names: Vec<&String> = loops.iter().flatten().collect();
+        let mut lines: Vec<String> = vec![];
+        for nodes in &loops {
+            for (idx, name) in nodes.iter().enumerate() {
+                // the end node leads on to the same node that the start node does
+                let position = idx % (nodes.len() - 1);
+                let next = &nodes[position + 1];
+                let other = rng.choose(&names);
+                let (left, right) = match steps[position % steps.len()] {
+                    'L' => (next, *other),
+                    _ => (*other, next),
+                };
+                lines.push(format!("{name} = ({left}, {right})"));
+            }
+        }
+        rng.shuffle(&mut lines);
+
+        format!(
+            "{}\n\n{}",
+            steps.iter().collect::<String>(),
+            lines.join("\n")
+        )
+    }
+}
+
+fn letter(rng: &mut Rng) -> char {
+    (b'A' + rng.below(26) as u8) as char
+}
+
+/// A distinct name for each `idx` that doesn't end in `A` or `Z`, three characters long
+/// until those run out.
+fn middle_name(idx: usize) -> String {
+    let last = MIDDLE_LETTERS[idx % MIDDLE_LETTERS.len()] as char;
+    let mut rest = idx / MIDDLE_LETTERS.len();
+    let mut name = vec![last];
+    while name.len() < 3 || rest > 0 {
+        name.push((b'A' + (rest % 26) as u8) as char);
+        rest /= 26;
+    }
+    name.iter().rev().collect()
+}
@@ -1,47 +1,99 @@
-use parser::ParsedNode;
+use common::{
+    diagnostic::ParseError,
+    graph::{self, NodeId},
+    parse,
+    puzzle::{InputPrinter, Puzzle, Tag},
+};
+use parser::{parse_input, ParsedNode};
+use puzzle08a::Puzzle08a;
+use puzzle08b::Puzzle08b;
 
+pub mod generator;
 pub mod puzzle08a;
 pub mod puzzle08b;
 
 pub mod parser;
 
-#[derive(Debug, PartialEq, Eq)]
+pub struct Puzzle08 {}
+
+impl Puzzle for Puzzle08 {
+    type Input<'a> = (Vec<Step>, Graph);
+    type PartA = Puzzle08a;
+    type PartB = Puzzle08b;
+
+    fn name() -> &'static str {
+        "08"
+    }
+
+    fn title() -> &'static str {
+        "Haunted Wasteland"
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Graph, Tag::Cycles]
+    }
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        Self::try_parse(input).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        let (steps, parsed_nodes) = parse::finish(input, parse_input)?;
+        Ok((steps, Graph::from(parsed_nodes)))
+    }
+}
+
+impl InputPrinter for Puzzle08 {
+    fn print(input: &Self::Input<'_>) -> String {
+        let (steps, graph) = input;
+        let steps: String = steps.iter().map(Step::to_string).collect();
+        format!("{steps}\n\n{graph}")
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Step {
     Left,
     Right,
 }
 
+/// The network of nodes, labeled by their names, with an edge for each way out of a node
+/// marked with the step that takes it.
 #[derive(Debug, PartialEq, Eq)]
-pub struct Node<'a> {
-    name: &'a str,
-    left_index: usize,
-    right_index: usize,
-}
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Graph(graph::Graph<(), Step>);
 
-#[derive(Debug, PartialEq, Eq)]
-pub struct Graph<'a>(Vec<Node<'a>>);
+impl Graph {
+    /// Where taking `step` from node `id` leads.
+    fn follow(&self, id: NodeId, step: Step) -> NodeId {
+        self.0
+            .edges(id)
+            .iter()
+            .find(|edge| edge.weight == step)
+            .map(|edge| edge.to)
+            .expect("every node has a way left and a way right")
+    }
+
+    fn name(&self, id: NodeId) -> &str {
+        self.0.label_of(id).expect("every node has a name")
+    }
+}
 
-impl<'a> From<Vec<ParsedNode<'a>>> for Graph<'a> {
-    fn from(parsed_nodes: Vec<ParsedNode<'a>>) -> Self {
-        let mut nodes: Vec<Node> = vec![];
+impl From<Vec<ParsedNode<'_>>> for Graph {
+    fn from(parsed_nodes: Vec<ParsedNode<'_>>) -> Self {
+        let mut graph = graph::Graph::new();
+        for ParsedNode { name, .. } in &parsed_nodes {
+            graph.add_labeled_node(name, ());
+        }
 
         for ParsedNode { name, left, right } in &parsed_nodes {
-            let left_index = parsed_nodes
-                .iter()
-                .position(|node| &node.name == left)
-                .unwrap();
-            let right_index = parsed_nodes
-                .iter()
-                .position(|node| &node.name == right)
-                .unwrap();
-            nodes.push(Node {
-                name,
-                left_index,
-                right_index,
-            });
+            let id = graph.id_of(name).unwrap();
+            graph.add_edge(id, graph.id_of(left).unwrap(), Step::Left);
+            graph.add_edge(id, graph.id_of(right).unwrap(), Step::Right);
         }
 
-        Graph(nodes)
+        Graph(graph)
     }
 }
 
@@ -71,25 +123,12 @@ mod tests {
         ];
         let graph = Graph::from(nodes);
 
-        assert_eq!(
-            graph,
-            Graph(vec![
-                Node {
-                    name: "AAA",
-                    left_index: 1,
-                    right_index: 1
-                },
-                Node {
-                    name: "BBB",
-                    left_index: 0,
-                    right_index: 2
-                },
-                Node {
-                    name: "ZZZ",
-                    left_index: 2,
-                    right_index: 2
-                },
-            ])
-        );
+        let (aaa, bbb, zzz) = (0, 1, 2);
+        assert_eq!(graph.name(bbb), "BBB");
+        assert_eq!(graph.follow(aaa, Step::Left), bbb);
+        assert_eq!(graph.follow(aaa, Step::Right), bbb);
+        assert_eq!(graph.follow(bbb, Step::Left), aaa);
+        assert_eq!(graph.follow(bbb, Step::Right), zzz);
+        assert_eq!(graph.follow(zzz, Step::Right), zzz);
     }
 }
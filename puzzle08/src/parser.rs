@@ -1,14 +1,39 @@
-use crate::Step;
-
-pub fn parse_step_line(input: &str) -> Vec<Step> {
-    input
-        .chars()
-        .map(|c| match c {
-            'L' => Step::Left,
-            'R' => Step::Right,
-            _ => panic!("Unrecognized step character"),
-        })
-        .collect()
+use crate::{Graph, Step};
+use common::parse::{expect, lines, named_pair, PResult};
+use nom::{
+    branch::alt,
+    character::complete::{alphanumeric1, char, line_ending},
+    combinator::{eof, map, peek, value},
+    multi::many1,
+    sequence::{pair, separated_pair, terminated},
+};
+use std::fmt::Display;
+
+/// The line of steps, a blank line, and then the nodes.
+pub fn parse_input(input: &str) -> PResult<'_, (Vec<Step>, Vec<ParsedNode<'_>>)> {
+    separated_pair(
+        parse_step_line,
+        expect("a blank line", pair(line_ending, line_ending)),
+        lines(parse_node),
+    )(input)
+}
+
+pub fn parse_step_line(input: &str) -> PResult<'_, Vec<Step>> {
+    let step = alt((value(Step::Left, char('L')), value(Step::Right, char('R'))));
+    // everything up to the end of the line has to be a step
+    terminated(
+        many1(expect("`L` or `R`", step)),
+        expect("`L` or `R`", peek(alt((line_ending, eof)))),
+    )(input)
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Step::Left => write!(f, "L"),
+            Step::Right => write!(f, "R"),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -20,26 +45,54 @@ pub struct ParsedNode<'a> {
 
 // nodes look like:
 // HGK = (LRV, NBJ)
-// they always have three-letter names, so we can just slice
-// into the input
-pub fn parse_node(input: &str) -> ParsedNode {
-    ParsedNode {
-        name: &input[0..3],
-        left: &input[7..10],
-        right: &input[12..15],
+pub fn parse_node(input: &str) -> PResult<'_, ParsedNode<'_>> {
+    map(
+        named_pair(alphanumeric1, alphanumeric1),
+        |(name, (left, right))| ParsedNode { name, left, right },
+    )(input)
+}
+
+impl Display for Graph {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (id, _) in self.0.nodes() {
+            if id > 0 {
+                writeln!(f)?;
+            }
+            write!(
+                f,
+                "{} = ({}, {})",
+                self.name(id),
+                self.name(self.follow(id, Step::Left)),
+                self.name(self.follow(id, Step::Right))
+            )?;
+        }
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::Step;
+    use crate::{Puzzle08, Step};
+    use common::{
+        check::{agree, Check},
+        diagnostic::ParseError,
+        puzzle::{InputPrinter, Puzzle},
+    };
+
+    /// Three capital letters, like the node names in the puzzle.
+    fn node_name(idx: usize) -> String {
+        [idx / 676, idx / 26 % 26, idx % 26]
+            .iter()
+            .map(|&letter| (b'A' + letter as u8) as char)
+            .collect()
+    }
 
     #[test]
     fn test_parse_step_line() {
         assert_eq!(
             parse_step_line("LLR"),
-            vec![Step::Left, Step::Left, Step::Right,]
+            Ok(("", vec![Step::Left, Step::Left, Step::Right]))
         )
     }
 
@@ -47,11 +100,73 @@ mod tests {
     fn test_parse_node() {
         assert_eq!(
             parse_node("HGK = (LRV, NBJ)"),
-            ParsedNode {
-                name: "HGK",
-                left: "LRV",
-                right: "NBJ",
-            }
+            Ok((
+                "",
+                ParsedNode {
+                    name: "HGK",
+                    left: "LRV",
+                    right: "NBJ",
+                }
+            ))
         )
     }
+
+    #[test]
+    fn test_parse_crlf() {
+        let input = "LR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)\n";
+        assert_eq!(
+            Puzzle08::try_parse(&input.replace('\n', "\r\n")).unwrap(),
+            Puzzle08::try_parse(input).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Puzzle08::try_parse("LXR\n\nAAA = (AAA, AAA)"),
+            Err(ParseError::new(1..2, "`L` or `R`"))
+        );
+        assert_eq!(
+            Puzzle08::try_parse("LR\n\nAAA = (AAA, AAA)\nBBB = (AAA BBB)"),
+            Err(ParseError::new(31..32, "`, `"))
+        );
+    }
+
+    #[test]
+    fn test_printed_input_parses_the_same() {
+        // steps as whether they go right, and nodes as the indices of their neighbours
+        Check::new().run(
+            |rng| {
+                let steps: Vec<bool> = (0..rng.range(1..10)).map(|_| rng.chance(0.5)).collect();
+                let count = rng.range(1..10);
+                let nodes: Vec<(usize, usize)> = (0..count)
+                    .map(|_| (rng.range(0..count), rng.range(0..count)))
+                    .collect();
+                (steps, nodes)
+            },
+            |(steps, nodes)| {
+                if steps.is_empty() || nodes.is_empty() {
+                    return Ok(());
+                }
+                // shrinking can drop nodes that others still point to
+                let name = |idx: usize| node_name(idx.min(nodes.len() - 1));
+                let steps: String = steps
+                    .iter()
+                    .map(|&right| if right { 'R' } else { 'L' })
+                    .collect();
+                let nodes: Vec<_> = nodes
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, &(left, right))| {
+                        format!("{} = ({}, {})", name(idx), name(left), name(right))
+                    })
+                    .collect();
+                let input = format!("{steps}\n\n{}", nodes.join("\n"));
+
+                let parsed = Puzzle08::parse(&input);
+                let printed = Puzzle08::print(&parsed);
+                agree(Puzzle08::parse(&printed), parsed)
+            },
+        );
+    }
 }
@@ -1,101 +1,134 @@
-use common::puzzle::PuzzlePart;
-
-use crate::{
-    parser::{parse_node, parse_step_line, ParsedNode},
-    Graph, Step,
+use common::{
+    cycle::{self, Cycle},
+    graph::NodeId,
+    math::{Congruence, CrtError},
+    puzzle::PuzzlePart,
 };
 
+use crate::{Graph, Step};
+
 pub struct Puzzle08b {}
 
 impl PuzzlePart for Puzzle08b {
+    type Input<'a> = (Vec<Step>, Graph);
+
     fn description() -> &'static str {
         "Find the number of steps needed to do multiple simultaneous traversals of a graph."
     }
 
-    // NOTE: This solution relies on a (fortunately correct) assumption that the correct path uses the
-    // whole set of left/right steps a whole number of times. So we can compute the result applying all 281 steps
-    // to get a `map` that takes each node (referred to by its index in the graph) to the node you end up
-    // after the full set of steps. We then iteratively apply that map until it takes the
-    // starting node set ("__A") into the ending node set ("__Z").
-    //
-    // I don't love this solution, but it works so at least there's that. It took a hot 25 minutes
-    // to run, meaning without the map optimization it would take something like five days to run. Yikes.
-    fn solve(input: &str) -> String {
-        let steps = parse_step_line(input.lines().next().unwrap());
-        let parsed_nodes: Vec<ParsedNode> = input.lines().skip(2).map(|s| parse_node(s)).collect();
-        let graph = Graph::from(parsed_nodes);
-        let map = graph.build_map(&steps);
-
-        let step_count = graph.map_traverse(&map);
-
-        (step_count as usize * steps.len()).to_string()
+    // NOTE: Each ghost is only ever somewhere in the graph and somewhere in the list of
+    // steps, so it has to start going round in circles eventually. Once we know where each
+    // ghost's loop starts, how long it is and when it passes an end node, the steps where
+    // they're all on end nodes at once are a system of congruences we can solve with the
+    // Chinese remainder theorem, rather than walking the ghosts there one step at a time
+    // (which took a hot 25 minutes, even after collapsing the whole list of steps into one
+    // big jump).
+    fn solve(input: &Self::Input<'_>) -> String {
+        let (steps, graph) = input;
+        graph
+            .ghost_traverse(steps)
+            .expect("the ghosts are never all on end nodes at once")
+            .to_string()
     }
 }
 
-impl<'a> Graph<'a> {
-    // returns the list of resulting nodes (their indices, actually) obtained by
-    // following the full set of steps
-    pub fn build_map(&'a self, steps: &[Step]) -> Vec<usize> {
-        let mut indices: Vec<_> = (0..self.0.len()).collect();
-
-        for step in steps {
-            indices.iter_mut().for_each(|index| {
-                let node = &self.0[*index];
-                *index = match step {
-                    Step::Left => node.left_index,
-                    Step::Right => node.right_index,
-                }
-            });
-        }
+/// Where a single ghost goes, as a loop and the steps of it where the ghost is on an end node.
+struct GhostPath {
+    cycle: Cycle,
+    /// In order, and all less than `cycle.prefix + cycle.period`.
+    ends: Vec<usize>,
+}
 
-        indices
+impl GhostPath {
+    fn at_end_after(&self, step_count: usize) -> bool {
+        self.ends
+            .binary_search(&self.cycle.reduce(step_count))
+            .is_ok()
     }
+}
 
-    pub fn map_traverse(&'a self, map: &[usize]) -> u64 {
-        let mut indices: Vec<_> = self
-            .0
+impl Graph {
+    fn ghost_path(&self, steps: &[Step], start: NodeId) -> GhostPath {
+        // a ghost's state is the node it's on and how far through the steps it is
+        let (cycle, states) = cycle::find((start, 0), |&(id, step_index)| {
+            (
+                self.follow(id, steps[step_index]),
+                (step_index + 1) % steps.len(),
+            )
+        });
+
+        let ends = states
             .iter()
             .enumerate()
-            .filter_map(|(index, node)| {
-                if node.name.ends_with('A') {
-                    Some(index)
-                } else {
-                    None
-                }
-            })
+            .filter(|(_, (id, _))| self.name(*id).ends_with('Z'))
+            .map(|(step_count, _)| step_count)
             .collect();
 
-        let end_indices: Vec<_> = self
+        GhostPath { cycle, ends }
+    }
+
+    /// The number of steps until every ghost, starting on every node ending in `A`, is on
+    /// a node ending in `Z` at the same time, if they ever are.
+    pub fn ghost_traverse(&self, steps: &[Step]) -> Option<u64> {
+        let paths: Vec<_> = self
             .0
-            .iter()
-            .enumerate()
-            .filter_map(|(index, node)| {
-                if node.name.ends_with('Z') {
-                    Some(index)
-                } else {
-                    None
-                }
-            })
+            .nodes()
+            .filter(|(id, _)| self.name(*id).ends_with('A'))
+            .map(|(id, _)| self.ghost_path(steps, id))
             .collect();
 
-        let mut step_count = 0;
+        // until every ghost has made it into its loop, just check each step in turn
+        let settled = paths.iter().map(|path| path.cycle.prefix).max()?;
+        if let Some(step_count) =
+            (0..settled).find(|step_count| paths.iter().all(|path| path.at_end_after(*step_count)))
+        {
+            return Some(step_count as u64);
+        }
+
+        // after that, each ghost is on an end node at steps congruent to one of the end
+        // nodes in its loop, so try every way of picking one of those for each ghost
+        let mut solutions = vec![Congruence::new(0, 1)];
+        for path in &paths {
+            let Cycle { prefix, period } = path.cycle;
+            let loop_ends: Vec<_> = path
+                .ends
+                .iter()
+                .filter(|end| **end >= prefix)
+                .map(|end| Congruence::new(*end as i128, period as i128))
+                .collect();
 
-        loop {
-            let n = indices
+            solutions = solutions
                 .iter()
-                .filter(|index| end_indices.contains(index))
-                .count();
-            // if n > 4 {
-            //     println!("got {n} matches at step : {step_count}")
-            // }
-            if n == indices.len() {
-                break;
-            }
-
-            indices.iter_mut().for_each(|index| *index = map[*index]);
-            step_count += 1;
+                .flat_map(|solution| loop_ends.iter().map(|end| solution.combine(end)))
+                .filter(|solution| *solution != Err(CrtError::NoSolution))
+                .collect::<Result<_, _>>()
+                .expect("the number of steps overflowed");
         }
 
-        step_count
+        solutions
+            .iter()
+            .map(|solution| solution.smallest_at_least(settled as i128))
+            .min()
+            .map(|step_count| step_count as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Puzzle08;
+    use common::puzzle::Puzzle;
+
+    #[test]
+    fn test_ghost_traverse() {
+        let (steps, graph) = Puzzle08::parse(include_str!("../data/example"));
+        assert_eq!(graph.ghost_traverse(&steps), Some(6));
+    }
+
+    #[test]
+    fn test_ghosts_that_never_line_up() {
+        // one ghost is on an end node every odd step, the other every even one
+        let input = "L\n\n11A = (11Z, XXX)\n11Z = (11A, XXX)\n22A = (22B, XXX)\n22B = (22Z, XXX)\n22Z = (22B, XXX)\nXXX = (XXX, XXX)\n";
+        let (steps, graph) = Puzzle08::parse(input);
+        assert_eq!(graph.ghost_traverse(&steps), None);
     }
 }
@@ -0,0 +1,30 @@
+use crate::Puzzle09;
+use common::{generate::InputGenerator, rng::Rng};
+
+const VALUES_PER_LINE: i64 = 21;
+
+impl InputGenerator for Puzzle09 {
+    /// `size` is the number of lines.
+    ///
+    /// Each line samples a polynomial with small integer coefficients, which is what
+    /// guarantees the differences eventually bottom out at zero.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        (0..size)
+            .map(|_| {
+                let coefficients: Vec<i64> =
+                    (0..rng.range(1..7)).map(|_| rng.range_i64(-4..5)).collect();
+                (0..VALUES_PER_LINE)
+                    .map(|x| {
+                        coefficients
+                            .iter()
+                            .rev()
+                            .fold(0, |value, coefficient| value * x + coefficient)
+                            .to_string()
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
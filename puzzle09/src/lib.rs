@@ -1,9 +1,52 @@
+use common::{
+    answer::{Answer, Checked},
+    puzzle::{InputPrinter, Puzzle, Tag},
+};
+use puzzle09a::Puzzle09a;
+use puzzle09b::Puzzle09b;
+
+pub mod generator;
 pub mod parser;
 pub mod puzzle09a;
 pub mod puzzle09b;
 
+pub struct Puzzle09 {}
+
+impl Puzzle for Puzzle09 {
+    type Input<'a> = Vec<DiscreteFn>;
+    type PartA = Puzzle09a;
+    type PartB = Puzzle09b;
+
+    fn name() -> &'static str {
+        "09"
+    }
+
+    fn title() -> &'static str {
+        "Mirage Maintenance"
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Math]
+    }
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input.lines().map(parser::parse_line).collect()
+    }
+}
+
+impl InputPrinter for Puzzle09 {
+    fn print(input: &Self::Input<'_>) -> String {
+        input
+            .iter()
+            .map(DiscreteFn::to_string)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
-pub struct DiscreteFn(Vec<i32>);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiscreteFn(Vec<i64>);
 
 impl DiscreteFn {
     pub fn difference(&self) -> DiscreteFn {
@@ -14,7 +57,7 @@ impl DiscreteFn {
                 let [v1, v2] = window else {
                     panic!("whoops?");
                 };
-                v2 - v1
+                v2.minus(*v1)
             })
             .collect();
         DiscreteFn(differences)
@@ -35,16 +78,20 @@ impl DiscreteFn {
         differences
     }
 
-    pub fn extrapolate_forwards(&self) -> i32 {
+    pub fn extrapolate_forwards(&self) -> Answer {
         self.all_differences()
             .iter()
-            .rfold(0, |value, func| value + func.0.last().unwrap())
+            .rfold(Answer::ZERO, |value, func| {
+                value + Answer::from(*func.0.last().unwrap())
+            })
     }
 
-    pub fn extrapolate_backwards(&self) -> i32 {
+    pub fn extrapolate_backwards(&self) -> Answer {
         self.all_differences()
             .iter()
-            .rfold(0, |value, func| func.0.first().unwrap() - value)
+            .rfold(Answer::ZERO, |value, func| {
+                Answer::from(*func.0.first().unwrap()) - value
+            })
     }
 }
 
@@ -86,24 +133,24 @@ mod tests {
     #[test]
     fn test_extrapolate_forwards() {
         let func = DiscreteFn(vec![0, 3, 6, 9, 12, 15]);
-        assert_eq!(func.extrapolate_forwards(), 18);
+        assert_eq!(func.extrapolate_forwards(), Answer::from(18));
 
         let func = DiscreteFn(vec![1, 3, 6, 10, 15, 21]);
-        assert_eq!(func.extrapolate_forwards(), 28);
+        assert_eq!(func.extrapolate_forwards(), Answer::from(28));
 
         let func = DiscreteFn(vec![10, 13, 16, 21, 30, 45]);
-        assert_eq!(func.extrapolate_forwards(), 68);
+        assert_eq!(func.extrapolate_forwards(), Answer::from(68));
     }
 
     #[test]
     fn test_extrapolate_backwards() {
         let func = DiscreteFn(vec![0, 3, 6, 9, 12, 15]);
-        assert_eq!(func.extrapolate_backwards(), -3);
+        assert_eq!(func.extrapolate_backwards(), Answer::from(-3));
 
         let func = DiscreteFn(vec![1, 3, 6, 10, 15, 21]);
-        assert_eq!(func.extrapolate_backwards(), 0);
+        assert_eq!(func.extrapolate_backwards(), Answer::from(0));
 
         let func = DiscreteFn(vec![10, 13, 16, 21, 30, 45]);
-        assert_eq!(func.extrapolate_backwards(), 5);
+        assert_eq!(func.extrapolate_backwards(), Answer::from(5));
     }
 }
@@ -1,4 +1,5 @@
 use crate::DiscreteFn;
+use std::fmt::Display;
 
 pub fn parse_line(line: &str) -> DiscreteFn {
     let nums = line
@@ -8,6 +9,13 @@ pub fn parse_line(line: &str) -> DiscreteFn {
     DiscreteFn(nums)
 }
 
+impl Display for DiscreteFn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let values: Vec<_> = self.0.iter().map(i64::to_string).collect();
+        write!(f, "{}", values.join(" "))
+    }
+}
+
 #[cfg(test)]
 mod parse_tests {
     use crate::{parser::parse_line, DiscreteFn};
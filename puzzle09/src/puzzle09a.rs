@@ -1,19 +1,16 @@
-use crate::parser::parse_line;
-use common::puzzle::PuzzlePart;
+use crate::DiscreteFn;
+use common::{parallel, puzzle::PuzzlePart};
 
 pub struct Puzzle09a {}
 
 impl PuzzlePart for Puzzle09a {
+    type Input<'a> = Vec<DiscreteFn>;
+
     fn description() -> &'static str {
         "Extrapolate the next values of a bunch of discrete functions, then add the results."
     }
 
-    fn solve(input: &str) -> String {
-        input
-            .lines()
-            .map(parse_line)
-            .map(|func| func.extrapolate_forwards())
-            .sum::<i32>()
-            .to_string()
+    fn solve(input: &Self::Input<'_>) -> String {
+        parallel::sum(input, DiscreteFn::extrapolate_forwards).to_string()
     }
 }
@@ -1,20 +1,17 @@
-use common::puzzle::PuzzlePart;
+use common::{parallel, puzzle::PuzzlePart};
 
-use crate::parser::parse_line;
+use crate::DiscreteFn;
 
 pub struct Puzzle09b {}
 
 impl PuzzlePart for Puzzle09b {
+    type Input<'a> = Vec<DiscreteFn>;
+
     fn description() -> &'static str {
         "Extrapolate the _previous_ values of a bunch of discrete functions, then add the results."
     }
 
-    fn solve(input: &str) -> String {
-        input
-            .lines()
-            .map(parse_line)
-            .map(|func| func.extrapolate_backwards())
-            .sum::<i32>()
-            .to_string()
+    fn solve(input: &Self::Input<'_>) -> String {
+        parallel::sum(input, DiscreteFn::extrapolate_backwards).to_string()
     }
 }
@@ -0,0 +1,121 @@
+use crate::Puzzle10;
+use common::{generate::InputGenerator, rng::Rng};
+
+const PIPES: [char; 7] = ['|', '-', 'L', 'J', '7', 'F', '.'];
+
+impl InputGenerator for Puzzle10 {
+    /// `size` is the width and height of the grid of pipes.
+    ///
+    /// The loop is the outline of a random blob of cells, grown one cell at a time
+    /// without ever enclosing a hole or touching itself at a corner, so that its
+    /// outline is always a single closed loop.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let size = size.max(4);
+        let blob = grow_blob(size - 1, rng);
+
+        // pipes sit on the corners of the blob's cells, and connect along its edges
+        let is_in = |row: usize, col: usize| {
+            (1..size).contains(&row) && (1..size).contains(&col) && blob[row - 1][col - 1]
+        };
+        let is_edge = |(r1, c1): (usize, usize), (r2, c2): (usize, usize)| {
+            // the cells either side of the edge between two neighboring corners,
+            // shifted by one so that cells above/left of the grid are row/col 0
+            let (cell1, cell2) = if r1 == r2 {
+                ((r1, c1.min(c2) + 1), (r1 + 1, c1.min(c2) + 1))
+            } else {
+                ((r1.min(r2) + 1, c1), (r1.min(r2) + 1, c1 + 1))
+            };
+            is_in(cell1.0, cell1.1) != is_in(cell2.0, cell2.1)
+        };
+
+        let mut grid: Vec<Vec<char>> = vec![vec!['.'; size]; size];
+        let mut on_loop = vec![];
+        for (row, line) in grid.iter_mut().enumerate() {
+            for (col, tile) in line.iter_mut().enumerate() {
+                let up = row > 0 && is_edge((row, col), (row - 1, col));
+                let down = row + 1 < size && is_edge((row, col), (row + 1, col));
+                let left = col > 0 && is_edge((row, col), (row, col - 1));
+                let right = col + 1 < size && is_edge((row, col), (row, col + 1));
+
+                *tile = match (up, down, left, right) {
+                    (true, true, false, false) => '|',
+                    (false, false, true, true) => '-',
+                    (true, false, false, true) => 'L',
+                    (true, false, true, false) => 'J',
+                    (false, true, true, false) => '7',
+                    (false, true, false, true) => 'F',
+                    _ => *rng.choose(&PIPES),
+                };
+                if up || down || left || right {
+                    on_loop.push((row, col));
+                }
+            }
+        }
+
+        // clear out the start's other neighbors, so that only the loop connects to it
+        let (start_row, start_col) = *rng.choose(&on_loop);
+        for (row, col) in [
+            (start_row - 1, start_col),
+            (start_row + 1, start_col),
+            (start_row, start_col - 1),
+            (start_row, start_col + 1),
+        ] {
+            if !on_loop.contains(&(row, col)) {
+                grid[row][col] = '.';
+            }
+        }
+        grid[start_row][start_col] = 'S';
+
+        grid.into_iter()
+            .map(|line| line.into_iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Grows a blob of cells in the middle of a `size` by `size` area, leaving a border
+/// of one cell around it so that its outline stays clear of the edges of the grid.
+fn grow_blob(size: usize, rng: &mut Rng) -> Vec<Vec<bool>> {
+    let mut blob = vec![vec![false; size]; size];
+    blob[size / 2][size / 2] = true;
+
+    let target = (size - 2) * (size - 2) / 3;
+    let mut cells = 1;
+    for _ in 0..target * 20 {
+        if cells >= target {
+            break;
+        }
+        let (row, col) = (rng.range(1..size - 1), rng.range(1..size - 1));
+        if !blob[row][col] && can_add(&blob, row, col) {
+            blob[row][col] = true;
+            cells += 1;
+        }
+    }
+    blob
+}
+
+/// A cell can be added if its neighbors in the blob form a single unbroken run when
+/// walking around it, starting or ending next to it rather than diagonally from it.
+/// Anything else would either enclose a hole or pinch the outline at a corner.
+fn can_add(blob: &[Vec<bool>], row: usize, col: usize) -> bool {
+    let ring = [
+        blob[row - 1][col],
+        blob[row - 1][col + 1],
+        blob[row][col + 1],
+        blob[row + 1][col + 1],
+        blob[row + 1][col],
+        blob[row + 1][col - 1],
+        blob[row][col - 1],
+        blob[row - 1][col - 1],
+    ];
+
+    let runs = (0..ring.len())
+        .filter(|idx| ring[*idx] && !ring[(idx + ring.len() - 1) % ring.len()])
+        .count();
+    let touches_side = ring.iter().step_by(2).any(|cell| *cell);
+    let pinches = (1..ring.len())
+        .step_by(2)
+        .any(|idx| ring[idx] && !ring[idx - 1] && !ring[(idx + 1) % ring.len()]);
+
+    runs == 1 && touches_side && !pinches
+}
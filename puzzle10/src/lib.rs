@@ -1,7 +1,48 @@
+pub use common::geom::Direction;
+use common::{
+    grid::{Grid, Point},
+    puzzle::{InputPrinter, Puzzle, Tag},
+};
+use puzzle10a::Puzzle10a;
+use puzzle10b::Puzzle10b;
+use std::fmt::Display;
+
+pub mod generator;
 pub mod puzzle10a;
 pub mod puzzle10b;
 
+pub struct Puzzle10 {}
+
+impl Puzzle for Puzzle10 {
+    type Input<'a> = PipeGrid;
+    type PartA = Puzzle10a;
+    type PartB = Puzzle10b;
+
+    fn name() -> &'static str {
+        "10"
+    }
+
+    fn title() -> &'static str {
+        "Pipe Maze"
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Grid, Tag::Graph]
+    }
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        PipeGrid::from(input)
+    }
+}
+
+impl InputPrinter for Puzzle10 {
+    fn print(input: &Self::Input<'_>) -> String {
+        input.to_string()
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PipeTile {
     Vertical,
     Horizontal,
@@ -13,59 +54,38 @@ pub enum PipeTile {
     Nothing,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-pub enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-#[derive(Debug, PartialEq, Eq)]
-pub struct PipeGrid(Vec<Vec<PipeTile>>);
-
-type GridPoint = (usize, usize);
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PipeGrid(Grid<PipeTile>);
 
 impl PipeGrid {
-    pub fn start(&self) -> (GridPoint, Direction) {
-        let start = self
-            .0
-            .iter()
-            .enumerate()
-            .find_map(|(row_idx, row)| {
-                row.iter()
-                    .position(|pipe| pipe == &PipeTile::Start)
-                    .map(|col_idx| (row_idx, col_idx))
-            })
-            .unwrap();
+    pub fn start(&self) -> (Point, Direction) {
+        let start = self.0.position(|pipe| pipe == &PipeTile::Start).unwrap();
 
         // probe the grid to find a valid starting direction
         // can we go left?
-        if start.0 > 0 {
-            let left_neighbor = self.get((start.0, start.1 - 1));
-            if let PipeTile::Horizontal | PipeTile::TopRight | PipeTile::BottomRight = left_neighbor
-            {
-                return (start, Direction::Left);
-            }
+        if let Some(PipeTile::Horizontal | PipeTile::TopRight | PipeTile::BottomRight) =
+            self.neighbor(start, Direction::Left)
+        {
+            return (start, Direction::Left);
         }
         // can we go up?
-        if start.1 > 0 {
-            let above_neighbor = self.get((start.0 - 1, start.1));
-            if let PipeTile::Vertical | PipeTile::BottomRight | PipeTile::BottomLeft =
-                above_neighbor
-            {
-                return (start, Direction::Up);
-            }
+        if let Some(PipeTile::Vertical | PipeTile::BottomRight | PipeTile::BottomLeft) =
+            self.neighbor(start, Direction::Up)
+        {
+            return (start, Direction::Up);
         }
         // okay well we can definitely go right then (two of the four directions must be valid)
         (start, Direction::Right)
     }
 
-    fn get(&self, point: GridPoint) -> &PipeTile {
-        &self.0[point.0][point.1]
+    fn neighbor(&self, point: Point, direction: Direction) -> Option<&PipeTile> {
+        self.0
+            .step(point, direction)
+            .map(|neighbor| &self.0[neighbor])
     }
 
-    fn find_loop(&mut self) -> Vec<GridPoint> {
+    fn find_loop(&mut self) -> Vec<Point> {
         // find the start location
         let (start, start_direction) = self.start();
 
@@ -76,18 +96,16 @@ impl PipeGrid {
 
         while points.is_empty() || current != start {
             // walk in the direction
-            current = match direction {
-                Direction::Up => (current.0 - 1, current.1),
-                Direction::Down => (current.0 + 1, current.1),
-                Direction::Left => (current.0, current.1 - 1),
-                Direction::Right => (current.0, current.1 + 1),
-            };
+            current = self
+                .0
+                .step(current, direction)
+                .unwrap_or_else(|| panic!("the loop left the grid at {current}"));
 
             // add the point to the list
             points.push(current);
 
             // look at the tile to figure out a new direction
-            let tile = self.get(current);
+            let tile = &self.0[current];
             direction = match (direction, tile) {
                 (Direction::Up, PipeTile::Vertical) => Direction::Up,
                 (Direction::Up, PipeTile::BottomRight) => Direction::Right,
@@ -133,19 +151,19 @@ impl PipeGrid {
 
             _ => panic!("invalid state while computing start tile"),
         };
-        self.0[start.0][start.1] = start_tile;
+        self.0[start] = start_tile;
 
         points
     }
 
-    pub fn count_in_loop(&self, pipe_loop: &[(usize, usize)]) -> u32 {
+    pub fn count_in_loop(&self, pipe_loop: &[Point]) -> u32 {
         let mut count = 0;
 
-        self.0.iter().enumerate().for_each(|(row_idx, row)| {
+        self.0.rows().enumerate().for_each(|(row_idx, row)| {
             let mut count_status = State::Not;
 
             row.iter().enumerate().for_each(|(col_idx, tile)| {
-                if pipe_loop.contains(&(row_idx, col_idx)) {
+                if pipe_loop.contains(&Point::new(row_idx, col_idx)) {
                     // we're scanning left to right, so every time
                     // we hit a tile in the loop, we either start/stop counting (if it's vertical)
                     // or start/stop considering counting if it's TopRight or BottomRight
@@ -202,26 +220,46 @@ impl State {
     }
 }
 
+impl From<char> for PipeTile {
+    fn from(c: char) -> Self {
+        match c {
+            '|' => PipeTile::Vertical,
+            '-' => PipeTile::Horizontal,
+            'L' => PipeTile::TopRight,
+            'J' => PipeTile::TopLeft,
+            '7' => PipeTile::BottomLeft,
+            'F' => PipeTile::BottomRight,
+            'S' => PipeTile::Start,
+            '.' => PipeTile::Nothing,
+            _ => panic!("unrecognized character"),
+        }
+    }
+}
+
+impl Display for PipeTile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            PipeTile::Vertical => '|',
+            PipeTile::Horizontal => '-',
+            PipeTile::TopRight => 'L',
+            PipeTile::TopLeft => 'J',
+            PipeTile::BottomLeft => '7',
+            PipeTile::BottomRight => 'F',
+            PipeTile::Start => 'S',
+            PipeTile::Nothing => '.',
+        };
+        write!(f, "{c}")
+    }
+}
+
 impl From<&str> for PipeGrid {
     fn from(input: &str) -> Self {
-        let pipes = input
-            .lines()
-            .map(|line| {
-                line.chars()
-                    .map(|c| match c {
-                        '|' => PipeTile::Vertical,
-                        '-' => PipeTile::Horizontal,
-                        'L' => PipeTile::TopRight,
-                        'J' => PipeTile::TopLeft,
-                        '7' => PipeTile::BottomLeft,
-                        'F' => PipeTile::BottomRight,
-                        'S' => PipeTile::Start,
-                        '.' => PipeTile::Nothing,
-                        _ => panic!("unrecognized character"),
-                    })
-                    .collect()
-            })
-            .collect();
-        Self(pipes)
+        Self(Grid::parse(input, PipeTile::from))
+    }
+}
+
+impl Display for PipeGrid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
     }
 }
@@ -5,12 +5,15 @@ use crate::PipeGrid;
 pub struct Puzzle10a {}
 
 impl PuzzlePart for Puzzle10a {
+    type Input<'a> = PipeGrid;
+
     fn description() -> &'static str {
         "Find the distance between the start and furthest point along a pipe loop."
     }
 
-    fn solve(input: &str) -> String {
-        let mut grid = PipeGrid::from(input);
+    fn solve(input: &Self::Input<'_>) -> String {
+        // finding the loop replaces the start tile, so work on a copy
+        let mut grid = input.clone();
         let pipe_loop = grid.find_loop();
 
         // the loop always has even length, and the point furthest from the
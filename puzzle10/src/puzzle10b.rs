@@ -5,12 +5,15 @@ use crate::PipeGrid;
 pub struct Puzzle10b {}
 
 impl PuzzlePart for Puzzle10b {
+    type Input<'a> = PipeGrid;
+
     fn description() -> &'static str {
         "Find the number of tiles enclosed by a loop of pipes."
     }
 
-    fn solve(input: &str) -> String {
-        let mut grid = PipeGrid::from(input);
+    fn solve(input: &Self::Input<'_>) -> String {
+        // finding the loop replaces the start tile, so work on a copy
+        let mut grid = input.clone();
         let pipe_loop = grid.find_loop();
 
         grid.count_in_loop(&pipe_loop).to_string()
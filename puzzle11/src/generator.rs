@@ -0,0 +1,17 @@
+use crate::Puzzle11;
+use common::{generate::InputGenerator, rng::Rng};
+
+impl InputGenerator for Puzzle11 {
+    /// `size` is the width and height of the image.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        // sparse enough that some rows and columns are left empty to expand
+        (0..size)
+            .map(|_| {
+                (0..size)
+                    .map(|_| if rng.chance(0.04) { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
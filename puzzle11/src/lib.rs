@@ -1,7 +1,47 @@
+use common::{
+    grid::{Grid, Point},
+    puzzle::{InputPrinter, Puzzle, Tag},
+};
+use puzzle11a::Puzzle11a;
+use puzzle11b::Puzzle11b;
+use std::fmt::Display;
+
+pub mod generator;
 pub mod puzzle11a;
 pub mod puzzle11b;
 
-#[derive(Debug)]
+pub struct Puzzle11 {}
+
+impl Puzzle for Puzzle11 {
+    type Input<'a> = Universe;
+    type PartA = Puzzle11a;
+    type PartB = Puzzle11b;
+
+    fn name() -> &'static str {
+        "11"
+    }
+
+    fn title() -> &'static str {
+        "Cosmic Expansion"
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Grid, Tag::Geometry]
+    }
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        Universe::from(input)
+    }
+}
+
+impl InputPrinter for Puzzle11 {
+    fn print(input: &Self::Input<'_>) -> String {
+        input.to_string()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Universe {
     galaxies: Vec<Galaxy>,
     empty_rows: Vec<usize>,
@@ -9,38 +49,29 @@ pub struct Universe {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Galaxy(usize, usize);
 
 impl From<&str> for Universe {
     fn from(input: &str) -> Self {
-        let galaxies: Vec<Galaxy> = input
-            .lines()
-            .enumerate()
-            .flat_map(|(row_idx, row)| {
-                row.chars()
-                    .enumerate()
-                    .filter_map(|(col_idx, c)| {
-                        if c == '#' {
-                            Some(Galaxy(row_idx, col_idx))
-                        } else {
-                            None
-                        }
-                    })
-                    .collect::<Vec<Galaxy>>()
-            })
-            .collect();
-
-        let filled_rows: Vec<_> = galaxies.iter().map(|Galaxy(row_idx, _)| row_idx).collect();
-        let filled_cols: Vec<_> = galaxies.iter().map(|Galaxy(_, col_idx)| col_idx).collect();
+        let image = Grid::parse(input, |c| c == '#');
 
-        let num_cols = input.lines().count();
-        let num_rows = input.lines().next().unwrap().len();
+        let galaxies = image
+            .indexed()
+            .filter(|(_, is_galaxy)| **is_galaxy)
+            .map(|(point, _)| Galaxy(point.row, point.col))
+            .collect();
 
-        let empty_rows: Vec<_> = (0..num_cols)
-            .filter(|row_idx| !filled_rows.contains(&row_idx))
+        let empty_rows = image
+            .rows()
+            .enumerate()
+            .filter(|(_, row)| !row.contains(&true))
+            .map(|(row_idx, _)| row_idx)
             .collect();
-        let empty_cols: Vec<_> = (0..num_rows)
-            .filter(|col_idx| !filled_cols.contains(&col_idx))
+        let empty_cols = image
+            .columns()
+            .enumerate()
+            .filter_map(|(col_idx, mut col)| (!col.any(|is_galaxy| *is_galaxy)).then_some(col_idx))
             .collect();
 
         Self {
@@ -51,6 +82,33 @@ impl From<&str> for Universe {
     }
 }
 
+impl Display for Universe {
+    /// Empty rows and columns are only kept by their indices, but since every row and
+    /// column is either empty or has a galaxy in it, the size of the image can be recovered.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let height = self
+            .galaxies
+            .iter()
+            .map(|Galaxy(row, _)| row)
+            .chain(&self.empty_rows)
+            .max()
+            .map_or(0, |max| max + 1);
+        let width = self
+            .galaxies
+            .iter()
+            .map(|Galaxy(_, col)| col)
+            .chain(&self.empty_cols)
+            .max()
+            .map_or(0, |max| max + 1);
+
+        let mut image = Grid::filled(width, height, '.');
+        for Galaxy(row_idx, col_idx) in &self.galaxies {
+            image[Point::new(*row_idx, *col_idx)] = '#';
+        }
+        write!(f, "{image}")
+    }
+}
+
 impl Universe {
     /// Enumerates all (n choose 2) pairs of galaxies in a `Universe`.
     pub fn galaxy_pairs(&self) -> Vec<(&Galaxy, &Galaxy)> {
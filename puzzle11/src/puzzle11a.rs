@@ -4,13 +4,13 @@ use common::puzzle::PuzzlePart;
 pub struct Puzzle11a {}
 
 impl PuzzlePart for Puzzle11a {
+    type Input<'a> = Universe;
+
     fn description() -> &'static str {
         "Find the pairwise distances between galaxies in an expanded universe."
     }
 
-    fn solve(input: &str) -> String {
-        let universe = Universe::from(input);
-
+    fn solve(universe: &Self::Input<'_>) -> String {
         universe
             .galaxy_pairs()
             .iter()
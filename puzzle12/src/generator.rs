@@ -0,0 +1,35 @@
+use crate::Puzzle12;
+use common::{generate::InputGenerator, rng::Rng};
+
+impl InputGenerator for Puzzle12 {
+    /// `size` is the number of rows of springs.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        (0..size)
+            .map(|_| spring_line(rng))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Lays out a row of springs, reads the damaged groups off of it and only then
+/// hides some of the springs, so that there's always at least one arrangement.
+fn spring_line(rng: &mut Rng) -> String {
+    let length = rng.range(1..21);
+    let mut springs: Vec<char> = (0..length)
+        .map(|_| if rng.chance(0.5) { '#' } else { '.' })
+        .collect();
+    let damaged_idx = rng.range(0..length);
+    springs[damaged_idx] = '#';
+
+    let groups: Vec<String> = springs
+        .split(|c| *c == '.')
+        .filter(|group| !group.is_empty())
+        .map(|group| group.len().to_string())
+        .collect();
+
+    let row: String = springs
+        .into_iter()
+        .map(|c| if rng.chance(0.5) { '?' } else { c })
+        .collect();
+    format!("{row} {}", groups.join(","))
+}
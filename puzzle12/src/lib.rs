@@ -1,8 +1,51 @@
+use common::{
+    memo::{Memo, Stats},
+    puzzle::{InputPrinter, Puzzle, Tag},
+};
+use puzzle12a::Puzzle12a;
+use puzzle12b::Puzzle12b;
+
+pub mod generator;
 pub mod parser;
 pub mod puzzle12a;
 pub mod puzzle12b;
 
+pub struct Puzzle12 {}
+
+impl Puzzle for Puzzle12 {
+    type Input<'a> = Vec<Schematic>;
+    type PartA = Puzzle12a;
+    type PartB = Puzzle12b;
+
+    fn name() -> &'static str {
+        "12"
+    }
+
+    fn title() -> &'static str {
+        "Hot Springs"
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Dp]
+    }
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input.lines().map(Schematic::from).collect()
+    }
+}
+
+impl InputPrinter for Puzzle12 {
+    fn print(input: &Self::Input<'_>) -> String {
+        input
+            .iter()
+            .map(Schematic::to_string)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SpringState {
     Damaged,
     Operational,
@@ -20,22 +63,43 @@ impl SpringState {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SpringRow(Vec<SpringState>);
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DamagedGroups(Vec<usize>);
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Schematic(SpringRow, DamagedGroups);
 
+/// Arrangements already counted, keyed by how many springs and damaged groups have been
+/// matched so far.
+type ArrangementMemo = Memo<(usize, usize), u64>;
+
 impl Schematic {
-    pub fn count_arrangements(&self, memory: &mut Vec<(Schematic, u64)>) -> u64 {
-        // did we already solve this problem before?
-        if let Some((_, num)) = memory.iter().find(|(schematic, _)| self == schematic) {
-            return *num;
-        }
+    pub fn count_arrangements(&self) -> u64 {
+        self.count_arrangements_with_stats().0
+    }
+
+    /// Counts the arrangements, along with how well the memo did at avoiding recounting.
+    pub fn count_arrangements_with_stats(&self) -> (u64, Stats) {
+        let mut memo = Memo::new();
+        let arrangements = self.count_arrangements_from(0, 0, &mut memo);
+        (arrangements, memo.stats())
+    }
 
+    /// Counts the arrangements of the springs from `spring_start` on, matching the damaged
+    /// groups from `group_start` on.
+    fn count_arrangements_from(
+        &self,
+        spring_start: usize,
+        group_start: usize,
+        memo: &mut ArrangementMemo,
+    ) -> u64 {
         let Schematic(SpringRow(springs), DamagedGroups(groups)) = self;
+        let (springs, groups) = (&springs[spring_start..], &groups[group_start..]);
 
         // if there are no damaged groups, and all of the springs are not explicitly damaged,
         // it means we successfully matched all of them, so return 1
@@ -55,61 +119,99 @@ impl Schematic {
             return 0;
         }
 
-        let mut arrangements = 0;
-
-        // walk the row until we find `first_group` consecutive damaged/unknown springs
-        for idx in 0..springs.len() - first_group + 1 {
-            if springs[idx..idx + first_group]
-                .iter()
-                .all(SpringState::could_be_damaged)
-            {
-                // now check the springs before and after this chunk -- if they exist,
-                // make sure they _could_ be operational
-                // (that is, make sure we've matched on a whole damaged group, rather than a subset of one)
-                let prev_spring_op = idx == 0 || springs[idx - 1].could_be_operational();
-                let next_spring_op = springs.len() == idx + *first_group
-                    || springs[idx + first_group].could_be_operational();
-
-                if prev_spring_op && next_spring_op {
-                    // chop off the bits we've already processed and recurse
-                    let next_spring_slice_start = springs.len().min(idx + first_group + 1);
-                    arrangements += Schematic(
-                        SpringRow(springs[next_spring_slice_start..].into()),
-                        DamagedGroups(groups[1..].into()),
-                    )
-                    .count_arrangements(memory);
+        // did we already solve this problem before? if not, memoize me captain!
+        memo.get_or_insert_with((spring_start, group_start), |memo| {
+            let mut arrangements = 0;
+
+            // walk the row until we find `first_group` consecutive damaged/unknown springs
+            for idx in 0..springs.len() - first_group + 1 {
+                if springs[idx..idx + first_group]
+                    .iter()
+                    .all(SpringState::could_be_damaged)
+                {
+                    // now check the springs before and after this chunk -- if they exist,
+                    // make sure they _could_ be operational
+                    // (that is, make sure we've matched on a whole damaged group, rather than a subset of one)
+                    let prev_spring_op = idx == 0 || springs[idx - 1].could_be_operational();
+                    let next_spring_op = springs.len() == idx + *first_group
+                        || springs[idx + first_group].could_be_operational();
+
+                    if prev_spring_op && next_spring_op {
+                        // skip past the bits we've already processed and recurse
+                        let next_spring_start = springs.len().min(idx + first_group + 1);
+                        arrangements += self.count_arrangements_from(
+                            spring_start + next_spring_start,
+                            group_start + 1,
+                            memo,
+                        );
+                    }
                 }
-            }
 
-            // if the first spring of the current window is damaged, we have to match on it,
-            // so we shouldn't check the rest of the windows
-            if springs[idx] == SpringState::Damaged {
-                break;
+                // if the first spring of the current window is damaged, we have to match on it,
+                // so we shouldn't check the rest of the windows
+                if springs[idx] == SpringState::Damaged {
+                    break;
+                }
             }
-        }
 
-        // memoize me captain!
-        memory.push((self.clone(), arrangements));
-        arrangements
+            arrangements
+        })
     }
 
     /// Replaces a schematic's spring row and damaged groups with five copies of themselves
     /// (The copies of the spring row get Unknown springs placed in between them.)
     pub fn unfold(&self) -> Self {
+        self.unfold_times(5)
+    }
+
+    /// Like [`Schematic::unfold`], but with any (positive) number of copies.
+    pub fn unfold_times(&self, copies: usize) -> Self {
         let mut springs = self.0 .0.clone();
         springs.push(SpringState::Unknown);
         let len = springs.len();
-        let spring_row = SpringRow(springs.into_iter().cycle().take(len * 5 - 1).collect());
+        let spring_row = SpringRow(springs.into_iter().cycle().take(len * copies - 1).collect());
 
         let groups = self.1 .0.clone();
         let damageds_groups = DamagedGroups(
             groups
                 .into_iter()
                 .cycle()
-                .take(self.1 .0.len() * 5)
+                .take(self.1 .0.len() * copies)
                 .collect(),
         );
 
         Self(spring_row, damageds_groups)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_arrangements() {
+        let schematics = Puzzle12::parse(include_str!("../data/example"));
+        let counts: Vec<_> = schematics
+            .iter()
+            .map(Schematic::count_arrangements)
+            .collect();
+        assert_eq!(counts, vec![1, 4, 1, 1, 4, 10]);
+
+        let counts: Vec<_> = schematics
+            .iter()
+            .map(|schematic| schematic.unfold().count_arrangements())
+            .collect();
+        assert_eq!(counts, vec![1, 16384, 1, 16, 2500, 506250]);
+    }
+
+    #[test]
+    fn test_memo_stays_small() {
+        let schematic = Schematic::from("?#?#?#?#?#?#?#? 1,3,1,6").unfold_times(200);
+        let (arrangements, stats) = schematic.count_arrangements_with_stats();
+        assert_eq!(arrangements, 1);
+
+        // there's at most one entry for each pair of starting points
+        let Schematic(SpringRow(springs), DamagedGroups(groups)) = &schematic;
+        assert!(stats.entries <= (springs.len() + 1) * (groups.len() + 1));
+    }
+}
@@ -1,4 +1,5 @@
 use crate::{DamagedGroups, Schematic, SpringRow, SpringState};
+use std::fmt::Display;
 
 impl From<&str> for SpringRow {
     fn from(input: &str) -> Self {
@@ -32,6 +33,36 @@ impl From<&str> for Schematic {
     }
 }
 
+impl Display for SpringState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            SpringState::Operational => '.',
+            SpringState::Damaged => '#',
+            SpringState::Unknown => '?',
+        };
+        write!(f, "{c}")
+    }
+}
+
+impl Display for SpringRow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.iter().try_for_each(|state| write!(f, "{state}"))
+    }
+}
+
+impl Display for DamagedGroups {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let groups: Vec<_> = self.0.iter().map(usize::to_string).collect();
+        write!(f, "{}", groups.join(","))
+    }
+}
+
+impl Display for Schematic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.0, self.1)
+    }
+}
+
 #[cfg(test)]
 mod parse_tests {
     use super::*;
@@ -90,4 +121,10 @@ mod parse_tests {
             )
         )
     }
+
+    #[test]
+    fn display_schematic() {
+        let input = "????.#...#... 4,1,1";
+        assert_eq!(Schematic::from(input).to_string(), input);
+    }
 }
@@ -0,0 +1,74 @@
+use crate::{Pattern, Puzzle13};
+use common::{generate::InputGenerator, rng::Rng};
+
+impl InputGenerator for Puzzle13 {
+    /// `size` is the number of patterns.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        (0..size)
+            .map(|_| pattern(rng))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+/// A pattern with exactly one line of reflection, and exactly one other line that
+/// would be a reflection if not for a single smudge.
+fn pattern(rng: &mut Rng) -> String {
+    loop {
+        let rows = pattern_rows(rng);
+        let pattern = Pattern::from(rows.join("\n").as_str());
+        let lines_with = |smudges: u32| {
+            (0..pattern.width() - 1)
+                .filter(|idx| pattern.is_symmetric_across_vert(*idx, smudges))
+                .count()
+                + (0..pattern.height() - 1)
+                    .filter(|idx| pattern.is_symmetric_across_horiz(*idx, smudges))
+                    .count()
+        };
+
+        // the construction makes this very likely, but the random parts of the
+        // pattern can occasionally add more reflections by accident
+        if lines_with(0) == 1 && lines_with(1) == 1 {
+            return rows.join("\n");
+        }
+    }
+}
+
+/// Builds a pattern reflected across a line somewhere past its middle, whose
+/// first two columns are a reflection apart from a single smudge.
+fn pattern_rows(rng: &mut Rng) -> Vec<String> {
+    let height = rng.range(5..18);
+    let reach = rng.range(1..6);
+    let width = 2 + 2 * reach + rng.range(0..6);
+    let axis = width - reach;
+
+    let mut grid: Vec<Vec<bool>> = (0..height)
+        .map(|_| (0..width).map(|_| rng.chance(0.5)).collect())
+        .collect();
+    for row in grid.iter_mut() {
+        for offset in 0..reach {
+            row[axis + offset] = row[axis - offset - 1];
+        }
+        row[1] = row[0];
+    }
+    let smudge_row = rng.range(0..height);
+    grid[smudge_row][1] = !grid[smudge_row][1];
+
+    // shuffle things around so the lines don't always end up in the same places
+    if rng.chance(0.5) {
+        grid.iter_mut().for_each(|row| row.reverse());
+    }
+    if rng.chance(0.5) {
+        grid = (0..width)
+            .map(|col| grid.iter().map(|row| row[col]).collect())
+            .collect();
+    }
+
+    grid.into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|rock| if rock { '#' } else { '.' })
+                .collect()
+        })
+        .collect()
+}
@@ -1,7 +1,51 @@
+use common::{
+    grid::Grid,
+    puzzle::{InputPrinter, Puzzle, Tag},
+};
+use puzzle13a::Puzzle13a;
+use puzzle13b::Puzzle13b;
+use std::fmt::Display;
+
+pub mod generator;
 pub mod puzzle13a;
 pub mod puzzle13b;
 
+pub struct Puzzle13 {}
+
+impl Puzzle for Puzzle13 {
+    type Input<'a> = Vec<Pattern>;
+    type PartA = Puzzle13a;
+    type PartB = Puzzle13b;
+
+    fn name() -> &'static str {
+        "13"
+    }
+
+    fn title() -> &'static str {
+        "Point of Incidence"
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Grid]
+    }
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input.split("\n\n").map(Pattern::from).collect()
+    }
+}
+
+impl InputPrinter for Puzzle13 {
+    fn print(input: &Self::Input<'_>) -> String {
+        input
+            .iter()
+            .map(Pattern::to_string)
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Element {
     Ash,
     Rock,
@@ -17,31 +61,46 @@ impl From<char> for Element {
     }
 }
 
+impl Display for Element {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Element::Ash => write!(f, "."),
+            Element::Rock => write!(f, "#"),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
-pub struct Pattern(Vec<Vec<Element>>);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pattern(Grid<Element>);
 
 impl From<&str> for Pattern {
     fn from(input: &str) -> Self {
-        Self(
-            input
-                .lines()
-                .map(|line| line.chars().map(Element::from).collect())
-                .collect(),
-        )
+        Self(Grid::parse(input, Element::from))
+    }
+}
+
+impl Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
     }
 }
 
+/// Counts the places where two lines of a pattern differ.
+fn differences<'a>(
+    line1: impl Iterator<Item = &'a Element>,
+    line2: impl Iterator<Item = &'a Element>,
+) -> u32 {
+    line1.zip(line2).filter(|(el1, el2)| el1 != el2).count() as u32
+}
+
 impl Pattern {
     fn height(&self) -> usize {
-        self.0.len()
+        self.0.height()
     }
 
     fn width(&self) -> usize {
-        self.0[0].len()
-    }
-
-    fn get(&self, row_idx: usize, col_idx: usize) -> &Element {
-        &self.0[row_idx][col_idx]
+        self.0.width()
     }
 
     /// Checks if a pattern is symmetric across the _vertical_ line
@@ -54,17 +113,12 @@ impl Pattern {
         let mut smudges = 0;
 
         while offset <= axis && axis + offset + 1 < self.width() {
-            let left_col_idx = axis - offset;
-            let right_col_idx = axis + offset + 1;
-
-            for row_idx in 0..self.height() {
-                if self.get(row_idx, right_col_idx) != self.get(row_idx, left_col_idx) {
-                    if smudges < smudge_target {
-                        smudges += 1
-                    } else {
-                        return false;
-                    }
-                }
+            let left_col = self.0.column(axis - offset);
+            let right_col = self.0.column(axis + offset + 1);
+
+            smudges += differences(left_col, right_col);
+            if smudges > smudge_target {
+                return false;
             }
 
             offset += 1
@@ -81,17 +135,12 @@ impl Pattern {
         let mut smudges = 0;
 
         while offset <= axis && axis + offset + 1 < self.height() {
-            let left_row_idx = axis - offset;
-            let right_row_idx = axis + offset + 1;
-
-            for col_idx in 0..self.width() {
-                if self.get(right_row_idx, col_idx) != self.get(left_row_idx, col_idx) {
-                    if smudges < smudge_target {
-                        smudges += 1
-                    } else {
-                        return false;
-                    }
-                }
+            let top_row = self.0.row(axis - offset);
+            let bottom_row = self.0.row(axis + offset + 1);
+
+            smudges += differences(top_row.iter(), bottom_row.iter());
+            if smudges > smudge_target {
+                return false;
             }
 
             offset += 1
@@ -0,0 +1,20 @@
+use crate::Puzzle14;
+use common::{generate::InputGenerator, rng::Rng};
+
+impl InputGenerator for Puzzle14 {
+    /// `size` is the width and height of the platform.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        (0..size)
+            .map(|_| {
+                (0..size)
+                    .map(|_| match rng.below(10) {
+                        0 | 1 => 'O',
+                        2 => '#',
+                        _ => '.',
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
@@ -1,58 +1,87 @@
+pub use common::geom::Direction;
+use common::{
+    cycle,
+    grid::Grid,
+    puzzle::{InputPrinter, Puzzle, Tag},
+};
+use puzzle14a::Puzzle14a;
+use puzzle14b::Puzzle14b;
+
+pub mod generator;
 pub mod parser;
 pub mod puzzle14a;
 pub mod puzzle14b;
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct Puzzle14 {}
+
+impl Puzzle for Puzzle14 {
+    type Input<'a> = Platform;
+    type PartA = Puzzle14a;
+    type PartB = Puzzle14b;
+
+    fn name() -> &'static str {
+        "14"
+    }
+
+    fn title() -> &'static str {
+        "Parabolic Reflector Dish"
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Grid, Tag::Simulation, Tag::Cycles]
+    }
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        Platform::from(input)
+    }
+}
+
+impl InputPrinter for Puzzle14 {
+    fn print(input: &Self::Input<'_>) -> String {
+        input.to_string()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 enum Element {
     RollingRock,
     StationaryRock,
     Nothing,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Platform(PlatformRep);
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 enum PlatformRep {
     Rows(PlatformRows),
     Columns(PlatformColumns),
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
-struct PlatformRows(Vec<Vec<Element>>);
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct PlatformRows(Grid<Element>);
 
-#[derive(Debug, PartialEq, Eq, Clone)]
-struct PlatformColumns(Vec<Vec<Element>>);
-
-fn transpose<T: Clone>(matrix: &Vec<Vec<T>>) -> Vec<Vec<T>> {
-    (0..matrix[0].len())
-        .map(|outer_idx| {
-            (0..matrix.len())
-                .map(|inner_idx| matrix[inner_idx][outer_idx].clone())
-                .collect()
-        })
-        .collect()
-}
+/// The platform transposed, so that each of its columns is a row of the grid.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct PlatformColumns(Grid<Element>);
 
 impl From<&PlatformRows> for PlatformColumns {
     fn from(rows: &PlatformRows) -> Self {
-        Self(transpose(&rows.0))
+        Self(rows.0.transpose())
     }
 }
 
 impl From<&PlatformColumns> for PlatformRows {
     fn from(cols: &PlatformColumns) -> Self {
-        Self(transpose(&cols.0))
+        Self(cols.0.transpose())
     }
 }
 
-pub enum Direction {
-    North,
-    South,
-    East,
-    West,
-}
-
 impl Platform {
     fn swap_rep(&mut self) {
         self.0 = match self.0 {
@@ -68,9 +97,9 @@ impl Platform {
         };
 
         // `RollingRock`s have weight equal to their distance to the bottom row (plus one)
-        let height = rows.0.len();
+        let height = rows.0.height();
         rows.0
-            .iter()
+            .rows()
             .enumerate()
             .map(|(row_idx, row)| {
                 (height - row_idx)
@@ -82,20 +111,40 @@ impl Platform {
             .sum::<usize>()
     }
 
+    /// Tilts the platform north, then west, then south, then east, with north being
+    /// [`Direction::Up`].
+    pub fn spin_cycle(&mut self) {
+        self.tilt(Direction::Up);
+        self.tilt(Direction::Left);
+        self.tilt(Direction::Down);
+        self.tilt(Direction::Right);
+    }
+
+    /// Finds the load on the north edge after the given number of spin cycles by
+    /// finding where the platform starts repeating itself, rather than running them all.
+    pub fn north_load_after(&self, cycles: usize) -> usize {
+        let spun = |platform: &Platform| {
+            let mut platform = platform.clone();
+            platform.spin_cycle();
+            platform
+        };
+        cycle::state_after(self.clone(), spun, cycles).north_load()
+    }
+
     pub fn tilt(&mut self, direction: Direction) {
         // use the representation most amenable to tilting in the given direction
         match &mut self.0 {
             PlatformRep::Columns(cols) => match direction {
-                Direction::North => cols.tilt_north(),
-                Direction::South => cols.tilt_south(),
+                Direction::Up => cols.tilt_north(),
+                Direction::Down => cols.tilt_south(),
                 direction => {
                     self.swap_rep();
                     self.tilt(direction);
                 }
             },
             PlatformRep::Rows(rows) => match direction {
-                Direction::East => rows.tilt_east(),
-                Direction::West => rows.tilt_west(),
+                Direction::Right => rows.tilt_east(),
+                Direction::Left => rows.tilt_west(),
                 direction => {
                     self.swap_rep();
                     self.tilt(direction);
@@ -110,7 +159,7 @@ impl PlatformColumns {
         // operate on each column individually
         // break it up into chunks based on the stationary rocks in it
         // within each chunk, move all of the rolling rocks to the front
-        for col in &mut self.0 {
+        for col in self.0.rows_mut() {
             for chunk in col.split_mut(|element| *element == Element::StationaryRock) {
                 // the derived order on `Element` places `RollingRock` before `Nothing`
                 chunk.sort();
@@ -120,7 +169,7 @@ impl PlatformColumns {
 
     pub fn tilt_south(&mut self) {
         // same as above, but opposite
-        for col in &mut self.0 {
+        for col in self.0.rows_mut() {
             for chunk in col.split_mut(|element| *element == Element::StationaryRock) {
                 // the derived order on `Element` places `RollingRock` before `Nothing`, so
                 // do the opposite of that
@@ -133,7 +182,7 @@ impl PlatformColumns {
 impl PlatformRows {
     pub fn tilt_west(&mut self) {
         // same as tilt_north, but moving horizontally
-        for row in &mut self.0 {
+        for row in self.0.rows_mut() {
             for chunk in row.split_mut(|element| *element == Element::StationaryRock) {
                 chunk.sort();
             }
@@ -142,7 +191,7 @@ impl PlatformRows {
 
     pub fn tilt_east(&mut self) {
         // yeah
-        for row in &mut self.0 {
+        for row in self.0.rows_mut() {
             for chunk in row.split_mut(|element| *element == Element::StationaryRock) {
                 chunk.sort_by(|el1, el2| el2.cmp(el1));
             }
@@ -153,10 +202,11 @@ impl PlatformRows {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use common::check::{agree, Check};
 
     #[test]
     fn test_rows_to_cols() {
-        let rows = PlatformRows(vec![
+        let rows = PlatformRows(Grid::from_rows(vec![
             vec![
                 Element::RollingRock,
                 Element::Nothing,
@@ -167,31 +217,31 @@ mod tests {
                 Element::Nothing,
                 Element::RollingRock,
             ],
-        ]);
+        ]));
 
         let cols = PlatformColumns::from(&rows);
         assert_eq!(
             cols,
-            PlatformColumns(vec![
+            PlatformColumns(Grid::from_rows(vec![
                 vec![Element::RollingRock, Element::StationaryRock],
                 vec![Element::Nothing, Element::Nothing],
                 vec![Element::StationaryRock, Element::RollingRock],
-            ])
+            ]))
         )
     }
 
     #[test]
     fn test_cols_to_rows() {
-        let cols = PlatformColumns(vec![
+        let cols = PlatformColumns(Grid::from_rows(vec![
             vec![Element::RollingRock, Element::StationaryRock],
             vec![Element::Nothing, Element::Nothing],
             vec![Element::StationaryRock, Element::RollingRock],
-        ]);
+        ]));
         let rows = PlatformRows::from(&cols);
 
         assert_eq!(
             rows,
-            PlatformRows(vec![
+            PlatformRows(Grid::from_rows(vec![
                 vec![
                     Element::RollingRock,
                     Element::Nothing,
@@ -202,35 +252,18 @@ mod tests {
                     Element::Nothing,
                     Element::RollingRock
                 ],
-            ])
+            ]))
         )
     }
 
     #[test]
     fn test_tilt_north() {
-        let mut platform = Platform(PlatformRep::Columns(PlatformColumns(vec![
-            vec![Element::RollingRock, Element::Nothing, Element::RollingRock],
+        let mut platform = Platform(PlatformRep::Columns(PlatformColumns(Grid::from_rows(
             vec![
-                Element::Nothing,
-                Element::RollingRock,
-                Element::StationaryRock,
-            ],
-            vec![
-                Element::StationaryRock,
-                Element::RollingRock,
-                Element::RollingRock,
-            ],
-        ])));
-
-        platform.tilt(Direction::North);
-
-        assert_eq!(
-            platform,
-            Platform(PlatformRep::Columns(PlatformColumns(vec![
-                vec![Element::RollingRock, Element::RollingRock, Element::Nothing],
+                vec![Element::RollingRock, Element::Nothing, Element::RollingRock],
                 vec![
-                    Element::RollingRock,
                     Element::Nothing,
+                    Element::RollingRock,
                     Element::StationaryRock,
                 ],
                 vec![
@@ -238,13 +271,34 @@ mod tests {
                     Element::RollingRock,
                     Element::RollingRock,
                 ],
-            ])))
+            ],
+        ))));
+
+        platform.tilt(Direction::Up);
+
+        assert_eq!(
+            platform,
+            Platform(PlatformRep::Columns(PlatformColumns(Grid::from_rows(
+                vec![
+                    vec![Element::RollingRock, Element::RollingRock, Element::Nothing],
+                    vec![
+                        Element::RollingRock,
+                        Element::Nothing,
+                        Element::StationaryRock,
+                    ],
+                    vec![
+                        Element::StationaryRock,
+                        Element::RollingRock,
+                        Element::RollingRock,
+                    ],
+                ]
+            ))))
         );
     }
 
     #[test]
     fn test_compute_load() {
-        let platform = Platform(PlatformRep::Rows(PlatformRows(vec![
+        let platform = Platform(PlatformRep::Rows(PlatformRows(Grid::from_rows(vec![
             vec![Element::RollingRock, Element::Nothing, Element::RollingRock],
             vec![
                 Element::StationaryRock,
@@ -256,7 +310,58 @@ mod tests {
                 Element::Nothing,
                 Element::RollingRock,
             ],
-        ])));
+        ]))));
         assert_eq!(platform.north_load(), 9);
     }
+
+    /// Lays out `cells` in rows of `width`: 0 is empty space, 1 a rolling rock
+    /// and anything else a stationary rock.
+    fn render_platform((width, cells): &(usize, Vec<u8>)) -> String {
+        cells
+            .chunks(*width)
+            .map(|row| {
+                (0..*width)
+                    .map(|idx| match row.get(idx) {
+                        None | Some(0) => '.',
+                        Some(1) => 'O',
+                        _ => '#',
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_extrapolated_load_matches_simulation() {
+        Check::new().cases(50).run(
+            |rng| {
+                let width = rng.range(1..7);
+                let cells: Vec<u8> = (0..width * rng.range(1..7))
+                    .map(|_| rng.below(3) as u8)
+                    .collect();
+                (width, cells)
+            },
+            |input| {
+                if input.0 == 0 || input.1.is_empty() {
+                    return Ok(());
+                }
+                let platform = Platform::from(render_platform(input).as_str());
+
+                let cycles = 137;
+                let mut simulated = platform.clone();
+                for _ in 0..cycles {
+                    simulated.spin_cycle();
+                }
+                agree(platform.north_load_after(cycles), simulated.north_load())
+            },
+        );
+    }
+
+    #[test]
+    fn test_display_after_tilting() {
+        let mut platform = Platform::from("O.#\n.O.\n..O");
+        platform.tilt(Direction::Up);
+        assert_eq!(platform.to_string(), "OO#\n..O\n...");
+    }
 }
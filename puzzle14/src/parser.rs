@@ -1,4 +1,6 @@
 use crate::{Element, Platform, PlatformRep, PlatformRows};
+use common::grid::Grid;
+use std::fmt::Display;
 
 impl From<char> for Element {
     fn from(c: char) -> Self {
@@ -13,11 +15,36 @@ impl From<char> for Element {
 
 impl From<&str> for Platform {
     fn from(input: &str) -> Self {
-        Self(PlatformRep::Rows(PlatformRows(
-            input
-                .lines()
-                .map(|line| line.chars().map(Element::from).collect())
-                .collect(),
-        )))
+        Self(PlatformRep::Rows(PlatformRows(Grid::parse(
+            input,
+            Element::from,
+        ))))
+    }
+}
+
+impl Display for Element {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            Element::RollingRock => 'O',
+            Element::StationaryRock => '#',
+            Element::Nothing => '.',
+        };
+        write!(f, "{c}")
+    }
+}
+
+impl Display for PlatformRows {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Display for Platform {
+    /// Always shows the platform row by row, the way it's parsed.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            PlatformRep::Rows(rows) => write!(f, "{rows}"),
+            PlatformRep::Columns(cols) => write!(f, "{}", PlatformRows::from(cols)),
+        }
     }
 }
@@ -4,14 +4,16 @@ use common::puzzle::PuzzlePart;
 pub struct Puzzle14a {}
 
 impl PuzzlePart for Puzzle14a {
+    type Input<'a> = Platform;
+
     fn description() -> &'static str {
         "Find the total load on the north edge of a platform of rocks after tilting it to the north."
     }
 
-    fn solve(input: &str) -> String {
-        let mut platform = Platform::from(input);
+    fn solve(input: &Self::Input<'_>) -> String {
+        let mut platform = input.clone();
 
-        platform.tilt(Direction::North);
+        platform.tilt(Direction::Up);
 
         platform.north_load().to_string()
     }
@@ -0,0 +1,28 @@
+use crate::Puzzle15;
+use common::{generate::InputGenerator, rng::Rng};
+
+impl InputGenerator for Puzzle15 {
+    /// `size` is the number of initialization steps.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        // reuse a limited set of labels, so that lenses get replaced and removed
+        let labels: Vec<String> = (0..size / 2 + 1)
+            .map(|_| {
+                (0..rng.range(2..7))
+                    .map(|_| char::from(b'a' + rng.below(26) as u8))
+                    .collect()
+            })
+            .collect();
+
+        (0..size)
+            .map(|_| {
+                let label = rng.choose(&labels);
+                if rng.chance(0.7) {
+                    format!("{label}={}", rng.range(1..10))
+                } else {
+                    format!("{label}-")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
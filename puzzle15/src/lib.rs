@@ -1,7 +1,49 @@
+use common::{
+    answer::Answer,
+    puzzle::{InputPrinter, Puzzle, Tag},
+};
+use puzzle15a::Puzzle15a;
+use puzzle15b::Puzzle15b;
+
+pub mod generator;
 pub mod parse;
 pub mod puzzle15a;
 pub mod puzzle15b;
 
+pub struct Puzzle15 {}
+
+impl Puzzle for Puzzle15 {
+    type Input<'a> = Vec<&'a str>;
+    type PartA = Puzzle15a;
+    type PartB = Puzzle15b;
+
+    fn name() -> &'static str {
+        "15"
+    }
+
+    fn title() -> &'static str {
+        "Lens Library"
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Hashing]
+    }
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input.trim().split(',').collect()
+    }
+}
+
+impl InputPrinter for Puzzle15 {
+    fn print(input: &Self::Input<'_>) -> String {
+        input
+            .iter()
+            .map(|&step| InitializationStep::from(step).to_string())
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
 pub struct Hash;
 
 impl Hash {
@@ -18,22 +60,26 @@ impl Hash {
 }
 
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum InitializationStep<'a> {
     Set { label: &'a str, value: u32 },
     Unset { label: &'a str },
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Lens<'a> {
     label: &'a str,
     value: u32,
 }
 
 #[derive(Debug, PartialEq, Eq, Default, Clone)]
-pub struct LensBox<'a>(Vec<Lens<'a>>);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LensBox<'a>(#[cfg_attr(feature = "serde", serde(borrow))] Vec<Lens<'a>>);
 
 #[derive(Debug, PartialEq, Eq)]
-pub struct LensArray<'a>(Vec<LensBox<'a>>);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LensArray<'a>(#[cfg_attr(feature = "serde", serde(borrow))] Vec<LensBox<'a>>);
 
 impl<'a> Default for LensArray<'a> {
     fn default() -> Self {
@@ -70,20 +116,22 @@ impl<'a> LensArray<'a> {
         }
     }
 
-    pub fn focusing_power(&self) -> u32 {
+    pub fn focusing_power(&self) -> Answer {
         self.0
             .iter()
             .enumerate()
             .map(|(box_idx, lens_box)| {
-                (box_idx as u32 + 1)
+                Answer::from(box_idx + 1)
                     * lens_box
                         .0
                         .iter()
                         .enumerate()
-                        .map(|(lens_idx, Lens { value, .. })| (lens_idx as u32 + 1) * *value)
-                        .sum::<u32>()
+                        .map(|(lens_idx, Lens { value, .. })| {
+                            Answer::from(lens_idx + 1) * Answer::from(*value)
+                        })
+                        .sum::<Answer>()
             })
-            .sum::<u32>()
+            .sum::<Answer>()
     }
 }
 
@@ -1,4 +1,5 @@
 use crate::InitializationStep;
+use std::fmt::Display;
 
 impl<'a> From<&'a str> for InitializationStep<'a> {
     fn from(input: &'a str) -> Self {
@@ -17,6 +18,15 @@ impl<'a> From<&'a str> for InitializationStep<'a> {
     }
 }
 
+impl Display for InitializationStep<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Set { label, value } => write!(f, "{label}={value}"),
+            Self::Unset { label } => write!(f, "{label}-"),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,19 +1,21 @@
 use crate::Hash;
-use common::puzzle::PuzzlePart;
+use common::{answer::Answer, puzzle::PuzzlePart};
 
 pub struct Puzzle15a {}
 
 impl PuzzlePart for Puzzle15a {
+    type Input<'a> = Vec<&'a str>;
+
     fn description() -> &'static str {
         "Find the sum of hash values of a bunch of strings."
     }
 
-    fn solve(input: &str) -> String {
+    fn solve(input: &Self::Input<'_>) -> String {
         input
-            .trim()
-            .split(',')
-            .map(Hash::hash)
-            .sum::<usize>()
+            .iter()
+            .copied()
+            .map(|step| Answer::from(Hash::hash(step)))
+            .sum::<Answer>()
             .to_string()
     }
 }
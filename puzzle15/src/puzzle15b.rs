@@ -5,14 +5,16 @@ use crate::{InitializationStep, Lens, LensArray};
 pub struct Puzzle15b {}
 
 impl PuzzlePart for Puzzle15b {
+    type Input<'a> = Vec<&'a str>;
+
     fn description() -> &'static str {
         "Find the `focusing power` of the final configuration of a hashmap-esque array of boxes."
     }
 
-    fn solve(input: &str) -> String {
+    fn solve(input: &Self::Input<'_>) -> String {
         let steps: Vec<_> = input
-            .trim()
-            .split(',')
+            .iter()
+            .copied()
             .map(InitializationStep::from)
             .collect();
 
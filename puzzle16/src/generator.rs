@@ -0,0 +1,22 @@
+use crate::Puzzle16;
+use common::{generate::InputGenerator, rng::Rng};
+
+impl InputGenerator for Puzzle16 {
+    /// `size` is the width and height of the contraption.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        (0..size)
+            .map(|_| {
+                (0..size)
+                    .map(|_| match rng.below(20) {
+                        0 => '/',
+                        1 => '\\',
+                        2 => '|',
+                        3 => '-',
+                        _ => '.',
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
@@ -1,8 +1,48 @@
+pub use common::geom::Direction;
+use common::{
+    grid::{Grid, Point},
+    puzzle::{InputPrinter, Puzzle, Tag},
+};
+use puzzle16a::Puzzle16a;
+use puzzle16b::Puzzle16b;
+
+pub mod generator;
 pub mod parser;
 pub mod puzzle16a;
 pub mod puzzle16b;
 
-#[derive(Debug, PartialEq, Eq)]
+pub struct Puzzle16 {}
+
+impl Puzzle for Puzzle16 {
+    type Input<'a> = TileGrid;
+    type PartA = Puzzle16a;
+    type PartB = Puzzle16b;
+
+    fn name() -> &'static str {
+        "16"
+    }
+
+    fn title() -> &'static str {
+        "The Floor Will Be Lava"
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Grid, Tag::Simulation]
+    }
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        TileGrid::from(input)
+    }
+}
+
+impl InputPrinter for Puzzle16 {
+    fn print(input: &Self::Input<'_>) -> String {
+        input.to_string()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Tile {
     Nothing,
     SplitterVertical,
@@ -28,51 +68,19 @@ impl Tile {
             }
             (Tile::SplitterHorizontal, dir) => vec![dir],
 
-            (Tile::DiagonalUp, Direction::Up) => vec![Direction::Right],
-            (Tile::DiagonalUp, Direction::Down) => vec![Direction::Left],
-            (Tile::DiagonalUp, Direction::Left) => vec![Direction::Down],
-            (Tile::DiagonalUp, Direction::Right) => vec![Direction::Up],
+            // `/` turns vertical beams to the right and horizontal ones to the left
+            (Tile::DiagonalUp, dir @ (Direction::Up | Direction::Down)) => vec![dir.turn_right()],
+            (Tile::DiagonalUp, dir) => vec![dir.turn_left()],
 
-            (Tile::DiagonalDown, Direction::Up) => vec![Direction::Left],
-            (Tile::DiagonalDown, Direction::Down) => vec![Direction::Right],
-            (Tile::DiagonalDown, Direction::Left) => vec![Direction::Up],
-            (Tile::DiagonalDown, Direction::Right) => vec![Direction::Down],
+            // and `\` does the opposite
+            (Tile::DiagonalDown, dir @ (Direction::Up | Direction::Down)) => vec![dir.turn_left()],
+            (Tile::DiagonalDown, dir) => vec![dir.turn_right()],
         }
     }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
-pub enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl Direction {
-    /// Gets the grid location that results from taking one step in this direction
-    /// starting from the given location.
-    fn map_location(&self, location: (usize, usize)) -> Option<(usize, usize)> {
-        match self {
-            Direction::Up => {
-                if location.0 == 0 {
-                    return None;
-                }
-                Some((location.0 - 1, location.1))
-            }
-            Direction::Down => Some((location.0 + 1, location.1)),
-            Direction::Left => {
-                if location.1 == 0 {
-                    return None;
-                }
-                Some((location.0, location.1 - 1))
-            }
-            Direction::Right => Some((location.0, location.1 + 1)),
-        }
-    }
-}
-
-#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EnergizedTile {
     /// The grid object at this location.
     tile: Tile,
@@ -82,25 +90,22 @@ pub struct EnergizedTile {
     beams: Vec<Direction>,
 }
 
-#[derive(Debug, PartialEq, Eq)]
-pub struct TileGrid(Vec<Vec<EnergizedTile>>);
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TileGrid(Grid<EnergizedTile>);
 
 impl TileGrid {
     pub fn width(&self) -> usize {
-        self.0[0].len()
+        self.0.width()
     }
 
     pub fn height(&self) -> usize {
-        self.0.len()
+        self.0.height()
     }
 
-    fn get_mut(&mut self, (row_idx, col_idx): (usize, usize)) -> Option<&mut EnergizedTile> {
-        self.0.get_mut(row_idx)?.get_mut(col_idx)
-    }
-
-    pub fn trace_beams(&mut self, location: (usize, usize), direction: Direction) {
+    pub fn trace_beams(&mut self, location: Point, direction: Direction) {
         // if there's no tile here, there's nothing to do
-        let Some(EnergizedTile { tile, beams }) = self.get_mut(location) else {
+        let Some(EnergizedTile { tile, beams }) = self.0.get_mut(location) else {
             return;
         };
 
@@ -110,7 +115,7 @@ impl TileGrid {
         if beams.contains(&direction) {
             return;
         } else {
-            beams.push(direction.clone());
+            beams.push(direction);
         }
 
         // based on the current tile and the direciton of the beam , determine where
@@ -118,7 +123,7 @@ impl TileGrid {
         let next_directions = tile.map_direction(direction);
 
         for next_direction in next_directions {
-            let Some(next_location) = next_direction.map_location(location) else {
+            let Some(next_location) = location.step(next_direction) else {
                 continue;
             };
             self.trace_beams(next_location, next_direction);
@@ -128,19 +133,13 @@ impl TileGrid {
     pub fn energized_tiles(&self) -> usize {
         self.0
             .iter()
-            .map(|row| {
-                row.iter()
-                    .map(|EnergizedTile { beams, .. }| if beams.is_empty() { 0 } else { 1 })
-                    .sum::<usize>()
-            })
-            .sum::<usize>()
+            .filter(|EnergizedTile { beams, .. }| !beams.is_empty())
+            .count()
     }
 
     pub fn reset(&mut self) {
-        for row in &mut self.0 {
-            for tile in row {
-                tile.beams = vec![]
-            }
+        for tile in self.0.iter_mut() {
+            tile.beams = vec![]
         }
     }
 }
@@ -1,4 +1,6 @@
 use crate::{EnergizedTile, Tile, TileGrid};
+use common::grid::Grid;
+use std::fmt::Display;
 
 impl From<char> for Tile {
     fn from(c: char) -> Self {
@@ -15,17 +17,29 @@ impl From<char> for Tile {
 
 impl From<&str> for TileGrid {
     fn from(input: &str) -> Self {
-        let tiles = input
-            .lines()
-            .map(|line| {
-                line.chars()
-                    .map(|char| EnergizedTile {
-                        tile: char.into(),
-                        beams: vec![],
-                    })
-                    .collect()
-            })
-            .collect();
-        Self(tiles)
+        Self(Grid::parse(input, |char| EnergizedTile {
+            tile: char.into(),
+            beams: vec![],
+        }))
+    }
+}
+
+impl Display for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            Self::Nothing => '.',
+            Self::SplitterVertical => '|',
+            Self::SplitterHorizontal => '-',
+            Self::DiagonalUp => '/',
+            Self::DiagonalDown => '\\',
+        };
+        write!(f, "{c}")
+    }
+}
+
+impl Display for TileGrid {
+    /// Shows the contraption's layout only, leaving out any beams that have been traced.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.map(|energized| energized.tile))
     }
 }
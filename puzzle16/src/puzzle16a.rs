@@ -1,16 +1,18 @@
 use crate::TileGrid;
-use common::puzzle::PuzzlePart;
+use common::{geom::Point, puzzle::PuzzlePart};
 
 pub struct Puzzle16a {}
 
 impl PuzzlePart for Puzzle16a {
+    type Input<'a> = TileGrid;
+
     fn description() -> &'static str {
         "Count how many tiles are energized by light bouncing around a grid."
     }
 
-    fn solve(input: &str) -> String {
-        let mut grid = TileGrid::from(input);
-        grid.trace_beams((0, 0), crate::Direction::Right);
+    fn solve(input: &Self::Input<'_>) -> String {
+        let mut grid = input.clone();
+        grid.trace_beams(Point::ORIGIN, crate::Direction::Right);
         grid.energized_tiles().to_string()
     }
 }
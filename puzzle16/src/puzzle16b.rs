@@ -1,37 +1,34 @@
 use crate::{Direction, TileGrid};
-use common::puzzle::PuzzlePart;
+use common::{geom::Point, parallel, puzzle::PuzzlePart};
 
 pub struct Puzzle16b {}
 
 impl PuzzlePart for Puzzle16b {
+    type Input<'a> = TileGrid;
+
     fn description() -> &'static str {
         "Find the maximum number of tiles that are energized by a beam of light with arbitrary starting location."
     }
 
-    fn solve(input: &str) -> String {
-        let mut grid = TileGrid::from(input);
-        let mut energized_tiles = vec![];
-
-        for row_idx in 0..grid.height() {
-            grid.trace_beams((row_idx, 0), Direction::Right);
-            energized_tiles.push(grid.energized_tiles());
-            grid.reset();
-
-            grid.trace_beams((row_idx, grid.width()), Direction::Left);
-            energized_tiles.push(grid.energized_tiles());
-            grid.reset();
+    fn solve(input: &Self::Input<'_>) -> String {
+        let (height, width) = (input.height(), input.width());
+        let mut starts = vec![];
+        for row_idx in 0..height {
+            starts.push((Point::new(row_idx, 0), Direction::Right));
+            starts.push((Point::new(row_idx, width), Direction::Left));
         }
-
-        for col_idx in 0..grid.width() {
-            grid.trace_beams((0, col_idx), Direction::Down);
-            energized_tiles.push(grid.energized_tiles());
-            grid.reset();
-
-            grid.trace_beams((grid.height(), col_idx), Direction::Up);
-            energized_tiles.push(grid.energized_tiles());
-            grid.reset();
+        for col_idx in 0..width {
+            starts.push((Point::new(0, col_idx), Direction::Down));
+            starts.push((Point::new(height, col_idx), Direction::Up));
         }
 
+        // every start traces on a grid of its own, so they can run in parallel
+        let energized_tiles = parallel::map(&starts, |(start, direction)| {
+            let mut grid = input.clone();
+            grid.trace_beams(*start, *direction);
+            grid.energized_tiles()
+        });
+
         energized_tiles.iter().max().unwrap().to_string()
     }
 }
@@ -0,0 +1,72 @@
+use crate::Puzzle19;
+use common::{generate::InputGenerator, rng::Rng};
+
+const CATEGORIES: [char; 4] = ['x', 'm', 'a', 's'];
+
+impl InputGenerator for Puzzle19 {
+    /// `size` is both the number of workflows and the number of parts.
+    ///
+    /// Workflows only ever send parts to workflows later in the list, so that every
+    /// part is eventually accepted or rejected.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let size = size.max(1);
+        let names = workflow_names(size, rng);
+
+        let workflows: Vec<String> = (0..size)
+            .map(|idx| {
+                let later = &names[idx + 1..];
+                let rules: Vec<String> = (0..rng.range(1..5))
+                    .map(|_| {
+                        let destination = destination(later, rng);
+                        format!(
+                            "{}{}{}:{destination}",
+                            rng.choose(&CATEGORIES),
+                            if rng.chance(0.5) { '<' } else { '>' },
+                            rng.range(1..4001)
+                        )
+                    })
+                    .collect();
+                format!(
+                    "{}{{{},{}}}",
+                    names[idx],
+                    rules.join(","),
+                    destination(later, rng)
+                )
+            })
+            .collect();
+
+        let parts: Vec<String> = (0..size)
+            .map(|_| {
+                let ratings: Vec<String> = CATEGORIES
+                    .iter()
+                    .map(|category| format!("{category}={}", rng.range(1..4001)))
+                    .collect();
+                format!("{{{}}}", ratings.join(","))
+            })
+            .collect();
+
+        format!("{}\n\n{}", workflows.join("\n"), parts.join("\n"))
+    }
+}
+
+fn destination(later_workflows: &[String], rng: &mut Rng) -> String {
+    if later_workflows.is_empty() || rng.chance(0.3) {
+        if rng.chance(0.5) { "A" } else { "R" }.to_string()
+    } else {
+        rng.choose(later_workflows).clone()
+    }
+}
+
+/// Unique lowercase names, the first of which is the "in" workflow.
+fn workflow_names(count: usize, rng: &mut Rng) -> Vec<String> {
+    let mut names = vec!["in".to_string()];
+    while names.len() < count {
+        let name: String = (0..rng.range(2..5))
+            .map(|_| char::from(b'a' + rng.below(26) as u8))
+            .collect();
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
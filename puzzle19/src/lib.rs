@@ -1,12 +1,64 @@
+use common::{
+    diagnostic::ParseError,
+    graph::Graph,
+    puzzle::{InputPrinter, Puzzle, Tag},
+};
 use part_set::PartSet;
-use std::collections::HashMap;
+use puzzle19a::Puzzle19a;
+use puzzle19b::Puzzle19b;
 
+pub mod generator;
 pub mod parser;
 pub mod part_set;
 pub mod puzzle19a;
 pub mod puzzle19b;
 
+pub struct Puzzle19 {}
+
+impl Puzzle for Puzzle19 {
+    type Input<'a> = (System<'a>, Vec<Part>);
+    type PartA = Puzzle19a;
+    type PartB = Puzzle19b;
+
+    fn name() -> &'static str {
+        "19"
+    }
+
+    fn title() -> &'static str {
+        "Aplenty"
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Parsing, Tag::Graph, Tag::Intervals]
+    }
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        Self::try_parse(input).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        let (system, parts) = input.split_once("\n\n").ok_or_else(|| {
+            ParseError::at_end(input, "a blank line between the workflows and the parts")
+        })?;
+        let system = System::try_from(system).map_err(|err| err.within(input, system))?;
+        let parts = parts
+            .lines()
+            .map(|line| Part::try_from(line).map_err(|err| err.within(input, line)))
+            .collect::<Result<_, _>>()?;
+        Ok((system, parts))
+    }
+}
+
+impl InputPrinter for Puzzle19 {
+    fn print(input: &Self::Input<'_>) -> String {
+        let (system, parts) = input;
+        let parts: Vec<_> = parts.iter().map(Part::to_string).collect();
+        format!("{system}\n\n{}", parts.join("\n"))
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Part {
     x: u64,
     m: u64,
@@ -30,6 +82,7 @@ impl Part {
 }
 
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Category {
     X,
     M,
@@ -38,12 +91,14 @@ pub enum Category {
 }
 
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Operator {
     GreaterThan,
     LessThan,
 }
 
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum State<'a> {
     Accept,
     Reject,
@@ -51,34 +106,54 @@ pub enum State<'a> {
 }
 
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ComparisonRule<'a> {
     category: Category,
     operator: Operator,
     value: u64,
+    #[cfg_attr(feature = "serde", serde(borrow))]
     destination: State<'a>,
 }
 
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DirectRule<'a> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
     destination: State<'a>,
 }
 
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Rule<'a> {
-    Comparison(ComparisonRule<'a>),
-    Direct(DirectRule<'a>),
+    Comparison(#[cfg_attr(feature = "serde", serde(borrow))] ComparisonRule<'a>),
+    Direct(#[cfg_attr(feature = "serde", serde(borrow))] DirectRule<'a>),
 }
 
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Workflow<'a> {
     name: &'a str,
+    #[cfg_attr(feature = "serde", serde(borrow))]
     rules: Vec<Rule<'a>>,
 }
 
+/// The workflows, labeled by their names, with an edge to each workflow they send parts to.
+///
+/// Parsing makes sure the names are unique, that there's an `in` workflow, that every
+/// destination exists and that the workflows don't form a loop.
 #[derive(Debug, PartialEq, Eq)]
-pub struct System<'a>(HashMap<&'a str, Workflow<'a>>);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct System<'a>(#[cfg_attr(feature = "serde", serde(borrow))] Graph<Workflow<'a>>);
 
 impl<'a> System<'a> {
+    fn workflow(&self, name: &str) -> &Workflow<'a> {
+        let id = self
+            .0
+            .id_of(name)
+            .expect("destinations are checked when parsing");
+        &self.0[id]
+    }
+
     fn accepts(&self, part: &Part) -> bool {
         let mut state = &State::Workflow("in");
         loop {
@@ -86,7 +161,7 @@ impl<'a> System<'a> {
                 State::Accept => return true,
                 State::Reject => return false,
                 State::Workflow(name) => {
-                    let workflow = self.0.get(name).unwrap();
+                    let workflow = self.workflow(name);
                     for rule in &workflow.rules {
                         match rule {
                             Rule::Comparison(ComparisonRule {
@@ -117,15 +192,18 @@ impl<'a> System<'a> {
     }
 
     fn count_accepted_parts(&self) -> u64 {
-        let start_state = State::Workflow("in");
-        let start_set = PartSet {
-            x: 1..4001,
-            m: 1..4001,
-            a: 1..4001,
-            s: 1..4001,
-        };
+        self.count_accepted_parts_in(&PartSet {
+            x: (1..4001).into(),
+            m: (1..4001).into(),
+            a: (1..4001).into(),
+            s: (1..4001).into(),
+        })
+    }
 
-        let accepted_sets = self.find_accepted_sets(&start_set, &start_state);
+    /// Counts how many of the parts in `parts` would be accepted.
+    fn count_accepted_parts_in(&self, parts: &PartSet) -> u64 {
+        let start_state = State::Workflow("in");
+        let accepted_sets = self.find_accepted_sets(parts, &start_state);
 
         // now refine these sets into disjoint sets
         let mut refined_sets = vec![];
@@ -148,7 +226,7 @@ impl<'a> System<'a> {
             State::Accept => vec![set.clone()],
             State::Reject => vec![],
             State::Workflow(name) => {
-                let workflow = self.0.get(name).unwrap();
+                let workflow = self.workflow(name);
                 let mut working_set = set.clone();
                 let mut new_sets = vec![];
 
@@ -184,3 +262,116 @@ impl<'a> System<'a> {
 }
 
 // for each "Accept" in the system
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{
+        check::{agree, Check},
+        rng::Rng,
+    };
+
+    /// Ratings only go up to this in the checks, so that every part can be tried.
+    const MAX_RATING: u64 = 6;
+
+    /// A rule's destination: 0 accepts, 1 rejects, and anything else goes that many
+    /// workflows further down the list (minus one), rejecting if that's past the end.
+    /// Only ever going further down the list keeps the system free of cycles.
+    type RuleData = (u8, bool, u64, u64);
+    type SystemData = Vec<(Vec<RuleData>, u64)>;
+
+    fn render_system(workflows: &SystemData) -> String {
+        let name = |idx: usize| {
+            if idx == 0 {
+                "in".to_string()
+            } else {
+                format!("w{idx}")
+            }
+        };
+        let destination = |idx: usize, destination: u64| match destination {
+            0 => "A".to_string(),
+            1 => "R".to_string(),
+            offset => {
+                let target = idx + offset as usize - 1;
+                if target < workflows.len() {
+                    name(target)
+                } else {
+                    "R".to_string()
+                }
+            }
+        };
+
+        workflows
+            .iter()
+            .enumerate()
+            .map(|(idx, (rules, fallback))| {
+                let rules: Vec<String> = rules
+                    .iter()
+                    .map(|(category, greater_than, value, dest)| {
+                        format!(
+                            "{}{}{value}:{}",
+                            ['x', 'm', 'a', 's'][*category as usize % 4],
+                            if *greater_than { '>' } else { '<' },
+                            destination(idx, *dest)
+                        )
+                    })
+                    .chain([destination(idx, *fallback)])
+                    .collect();
+                format!("{}{{{}}}", name(idx), rules.join(","))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_count_accepted_parts_matches_enumeration() {
+        Check::new().run(
+            |rng: &mut Rng| {
+                (0..rng.range(1..5))
+                    .map(|_| {
+                        let rules = (0..rng.range(0..4))
+                            .map(|_| {
+                                (
+                                    rng.below(4) as u8,
+                                    rng.chance(0.5),
+                                    rng.below(MAX_RATING + 2),
+                                    rng.below(5),
+                                )
+                            })
+                            .collect();
+                        (rules, rng.below(5))
+                    })
+                    .collect::<SystemData>()
+            },
+            |workflows| {
+                if workflows.is_empty() {
+                    return Ok(());
+                }
+                let input = render_system(workflows);
+                let system = System::try_from(input.as_str()).unwrap();
+
+                let ratings = 1..MAX_RATING + 1;
+                let mut accepted = 0;
+                for x in ratings.clone() {
+                    for m in ratings.clone() {
+                        for a in ratings.clone() {
+                            for s in ratings.clone() {
+                                if system.accepts(&Part { x, m, a, s }) {
+                                    accepted += 1;
+                                }
+                            }
+                        }
+                    }
+                }
+
+                let all_parts = PartSet {
+                    x: ratings.clone().into(),
+                    m: ratings.clone().into(),
+                    a: ratings.clone().into(),
+                    s: ratings.into(),
+                };
+                agree(system.count_accepted_parts_in(&all_parts), accepted)
+            },
+        );
+    }
+}
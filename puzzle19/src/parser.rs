@@ -1,102 +1,292 @@
 use crate::{Category, ComparisonRule, DirectRule, Operator, Part, Rule, State, System, Workflow};
+use common::diagnostic::ParseError;
+use common::graph::Graph;
+use std::fmt::Display;
 
-impl From<char> for Category {
-    fn from(c: char) -> Self {
-        match c {
-            'x' => Self::X,
-            'm' => Self::M,
-            'a' => Self::A,
-            's' => Self::S,
-            _ => panic!("unrecognized category character"),
+impl TryFrom<&str> for Category {
+    type Error = ParseError;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        match input {
+            "x" => Ok(Self::X),
+            "m" => Ok(Self::M),
+            "a" => Ok(Self::A),
+            "s" => Ok(Self::S),
+            _ => Err(ParseError::at(
+                input,
+                input,
+                "a category (`x`, `m`, `a` or `s`)",
+            )),
         }
     }
 }
 
-impl From<&str> for Part {
-    fn from(input: &str) -> Self {
+fn parse_rating(input: &str) -> Result<u64, ParseError> {
+    input
+        .parse::<u64>()
+        .map_err(|_| ParseError::at(input, input, "a rating"))
+}
+
+impl TryFrom<&str> for Part {
+    type Error = ParseError;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
         // starts/ends with a '{' and '}'
         // then a comma-separated list that's always in the
         // order x=???, m=???, a=???, s=???
-        let mut ratings = input[1..input.len() - 1]
-            .split(',')
-            .map(|assignment| assignment[2..].parse::<u64>().unwrap());
-        Self {
-            x: ratings.next().unwrap(),
-            m: ratings.next().unwrap(),
-            a: ratings.next().unwrap(),
-            s: ratings.next().unwrap(),
+        let assignments = input
+            .strip_prefix('{')
+            .ok_or_else(|| ParseError::at(input, input.get(..1).unwrap_or(input), "`{`"))?
+            .strip_suffix('}')
+            .ok_or_else(|| ParseError::at_end(input, "`}` to close the part"))?;
+
+        let mut assignments = assignments.split(',');
+        let mut rating = |category: &str| {
+            let Some(assignment) = assignments.next() else {
+                return Err(ParseError::new(
+                    input.len() - 1..input.len(),
+                    format!("a rating for `{category}`"),
+                ));
+            };
+            let Some(value) = assignment
+                .strip_prefix(category)
+                .and_then(|rest| rest.strip_prefix('='))
+            else {
+                return Err(ParseError::at(input, assignment, format!("`{category}=`")));
+            };
+            parse_rating(value).map_err(|err| err.within(input, value))
+        };
+
+        Ok(Self {
+            x: rating("x")?,
+            m: rating("m")?,
+            a: rating("a")?,
+            s: rating("s")?,
+        })
+    }
+}
+
+impl<'a> From<&'a str> for State<'a> {
+    fn from(name: &'a str) -> Self {
+        match name {
+            "A" => State::Accept,
+            "R" => State::Reject,
+            name => State::Workflow(name),
         }
     }
 }
 
-impl<'a> From<&'a str> for Rule<'a> {
-    fn from(input: &'a str) -> Self {
-        if let Some((comparison, name)) = input.split_once(':') {
-            // comparison rule
-            let category = comparison.chars().next().unwrap().into();
-            let operator = if comparison.chars().nth(1).unwrap() == '>' {
-                Operator::GreaterThan
-            } else {
-                Operator::LessThan
-            };
-            let value = comparison[2..].parse::<u64>().unwrap();
-            let destination = match name {
-                "A" => State::Accept,
-                "R" => State::Reject,
-                name => State::Workflow(name),
+fn parse_destination(input: &str) -> Result<State<'_>, ParseError> {
+    if input.is_empty() || !input.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(ParseError::at(input, input, "a workflow name, `A` or `R`"));
+    }
+    Ok(State::from(input))
+}
+
+impl<'a> TryFrom<&'a str> for Rule<'a> {
+    type Error = ParseError;
+
+    fn try_from(input: &'a str) -> Result<Self, Self::Error> {
+        let Some((comparison, name)) = input.split_once(':') else {
+            // direct rule
+            let destination = parse_destination(input)?;
+            return Ok(Self::Direct(DirectRule { destination }));
+        };
+
+        // comparison rule
+        let within = |err: ParseError, part: &str| err.within(input, part);
+        let category_str = comparison.get(..1).unwrap_or(comparison);
+        let category = Category::try_from(category_str).map_err(|err| within(err, category_str))?;
+        let operator = match comparison.get(1..2) {
+            Some(">") => Operator::GreaterThan,
+            Some("<") => Operator::LessThan,
+            _ => {
+                let bad = comparison.get(1..2).unwrap_or(&comparison[1..]);
+                return Err(ParseError::at(input, bad, "`<` or `>` after category"));
+            }
+        };
+        let value_str = &comparison[2..];
+        let value = value_str
+            .parse::<u64>()
+            .map_err(|_| ParseError::at(input, value_str, "a number after the comparison"))?;
+        let destination = parse_destination(name).map_err(|err| within(err, name))?;
+
+        Ok(Self::Comparison(ComparisonRule {
+            category,
+            operator,
+            value,
+            destination,
+        }))
+    }
+}
+
+impl<'a> TryFrom<&'a str> for Workflow<'a> {
+    type Error = ParseError;
+
+    fn try_from(input: &'a str) -> Result<Self, Self::Error> {
+        // input looks like `name{rule1,rule2,rule3}`
+        let Some((name, rules)) = input.split_once('{') else {
+            return Err(ParseError::at_end(input, "`{` after the workflow name"));
+        };
+        let Some(rules) = rules.strip_suffix('}') else {
+            return Err(ParseError::at_end(input, "`}` to close the workflow"));
+        };
+        let rules = rules
+            .split(',')
+            .map(|rule| Rule::try_from(rule).map_err(|err| err.within(input, rule)))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { name, rules })
+    }
+}
+
+impl<'a> Workflow<'a> {
+    /// The names of the workflows this one sends parts to.
+    fn destinations(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.rules.iter().filter_map(|rule| {
+            let destination = match rule {
+                Rule::Comparison(ComparisonRule { destination, .. })
+                | Rule::Direct(DirectRule { destination }) => destination,
             };
+            match destination {
+                State::Workflow(name) => Some(*name),
+                State::Accept | State::Reject => None,
+            }
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a str> for System<'a> {
+    type Error = ParseError;
+
+    fn try_from(input: &'a str) -> Result<Self, Self::Error> {
+        let mut graph = Graph::new();
+        for line in input.lines() {
+            let workflow = Workflow::try_from(line).map_err(|err| err.within(input, line))?;
+            if graph.id_of(workflow.name).is_some() {
+                return Err(ParseError::at(
+                    input,
+                    workflow.name,
+                    "a workflow name that isn't already taken",
+                ));
+            }
+            graph.add_labeled_node(workflow.name, workflow);
+        }
+        if graph.id_of("in").is_none() {
+            return Err(ParseError::at_end(input, "an `in` workflow"));
+        }
+
+        let mut edges = vec![];
+        for (id, workflow) in graph.nodes() {
+            for name in workflow.destinations() {
+                let destination = graph
+                    .id_of(name)
+                    .ok_or_else(|| ParseError::at(input, name, "the name of a workflow"))?;
+                edges.push((id, destination));
+            }
+        }
+        for (from, to) in edges {
+            graph.add_edge(from, to, ());
+        }
+
+        // parts that get sent round in a loop would never be accepted or rejected
+        let looping = graph
+            .strongly_connected_components()
+            .into_iter()
+            .find(|component| {
+                component.len() > 1 || graph.neighbors(component[0]).any(|id| id == component[0])
+            });
+        if let Some(component) = looping {
+            return Err(ParseError::at(
+                input,
+                graph[component[0]].name,
+                "workflows that don't form a loop",
+            ));
+        }
+
+        Ok(Self(graph))
+    }
+}
+
+impl Display for Category {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            Self::X => 'x',
+            Self::M => 'm',
+            Self::A => 'a',
+            Self::S => 's',
+        };
+        write!(f, "{c}")
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{{x={},m={},a={},s={}}}", self.x, self.m, self.a, self.s)
+    }
+}
+
+impl Display for Operator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::GreaterThan => write!(f, ">"),
+            Self::LessThan => write!(f, "<"),
+        }
+    }
+}
+
+impl Display for State<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Accept => write!(f, "A"),
+            Self::Reject => write!(f, "R"),
+            Self::Workflow(name) => write!(f, "{name}"),
+        }
+    }
+}
+
+impl Display for Rule<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
             Self::Comparison(ComparisonRule {
                 category,
                 operator,
                 value,
                 destination,
-            })
-        } else {
-            // direct rule
-            let destination = match input {
-                "A" => State::Accept,
-                "R" => State::Reject,
-                name => State::Workflow(name),
-            };
-            Self::Direct(DirectRule { destination })
+            }) => write!(f, "{category}{operator}{value}:{destination}"),
+            Self::Direct(DirectRule { destination }) => write!(f, "{destination}"),
         }
     }
 }
 
-impl<'a> From<&'a str> for Workflow<'a> {
-    fn from(input: &'a str) -> Self {
-        // input looks like `name{rule1,rule2,rule3}`
-        let (name, rules) = input.split_once('{').unwrap();
-        let rules = rules[..rules.len() - 1]
-            .split(',')
-            .map(Rule::from)
-            .collect();
-        Self { name, rules }
-    }
-}
-
-impl<'a> From<&'a str> for System<'a> {
-    fn from(input: &'a str) -> Self {
-        Self(
-            input
-                .lines()
-                .map(|line| {
-                    let workflow = Workflow::from(line);
-                    (workflow.name, workflow)
-                })
-                .collect(),
-        )
+impl Display for Workflow<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rules: Vec<_> = self.rules.iter().map(Rule::to_string).collect();
+        write!(f, "{}{{{}}}", self.name, rules.join(","))
+    }
+}
+
+impl Display for System<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (id, workflow) in self.0.nodes() {
+            if id > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{workflow}")?;
+        }
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Puzzle19;
+    use common::puzzle::Puzzle;
 
     #[test]
     fn test_parse_part_rating() {
         let input = "{x=787,m=2655,a=1222,s=2876}";
-        let part = Part::from(input);
+        let part = Part::try_from(input).unwrap();
 
         assert_eq!(
             part,
@@ -112,7 +302,7 @@ mod tests {
     #[test]
     fn test_parse_direct_rule() {
         let input = "A";
-        let rule = Rule::from(input);
+        let rule = Rule::try_from(input).unwrap();
 
         assert_eq!(
             rule,
@@ -122,7 +312,7 @@ mod tests {
         );
 
         let input = "R";
-        let rule = Rule::from(input);
+        let rule = Rule::try_from(input).unwrap();
 
         assert_eq!(
             rule,
@@ -132,7 +322,7 @@ mod tests {
         );
 
         let input = "nice";
-        let rule = Rule::from(input);
+        let rule = Rule::try_from(input).unwrap();
 
         assert_eq!(
             rule,
@@ -145,7 +335,7 @@ mod tests {
     #[test]
     fn test_parse_comparision_rule() {
         let input = "a<2006:qkq";
-        let rule = Rule::from(input);
+        let rule = Rule::try_from(input).unwrap();
 
         assert_eq!(
             rule,
@@ -158,7 +348,7 @@ mod tests {
         );
 
         let input = "x>2440:R";
-        let rule = Rule::from(input);
+        let rule = Rule::try_from(input).unwrap();
 
         assert_eq!(
             rule,
@@ -174,7 +364,7 @@ mod tests {
     #[test]
     fn test_parse_workflow() {
         let input = "px{a<2006:qkq,m>2090:A,rfg}";
-        let workflow = Workflow::from(input);
+        let workflow = Workflow::try_from(input).unwrap();
 
         assert_eq!(
             workflow,
@@ -200,4 +390,51 @@ mod tests {
             }
         )
     }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = Rule::try_from("a=2006:qkq").unwrap_err();
+        assert_eq!(error, ParseError::new(1..2, "`<` or `>` after category"));
+
+        let error = Part::try_from("{x=787,m=2655,s=1222,s=2876}").unwrap_err();
+        assert_eq!(error, ParseError::new(14..20, "`a=`"));
+
+        let input = "in{s<1351:px,qqz}\npx{a=2006:qkq,m>2090:A,rfg}\n\n{x=1,m=2,a=3,s=4}";
+        let error = Puzzle19::try_parse(input).unwrap_err();
+        assert_eq!(
+            error.render(input, "input"),
+            [
+                "error: expected `<` or `>` after category",
+                " --> input:2:5",
+                "  |",
+                "2 | px{a=2006:qkq,m>2090:A,rfg}",
+                "  |     ^",
+            ]
+            .join("\n")
+        );
+
+        let error = System::try_from("in{a<5:px,A}\npx{m>3:qqz,R}").unwrap_err();
+        assert_eq!(error, ParseError::new(20..23, "the name of a workflow"));
+
+        let error = System::try_from("px{m>3:in,R}\nin{a<5:px,A}").unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::new(0..2, "workflows that don't form a loop")
+        );
+
+        let error = System::try_from("in{a<5:A,R}\nin{m>3:A,R}").unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::new(12..14, "a workflow name that isn't already taken")
+        );
+
+        let error = System::try_from("px{a<5:A,R}").unwrap_err();
+        assert_eq!(error, ParseError::new(11..11, "an `in` workflow"));
+    }
+
+    #[test]
+    fn test_display_workflow() {
+        let input = "px{a<2006:qkq,m>2090:A,rfg}";
+        assert_eq!(Workflow::try_from(input).unwrap().to_string(), input);
+    }
 }
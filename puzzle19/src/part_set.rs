@@ -1,16 +1,19 @@
 use crate::{Category, Operator};
-use std::ops::Range;
+use common::interval::IntervalSet;
 
+/// Every combination of the ratings allowed in each category.
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct PartSet {
-    pub x: Range<u64>,
-    pub m: Range<u64>,
-    pub a: Range<u64>,
-    pub s: Range<u64>,
+    pub x: IntervalSet<u64>,
+    pub m: IntervalSet<u64>,
+    pub a: IntervalSet<u64>,
+    pub s: IntervalSet<u64>,
 }
 
+const CATEGORIES: [Category; 4] = [Category::X, Category::M, Category::A, Category::S];
+
 impl PartSet {
-    pub fn get(&self, category: &Category) -> &Range<u64> {
+    pub fn get(&self, category: &Category) -> &IntervalSet<u64> {
         match category {
             Category::X => &self.x,
             Category::M => &self.m,
@@ -19,22 +22,22 @@ impl PartSet {
         }
     }
 
-    pub fn clone_with(&self, category: &Category, range: Range<u64>) -> PartSet {
+    pub fn clone_with(&self, category: &Category, values: IntervalSet<u64>) -> PartSet {
         match category {
             Category::X => PartSet {
-                x: range,
+                x: values,
                 ..self.clone()
             },
             Category::M => PartSet {
-                m: range,
+                m: values,
                 ..self.clone()
             },
             Category::A => PartSet {
-                a: range,
+                a: values,
                 ..self.clone()
             },
             Category::S => PartSet {
-                s: range,
+                s: values,
                 ..self.clone()
             },
         }
@@ -49,125 +52,57 @@ impl PartSet {
         operator: &Operator,
         value: u64,
     ) -> (Option<PartSet>, Option<PartSet>) {
-        let range = self.get(category);
-
-        // check if this entire set matches
-        if (*operator == Operator::GreaterThan && range.start > value)
-            || (*operator == Operator::LessThan && range.end <= value)
-        {
-            return (Some(self.clone()), None);
-        }
-
-        // check if this entire set does not match
-        if (*operator == Operator::GreaterThan && range.end <= value)
-            || (*operator == Operator::LessThan && range.start > value)
-        {
-            return (None, Some(self.clone()));
-        }
-
-        let (match_range, unmatch_range) = match operator {
-            Operator::GreaterThan => (value + 1..range.end, range.start..value + 1),
-            Operator::LessThan => (range.start..value, value..range.end),
-        };
-
+        let values = self.get(category);
+        let satisfying = IntervalSet::from(match operator {
+            Operator::GreaterThan => value + 1..u64::MAX,
+            Operator::LessThan => 0..value,
+        });
+
+        let matching = values.intersection(&satisfying);
+        let unmatching = values.difference(&satisfying);
         (
-            Some(self.clone_with(category, match_range)),
-            Some(self.clone_with(category, unmatch_range)),
+            (!matching.is_empty()).then(|| self.clone_with(category, matching)),
+            (!unmatching.is_empty()).then(|| self.clone_with(category, unmatching)),
         )
     }
 
     /// Decompose a set into a collection of subsets that are
     /// pairwise disjoint from themselves and from this set
     pub fn refine(&self, other: &PartSet) -> Vec<PartSet> {
-        let mut refinements = vec![other.clone()];
-
-        while !refinements.iter().all(|set| self.is_disjoint_from(set)) {
-            for category in [Category::X, Category::M, Category::A, Category::S] {
-                let self_range = self.get(&category);
-                let other_range = other.get(&category);
-
-                refinements = refinements
-                    .into_iter()
-                    .flat_map(|set| {
-                        let mut sets = vec![];
-
-                        // self:     (      )
-                        // other: (      )
-                        if other_range.contains(&(self_range.start - 1))
-                            && !other_range.contains(&self_range.end)
-                        {
-                            sets.push(
-                                set.clone_with(&category, other_range.start..self_range.start),
-                            );
-                            sets.push(set.clone_with(&category, self_range.start..other_range.end));
-                        }
-                        // self:  (      )
-                        // other:     (      )
-                        else if other_range.contains(&self_range.end)
-                            && !other_range.contains(&(self_range.start - 1))
-                        {
-                            sets.push(set.clone_with(&category, other_range.start..self_range.end));
-                            sets.push(set.clone_with(&category, self_range.end..other_range.end));
-                        }
-                        // self:      (      )
-                        // other:  (             )
-                        else if other_range.contains(&self_range.end)
-                            && other_range.contains(&self_range.start)
-                        {
-                            sets.push(
-                                set.clone_with(&category, other_range.start..self_range.start),
-                            );
-                            sets.push(set.clone_with(&category, self_range.start..self_range.end));
-                            sets.push(set.clone_with(&category, self_range.end..other_range.end));
-                        // }
-                        // self:  (             )
-                        // other:     (      )
-                        // else if self_range.contains(&(other_range.end - 1))
-                        //     && self_range.contains(&other_range.start)
-                        // {
-                        // FIXME: I think this is wrong
-                        // do nothing, just eat this one later?
-                        } else {
-                            sets.push(set);
-                        }
-
-                        sets
-                    })
-                    .collect();
-            }
+        if self.is_disjoint_from(other) {
+            return vec![other.clone()];
+        }
 
-            // remove all of the sets of the refinement that are proper subsets
-            // of this one
-            refinements.retain(|set| {
-                ![Category::X, Category::M, Category::A, Category::S]
-                    .iter()
-                    .all(|category| {
-                        let self_range = self.get(category);
-                        let other_range = set.get(category);
-                        self_range.contains(&other_range.start)
-                            && self_range.contains(&(other_range.end - 1))
-                    })
-            });
+        // peel off the parts of `other` that are outside of this set one category at a
+        // time, narrowing down what's left to the values inside of it as we go
+        let mut refinements = vec![];
+        let mut rest = other.clone();
+        for category in &CATEGORIES {
+            let outside = rest.get(category).difference(self.get(category));
+            if !outside.is_empty() {
+                refinements.push(rest.clone_with(category, outside));
+            }
+            let inside = rest.get(category).intersection(self.get(category));
+            rest = rest.clone_with(category, inside);
         }
 
+        // whatever is left is inside of this set
         refinements
     }
 
     pub fn size(&self) -> u64 {
-        (self.x.end - self.x.start)
-            * (self.m.end - self.m.start)
-            * (self.a.end - self.a.start)
-            * (self.s.end - self.s.start)
+        CATEGORIES
+            .iter()
+            .map(|category| self.get(category).len())
+            .product()
     }
 
     pub fn is_disjoint_from(&self, other: &PartSet) -> bool {
-        [Category::X, Category::M, Category::A, Category::S]
-            .iter()
-            .any(|category| {
-                let self_range = self.get(category);
-                let other_range = other.get(category);
-                self_range.end <= other_range.start || other_range.end <= self_range.start
-            })
+        CATEGORIES.iter().any(|category| {
+            self.get(category)
+                .intersection(other.get(category))
+                .is_empty()
+        })
     }
 }
 
@@ -178,19 +113,19 @@ mod tests {
     #[test]
     fn test_compute_size() {
         let set = PartSet {
-            x: 1..4001,
-            m: 1..4001,
-            a: 1..4001,
-            s: 1..4001,
+            x: (1..4001).into(),
+            m: (1..4001).into(),
+            a: (1..4001).into(),
+            s: (1..4001).into(),
         };
 
         assert_eq!(set.size(), 256_000_000_000_000);
 
         let set = PartSet {
-            x: 1..4001,
-            m: 1..1,
-            a: 1..4001,
-            s: 1..4001,
+            x: (1..4001).into(),
+            m: (1..1).into(),
+            a: (1..4001).into(),
+            s: (1..4001).into(),
         };
 
         assert_eq!(set.size(), 0);
@@ -199,29 +134,29 @@ mod tests {
     #[test]
     fn test_split() {
         let set = PartSet {
-            x: 1..4001,
-            m: 1000..2000,
-            a: 1..10,
-            s: 1..4001,
+            x: (1..4001).into(),
+            m: (1000..2000).into(),
+            a: (1..10).into(),
+            s: (1..4001).into(),
         };
 
         let (match_set, unmatch_set) = set.split(&Category::X, &Operator::GreaterThan, 3333);
         assert_eq!(
             match_set,
             Some(PartSet {
-                x: 3334..4001,
-                m: 1000..2000,
-                a: 1..10,
-                s: 1..4001,
+                x: (3334..4001).into(),
+                m: (1000..2000).into(),
+                a: (1..10).into(),
+                s: (1..4001).into(),
             })
         );
         assert_eq!(
             unmatch_set,
             Some(PartSet {
-                x: 1..3334,
-                m: 1000..2000,
-                a: 1..10,
-                s: 1..4001,
+                x: (1..3334).into(),
+                m: (1000..2000).into(),
+                a: (1..10).into(),
+                s: (1..4001).into(),
             })
         );
 
@@ -230,10 +165,10 @@ mod tests {
         assert_eq!(
             unmatch_set,
             Some(PartSet {
-                x: 1..4001,
-                m: 1000..2000,
-                a: 1..10,
-                s: 1..4001,
+                x: (1..4001).into(),
+                m: (1000..2000).into(),
+                a: (1..10).into(),
+                s: (1..4001).into(),
             })
         );
 
@@ -241,10 +176,10 @@ mod tests {
         assert_eq!(
             match_set,
             Some(PartSet {
-                x: 1..4001,
-                m: 1000..2000,
-                a: 1..10,
-                s: 1..4001,
+                x: (1..4001).into(),
+                m: (1000..2000).into(),
+                a: (1..10).into(),
+                s: (1..4001).into(),
             })
         );
         assert_eq!(unmatch_set, None);
@@ -253,19 +188,19 @@ mod tests {
         assert_eq!(
             match_set,
             Some(PartSet {
-                x: 1..4001,
-                m: 1000..2000,
-                a: 1..9,
-                s: 1..4001,
+                x: (1..4001).into(),
+                m: (1000..2000).into(),
+                a: (1..9).into(),
+                s: (1..4001).into(),
             })
         );
         assert_eq!(
             unmatch_set,
             Some(PartSet {
-                x: 1..4001,
-                m: 1000..2000,
-                a: 9..10,
-                s: 1..4001,
+                x: (1..4001).into(),
+                m: (1000..2000).into(),
+                a: (9..10).into(),
+                s: (1..4001).into(),
             })
         );
     }
@@ -273,16 +208,16 @@ mod tests {
     #[test]
     fn test_refine_already_disjoint() {
         let set1 = PartSet {
-            x: 1..3,
-            m: 1..3,
-            a: 1..3,
-            s: 1..3,
+            x: (1..3).into(),
+            m: (1..3).into(),
+            a: (1..3).into(),
+            s: (1..3).into(),
         };
         let set2 = PartSet {
-            x: 3..5,
-            m: 3..5,
-            a: 3..5,
-            s: 3..5,
+            x: (3..5).into(),
+            m: (3..5).into(),
+            a: (3..5).into(),
+            s: (3..5).into(),
         };
 
         assert_eq!(set1.refine(&set2), vec![set2]);
@@ -291,16 +226,16 @@ mod tests {
     #[test]
     fn test_refine_not_disjoint() {
         let set1 = PartSet {
-            x: 1..3,
-            m: 1..3,
-            a: 1..3,
-            s: 1..3,
+            x: (1..3).into(),
+            m: (1..3).into(),
+            a: (1..3).into(),
+            s: (1..3).into(),
         };
         let set2 = PartSet {
-            x: 2..4,
-            m: 2..4,
-            a: 2..4,
-            s: 2..4,
+            x: (2..4).into(),
+            m: (2..4).into(),
+            a: (2..4).into(),
+            s: (2..4).into(),
         };
 
         let refinement = set1.refine(&set2);
@@ -308,16 +243,16 @@ mod tests {
         assert_eq!(refinement.iter().map(|set| set.size()).sum::<u64>(), 15);
 
         let set1 = PartSet {
-            x: 2..3,
-            m: 2..3,
-            a: 1..4,
-            s: 1..2,
+            x: (2..3).into(),
+            m: (2..3).into(),
+            a: (1..4).into(),
+            s: (1..2).into(),
         };
         let set2 = PartSet {
-            x: 1..4,
-            m: 1..4,
-            a: 2..3,
-            s: 1..2,
+            x: (1..4).into(),
+            m: (1..4).into(),
+            a: (2..3).into(),
+            s: (1..2).into(),
         };
 
         let refinement = set1.refine(&set2);
@@ -332,16 +267,16 @@ mod tests {
     #[test]
     fn test_refine_proper_subset() {
         let set1 = PartSet {
-            x: 1..5,
-            m: 1..5,
-            a: 1..5,
-            s: 1..5,
+            x: (1..5).into(),
+            m: (1..5).into(),
+            a: (1..5).into(),
+            s: (1..5).into(),
         };
         let set2 = PartSet {
-            x: 2..4,
-            m: 2..4,
-            a: 2..4,
-            s: 2..4,
+            x: (2..4).into(),
+            m: (2..4).into(),
+            a: (2..4).into(),
+            s: (2..4).into(),
         };
 
         let refinement = set1.refine(&set2);
@@ -349,10 +284,10 @@ mod tests {
         assert!(refinement.is_empty());
 
         let set1 = PartSet {
-            x: 1..4000,
-            m: 1..4000,
-            a: 1..2006,
-            s: 1..4000,
+            x: (1..4000).into(),
+            m: (1..4000).into(),
+            a: (1..2006).into(),
+            s: (1..4000).into(),
         };
 
         let refinement = set1.refine(&set1);
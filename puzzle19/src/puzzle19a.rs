@@ -5,14 +5,14 @@ use crate::{Part, System};
 pub struct Puzzle19a {}
 
 impl PuzzlePart for Puzzle19a {
+    type Input<'a> = (System<'a>, Vec<Part>);
+
     fn description() -> &'static str {
         "Find the total ratings of the parts that accepted by a system of workflows."
     }
 
-    fn solve(input: &str) -> String {
-        let (system, parts) = input.split_once("\n\n").unwrap();
-        let system = System::from(system);
-        let parts: Vec<_> = parts.lines().map(Part::from).collect();
+    fn solve(input: &Self::Input<'_>) -> String {
+        let (system, parts) = input;
 
         parts
             .iter()
@@ -1,16 +1,18 @@
 use common::puzzle::PuzzlePart;
 
-use crate::System;
+use crate::{Part, System};
 
 pub struct Puzzle19b {}
 
 impl PuzzlePart for Puzzle19b {
+    type Input<'a> = (System<'a>, Vec<Part>);
+
     fn description() -> &'static str {
         "Find the total number of parts that will be accepted by a system of workflows."
     }
 
-    fn solve(input: &str) -> String {
-        let system = System::from(input.split("\n\n").next().unwrap());
+    fn solve(input: &Self::Input<'_>) -> String {
+        let (system, _) = input;
 
         system.count_accepted_parts().to_string()
     }
@@ -0,0 +1,39 @@
+use crate::Puzzle24;
+use common::{generate::InputGenerator, rng::Rng};
+
+impl InputGenerator for Puzzle24 {
+    /// `size` is the number of hailstones.
+    ///
+    /// Like the real inputs, every hailstone is on a collision course with a single
+    /// rock thrown from some position, each one at a different (positive) time.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let rock_position: Vec<i64> = (0..3)
+            .map(|_| rng.range_i64(100_000_000_000_000..400_000_000_000_000))
+            .collect();
+        let rock_velocity: Vec<i64> = (0..3).map(|_| rng.range_i64(-300..301)).collect();
+
+        let mut times: Vec<i64> = vec![];
+        while times.len() < size {
+            let time = rng.range_i64(10_000_000_000..1_000_000_000_000);
+            if !times.contains(&time) {
+                times.push(time);
+            }
+        }
+
+        times
+            .into_iter()
+            .map(|time| {
+                let velocity: Vec<i64> = (0..3).map(|_| rng.range_i64(-300..301)).collect();
+                // work backwards from where the rock is at the time of the collision
+                let position: Vec<i64> = (0..3)
+                    .map(|axis| rock_position[axis] + time * (rock_velocity[axis] - velocity[axis]))
+                    .collect();
+                format!(
+                    "{}, {}, {} @ {}, {}, {}",
+                    position[0], position[1], position[2], velocity[0], velocity[1], velocity[2]
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
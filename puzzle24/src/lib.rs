@@ -1,44 +1,83 @@
+use common::{
+    num::{BigInt, Rational},
+    puzzle::{InputPrinter, Puzzle, Tag},
+};
+use puzzle24a::Puzzle24a;
+use puzzle24b::Puzzle24b;
+
+pub mod generator;
 pub mod parser;
 pub mod puzzle24a;
 pub mod puzzle24b;
 
-#[derive(Debug)]
-pub struct Vector(f64, f64, f64);
+pub struct Puzzle24 {}
+
+impl Puzzle for Puzzle24 {
+    type Input<'a> = Vec<Path>;
+    type PartA = Puzzle24a;
+    type PartB = Puzzle24b;
+
+    fn name() -> &'static str {
+        "24"
+    }
 
-impl From<(i32, i32, i32)> for Vector {
-    fn from(tuple: (i32, i32, i32)) -> Self {
-        Self(tuple.0 as f64, tuple.1 as f64, tuple.2 as f64)
+    fn title() -> &'static str {
+        "Never Tell Me The Odds"
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Geometry, Tag::Math]
+    }
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input.lines().map(Path::from).collect()
+    }
+}
+
+impl InputPrinter for Puzzle24 {
+    fn print(input: &Self::Input<'_>) -> String {
+        input
+            .iter()
+            .map(Path::to_string)
+            .collect::<Vec<_>>()
+            .join("\n")
     }
 }
 
-const TOLERANCE: f64 = 0.000001;
+/// Three coordinates. Positions and velocities in the puzzle are whole numbers, while the
+/// points paths pass through at fractional times are kept as exact fractions.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Vector<T = i64>(T, T, T);
 
-impl PartialEq for Vector {
-    fn eq(&self, other: &Self) -> bool {
-        (self.0 - other.0).abs() < TOLERANCE
-            && (self.1 - other.1).abs() < TOLERANCE
-            && (self.2 - other.2).abs() < TOLERANCE
+impl From<(i64, i64, i64)> for Vector {
+    fn from(tuple: (i64, i64, i64)) -> Self {
+        Self(tuple.0, tuple.1, tuple.2)
     }
 }
 
-impl Vector {
-    pub fn has_xy_in(&self, min: f64, max: f64) -> bool {
-        self.0 >= min && self.0 <= max && self.1 >= min && self.1 <= max
+impl<T: PartialOrd> Vector<T> {
+    pub fn has_xy_in(&self, min: &T, max: &T) -> bool {
+        self.0 >= *min && self.0 <= *max && self.1 >= *min && self.1 <= *max
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Path {
     start: Vector,
     velocity: Vector,
 }
 
 impl Path {
-    pub fn at(&self, time: f64) -> Vector {
+    pub fn at(&self, time: &Rational) -> Vector<Rational> {
+        let coord = |start: i64, velocity: i64| {
+            Rational::from(start) + time.clone() * Rational::from(velocity)
+        };
         Vector(
-            self.start.0 + time * self.velocity.0,
-            self.start.1 + time * self.velocity.1,
-            self.start.2 + time * self.velocity.2,
+            coord(self.start.0, self.velocity.0),
+            coord(self.start.1, self.velocity.1),
+            coord(self.start.2, self.velocity.2),
         )
     }
 
@@ -61,22 +100,23 @@ impl Path {
     //   |  t | = (1/D) | d.y -d.x | | c.x - a.x |
     //   | - s|         | -b.y b.x | | c.y - a.y |
     // Now just expand that stuff to find t and s:
-    //   t = (1/D) ( (d.y)(c.x - a.x) - (d.x)(c.y - b.y) )
-    //   s = (1/D) ( (b.y)(c.x - a.x) - (b.x)(c.y - b.y) )
+    //   t = (1/D) ( (d.y)(c.x - a.x) - (d.x)(c.y - a.y) )
+    //   s = (1/D) ( (b.y)(c.x - a.x) - (b.x)(c.y - a.y) )
     // Tada! Plug t into the equation for the path p1 to find the intersection location, if desired.
-    pub fn intersection_times(&self, other: &Path) -> Option<(f64, f64)> {
-        let det = self.velocity.0 * other.velocity.1 - other.velocity.0 * self.velocity.1;
-        if det == 0.0 {
-            None
-        } else {
-            let t = (1.0 / det)
-                * ((other.velocity.1) * (other.start.0 - self.start.0)
-                    - (other.velocity.0) * (other.start.1 - self.start.1));
-            let s = (1.0 / det)
-                * ((self.velocity.1) * (other.start.0 - self.start.0)
-                    - (self.velocity.0) * (other.start.1 - self.start.1));
-            Some((t, s))
+    // The coordinates are around 4e14, where floats can only tell apart values about a
+    // sixteenth apart, so all of this is done with exact fractions instead.
+    pub fn intersection_times(&self, other: &Path) -> Option<(Rational, Rational)> {
+        let big = BigInt::from;
+        let (a, b, c, d) = (&self.start, &self.velocity, &other.start, &other.velocity);
+
+        let det = big(b.0) * big(d.1) - big(d.0) * big(b.1);
+        if det == big(0) {
+            return None;
         }
+        let (dx, dy) = (big(c.0) - big(a.0), big(c.1) - big(a.1));
+        let t = Rational::new(big(d.1) * dx.clone() - big(d.0) * dy.clone(), det.clone());
+        let s = Rational::new(big(b.1) * dx - big(b.0) * dy, det);
+        Some((t, s))
     }
 }
 
@@ -97,11 +137,11 @@ mod tests {
 
         let (t1, t2) = path1.intersection_times(&path2).unwrap();
 
-        let p1 = path1.at(t1);
-        let p2 = path2.at(t2);
+        let p1 = path1.at(&t1);
+        let p2 = path2.at(&t2);
 
-        assert!((p1.0 - p2.0).abs() < TOLERANCE);
-        assert!((p1.1 - p2.1).abs() < TOLERANCE);
+        assert_eq!(p1.to_string(), "31/5, 97/5, 86/5");
+        assert_eq!((p1.0, p1.1), (p2.0, p2.1));
     }
 
     #[test]
@@ -1,12 +1,13 @@
 use crate::{Path, Vector};
+use std::fmt::Display;
 
 impl From<&str> for Vector {
     fn from(input: &str) -> Self {
         let mut values = input.split(',').map(|s| s.trim().parse::<i64>().unwrap());
         Self(
-            values.next().unwrap() as f64,
-            values.next().unwrap() as f64,
-            values.next().unwrap() as f64,
+            values.next().unwrap(),
+            values.next().unwrap(),
+            values.next().unwrap(),
         )
     }
 }
@@ -21,6 +22,18 @@ impl From<&str> for Path {
     }
 }
 
+impl<T: Display> Display for Vector<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}, {}, {}", self.0, self.1, self.2)
+    }
+}
+
+impl Display for Path {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} @ {}", self.start, self.velocity)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -30,7 +43,7 @@ mod tests {
         let input = "1, -2, 3";
 
         let vector = Vector::from(input);
-        assert_eq!(vector, Vector(1.0, -2.0, 3.0));
+        assert_eq!(vector, Vector(1, -2, 3));
     }
 
     #[test]
@@ -41,9 +54,15 @@ mod tests {
         assert_eq!(
             path,
             Path {
-                start: Vector(19.0, 13.0, 30.0),
-                velocity: Vector(-2.0, 1.0, -2.0),
+                start: Vector(19, 13, 30),
+                velocity: Vector(-2, 1, -2),
             }
         );
     }
+
+    #[test]
+    fn test_display_path() {
+        let input = "19, 13, 30 @ -2, 1, -2";
+        assert_eq!(Path::from(input).to_string(), input);
+    }
 }
@@ -1,21 +1,24 @@
-use common::puzzle::PuzzlePart;
+use common::{num::Rational, puzzle::PuzzlePart};
 
 use crate::Path;
 
 pub struct Puzzle24a {}
 
-// const MIN_COORD: f64 = 7.0;
-// const MAX_COORD: f64 = 27.0;
-const MIN_COORD: f64 = 200000000000000.0;
-const MAX_COORD: f64 = 400000000000000.0;
+// const MIN_COORD: i64 = 7;
+// const MAX_COORD: i64 = 27;
+const MIN_COORD: i64 = 200000000000000;
+const MAX_COORD: i64 = 400000000000000;
 
 impl PuzzlePart for Puzzle24a {
+    type Input<'a> = Vec<Path>;
+
     fn description() -> &'static str {
         "Count the number of pairs of paths that whose projections to the xy-plane intersect in a certain area."
     }
 
-    fn solve(input: &str) -> String {
-        let paths: Vec<_> = input.lines().map(Path::from).collect();
+    fn solve(input: &Self::Input<'_>) -> String {
+        let paths = input;
+        let (min, max) = (Rational::from(MIN_COORD), Rational::from(MAX_COORD));
 
         paths
             .iter()
@@ -23,10 +26,10 @@ impl PuzzlePart for Puzzle24a {
             .flat_map(|(idx, path1)| {
                 paths[idx + 1..].iter().filter(|path2| {
                     path1.intersection_times(path2).is_some_and(|(t1, t2)| {
-                        t1 > 0.0
-                            && t2 > 0.0
-                            && path1.at(t1).has_xy_in(MIN_COORD, MAX_COORD)
-                            && path2.at(t2).has_xy_in(MIN_COORD, MAX_COORD)
+                        t1.is_positive()
+                            && t2.is_positive()
+                            && path1.at(&t1).has_xy_in(&min, &max)
+                            && path2.at(&t2).has_xy_in(&min, &max)
                     })
                 })
             })
@@ -1,13 +1,20 @@
-use common::puzzle::PuzzlePart;
+use crate::Path;
+use common::puzzle::{PuzzlePart, Status};
 
 pub struct Puzzle24b {}
 
 impl PuzzlePart for Puzzle24b {
+    type Input<'a> = Vec<Path>;
+
     fn description() -> &'static str {
-        todo!()
+        "Find the sum of the coordinates of the starting position of a rock that hits every hailstone."
+    }
+
+    fn status() -> Status {
+        Status::Todo
     }
 
-    fn solve(_input: &str) -> String {
+    fn solve(_input: &Self::Input<'_>) -> String {
         todo!()
     }
 }
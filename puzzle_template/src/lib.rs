@@ -1,2 +1,26 @@
+use common::puzzle::Puzzle;
+use puzzle00a::Puzzle00a;
+use puzzle00b::Puzzle00b;
+
 pub mod puzzle00a;
 pub mod puzzle00b;
+
+pub struct Puzzle00 {}
+
+impl Puzzle for Puzzle00 {
+    type Input<'a> = &'a str;
+    type PartA = Puzzle00a;
+    type PartB = Puzzle00b;
+
+    fn name() -> &'static str {
+        todo!()
+    }
+
+    fn title() -> &'static str {
+        todo!()
+    }
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input
+    }
+}
@@ -1,13 +1,19 @@
-use common::puzzle::PuzzlePart;
+use common::puzzle::{PuzzlePart, Status};
 
 pub struct Puzzle00b {}
 
 impl PuzzlePart for Puzzle00b {
+    type Input<'a> = &'a str;
+
     fn description() -> &'static str {
         todo!()
     }
 
-    fn solve(_input: &str) -> String {
+    fn status() -> Status {
+        Status::Todo
+    }
+
+    fn solve(_input: &Self::Input<'_>) -> String {
         todo!()
     }
 }